@@ -1,5 +1,7 @@
+use crate::utils::compress;
+use crate::utils::encoding::TextEncoding;
+use crate::utils::error::RsvResult;
 use crate::utils::return_result::{CliResultData, ResultData};
-use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
@@ -16,10 +18,10 @@ pub fn csv_count(file: &Path, no_header: bool) -> CliResultData {
     }))
 }
 
-fn count_file_lines(path: &Path, no_header: bool) -> Result<usize, Box<dyn std::error::Error>> {
+fn count_file_lines(path: &Path, no_header: bool) -> RsvResult<usize> {
     // open file and count
     let mut n = 0;
-    let file = File::open(path)?;
+    let file = compress::open(path, TextEncoding::Auto)?;
     let mut rdr = BufReader::with_capacity(1024 * 32, file);
     loop {
         let bytes_read = {
@@ -41,7 +43,7 @@ fn count_file_lines(path: &Path, no_header: bool) -> Result<usize, Box<dyn std::
     Ok(n)
 }
 
-fn count_dir_files(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+fn count_dir_files(path: &Path) -> RsvResult<usize> {
     let mut file_n = 0;
     let mut dir_n = 0;
 
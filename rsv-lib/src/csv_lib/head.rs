@@ -1,7 +1,9 @@
+use crate::utils::compress;
+use crate::utils::encoding::TextEncoding;
+use crate::utils::reader::RecordReader;
 use crate::utils::return_result::{CliResultData, ResultData};
 use crate::utils::row_split::CsvRowSplitter;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::path::PathBuf;
 
 pub fn csv_head(
@@ -14,9 +16,12 @@ pub fn csv_head(
     let mut out = ResultData::new();
 
     // show head n
-    let mut lines = BufReader::new(File::open(file)?)
-        .lines()
-        .take(n + 1 - no_header as usize);
+    let mut lines = RecordReader::new(
+        BufReader::new(compress::open(file, TextEncoding::Auto)?),
+        sep,
+        quote,
+    )
+    .take(n + 1 - no_header as usize);
 
     // Process header
     if let Some(Ok(h)) = lines.next() {
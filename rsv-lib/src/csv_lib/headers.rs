@@ -1,14 +1,20 @@
+use crate::utils::compress;
+use crate::utils::encoding::TextEncoding;
+use crate::utils::reader::RecordReader;
 use crate::utils::return_result::{CliResultData, ResultData};
 use crate::utils::row_split::CsvRowSplitter;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::path::PathBuf;
 
 pub fn csv_headers(file: &PathBuf, sep: char, quote: char) -> CliResultData {
     let mut out = ResultData::new();
 
     // open file and header
-    let mut rdr = BufReader::new(File::open(file)?).lines();
+    let mut rdr = RecordReader::new(
+        BufReader::new(compress::open(file, TextEncoding::Auto)?),
+        sep,
+        quote,
+    );
 
     out.insert_header(vec!["column_name".to_string()]);
     if let Some(r) = rdr.next() {
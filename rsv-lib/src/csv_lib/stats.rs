@@ -2,6 +2,7 @@ use crate::utils::chunk::{ChunkParser, ChunkResult};
 use crate::utils::column::Columns;
 use crate::utils::column_stats::{CStat, ColumnStats};
 use crate::utils::column_type::ColumnTypes;
+use crate::utils::encoding::TextEncoding;
 use crate::utils::file::column_n;
 use crate::utils::progress::Progress;
 use crate::utils::reader::ChunkReader;
@@ -10,6 +11,7 @@ use crate::utils::row_split::CsvRowSplitter;
 use crossbeam_channel::{bounded, unbounded};
 use rayon::ThreadPoolBuilder;
 use std::path::Path;
+use std::thread;
 
 pub fn csv_stats(
     file: &Path,
@@ -24,20 +26,28 @@ pub fn csv_stats(
 
     // Column
     let cols = Columns::new(cols.as_str())
-        .total_col_of(file, sep, quote)
-        .parse();
-    let Some(col_type) =
-        ColumnTypes::guess_from_csv(file, sep, quote, no_header, &cols, text_columns, &[])?
+        .total_col_of(file, sep, quote, TextEncoding::Auto)
+        .parse()?;
+    let Some(col_type) = ColumnTypes::guess_from_csv(
+        file,
+        sep,
+        quote,
+        TextEncoding::Auto,
+        no_header,
+        &cols,
+        text_columns,
+        &[],
+    )?
     else {
         return Ok(Some(result_data));
     };
 
     // open file
-    let mut rdr = ChunkReader::new(file)?;
+    let mut rdr = ChunkReader::new(file, sep, quote, TextEncoding::Auto)?;
 
     // header
     let name = if no_header {
-        let Some(n) = column_n(file, sep, quote)? else {
+        let Some(n) = column_n(file, sep, quote, TextEncoding::Auto)? else {
             return Ok(Some(result_data));
         };
         cols.artificial_n_cols(n)
@@ -45,9 +55,7 @@ pub fn csv_stats(
         let Some(r) = rdr.next() else {
             return Ok(Some(result_data));
         };
-        CsvRowSplitter::new(&r?, sep, quote)
-            .map(|i| i.to_owned())
-            .collect::<Vec<_>>()
+        CsvRowSplitter::new(&r?, sep, quote).collect_owned()
     };
 
     // stats holder
@@ -68,7 +76,7 @@ pub fn csv_stats(
     let pool = ThreadPoolBuilder::new().build().unwrap();
 
     // read
-    pool.spawn(move || rdr.send_to_channel_by_chunks(tx_chunk, 50_000));
+    let reader = thread::spawn(move || rdr.send_to_channel_by_chunks(tx_chunk, 50_000));
 
     // parallel process
     pool.scope(|s| {
@@ -95,6 +103,7 @@ pub fn csv_stats(
 
         prog.clear();
     });
+    reader.join().unwrap()?;
 
     // refine result
     stat.cal_unique_and_mean();
@@ -13,9 +13,11 @@ pub fn excel_stats(file: &Path, no_header: bool, cols: String, sheet: usize) ->
 
     // Column type
     let cols = Columns::new(cols.as_str())
-        .total_col(rdr.column_n())
-        .parse();
-    let col_type = ColumnTypes::guess_from_excel(&rdr, no_header, &cols).unwrap();
+        .header(rdr.header_names())
+        .parse()?;
+    let Some(col_type) = ColumnTypes::guess_from_excel(&rdr, no_header, &cols) else {
+        return Ok(Some(result_data));
+    };
 
     // header
     let name = match no_header {
@@ -1,19 +1,27 @@
 mod csv_lib;
 mod excel_lib;
 mod general_lib;
+mod source_lib;
 pub mod utils;
 
+pub use crate::utils::error::{RsvError, RsvResult};
 pub use crate::utils::return_result::ResultData;
-use crate::utils::{file::is_excel, filename::full_path, return_result::CliResultData};
+use crate::utils::{file::is_sheet, filename::full_path, return_result::CliResultData};
 // general
 pub use general_lib::size::file_size;
 
+// builders of other operations, for all of CSV, TXT, EXCEL, Parquet and JSON files
+pub use source_lib::{
+    frequency::Frequency, sample::Sample, search::Search, select::Select, slice::Slice, sort::Sort,
+    split::Split, unique::Unique,
+};
+
 // count
 use csv_lib::count::csv_count;
 use excel_lib::count::excel_count;
 pub fn file_count(file: &str, no_header: bool, sheet: usize) -> CliResultData {
     let path = full_path(file);
-    match is_excel(&path) {
+    match is_sheet(&path) {
         true => excel_count(&path, no_header, sheet),
         false => csv_count(&path, no_header),
     }
@@ -31,7 +39,7 @@ pub fn file_head(
     n: usize,
 ) -> CliResultData {
     let path = full_path(file);
-    match is_excel(&path) {
+    match is_sheet(&path) {
         true => excel_head(&path, no_header, sheet, n),
         false => csv_head(&path, no_header, sep, quote, n),
     }
@@ -42,7 +50,7 @@ use csv_lib::headers::csv_headers;
 use excel_lib::headers::excel_headers;
 pub fn file_headers(file: &str, sep: char, quote: char, sheet: usize) -> CliResultData {
     let path = full_path(file);
-    match is_excel(&path) {
+    match is_sheet(&path) {
         true => excel_headers(&path, sheet),
         false => csv_headers(&path, sep, quote),
     }
@@ -61,7 +69,7 @@ pub fn file_stats(
     text_columns: &[usize],
 ) -> CliResultData {
     let path = full_path(file);
-    match is_excel(&path) {
+    match is_sheet(&path) {
         true => excel_stats(&path, no_header, cols, sheet),
         false => csv_stats(&path, sep, quote, no_header, cols, text_columns),
     }
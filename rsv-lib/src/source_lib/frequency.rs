@@ -0,0 +1,88 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, return_result::ResultData, util::sort_frequency};
+use ahash::HashMap;
+use rayon::prelude::*;
+
+/// Frequency table of column(s), e.g., `Frequency::new("data.csv").cols("0,1").top(10).run()`.
+/// The result has the selected columns and a count column named n, in descending
+/// order of counts by default. All columns are counted when no column is given.
+#[derive(Debug, Clone)]
+pub struct Frequency {
+    input: Input,
+    cols: String,
+    ascending: bool,
+    top: Option<usize>,
+}
+
+impl_input_options!(Frequency);
+
+impl Frequency {
+    pub fn new(file: &str) -> Self {
+        Frequency {
+            input: Input::new(file),
+            cols: String::new(),
+            ascending: false,
+            top: None,
+        }
+    }
+
+    pub fn cols(mut self, cols: &str) -> Self {
+        self.cols = cols.to_owned();
+        self
+    }
+
+    pub fn ascending(mut self, ascending: bool) -> Self {
+        self.ascending = ascending;
+        self
+    }
+
+    /// Keep the first n values only.
+    pub fn top(mut self, n: usize) -> Self {
+        self.top = Some(n);
+        self
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut rows = self.input.open()?;
+        let cols = rows.columns(&self.cols)?;
+
+        let mut freq = HashMap::default();
+        loop {
+            let chunk = rows.next_chunk()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let counts = chunk
+                .into_par_iter()
+                .fold(HashMap::default, |mut m, r| {
+                    let k = match cols.select_all {
+                        true => r,
+                        false => cols.select_owned_vector(&r),
+                    };
+                    *m.entry(k).or_insert(0) += 1;
+                    m
+                })
+                .collect::<Vec<HashMap<_, usize>>>();
+            for m in counts {
+                m.into_iter()
+                    .for_each(|(k, n)| *freq.entry(k).or_insert(0) += n);
+            }
+        }
+
+        let mut freq = freq.into_iter().collect::<Vec<_>>();
+        sort_frequency(&mut freq, self.ascending, self.top);
+
+        let mut header = rows.selected_header(&cols);
+        header.push("n".to_owned());
+        let data = freq
+            .into_iter()
+            .map(|(mut k, n)| {
+                k.push(n.to_string());
+                k
+            })
+            .collect();
+
+        Ok(ResultData::from(header, data))
+    }
+}
@@ -0,0 +1,149 @@
+pub mod frequency;
+pub mod sample;
+pub mod search;
+pub mod select;
+pub mod slice;
+pub mod sort;
+pub mod split;
+pub mod unique;
+
+use crate::utils::{
+    column::Columns,
+    encoding::TextEncoding,
+    error::{RsvError, RsvResult},
+    filename::full_path,
+    source::{self, RecordSource},
+    writer::Dialect,
+};
+use std::path::PathBuf;
+
+const CHUNK_SIZE: usize = 10_000;
+
+// The file to read and how to read it, shared by all operations. A CSV or TXT
+// file, and an EXCEL, Parquet or JSON file read as a sheet, are all supported.
+#[derive(Debug, Clone)]
+struct Input {
+    path: PathBuf,
+    sep: char,
+    quote: char,
+    enc: TextEncoding,
+    sheet: usize,
+    no_header: bool,
+}
+
+impl Input {
+    fn new(file: &str) -> Self {
+        Input {
+            path: full_path(file),
+            sep: ',',
+            quote: '"',
+            enc: TextEncoding::Auto,
+            sheet: 0,
+            no_header: false,
+        }
+    }
+
+    fn open(&self) -> RsvResult<Rows> {
+        let mut src = source::open(Some(&self.path), self.sep, self.quote, self.sheet, self.enc)?;
+
+        // artificial names, i.e., col0, col1..., when there is no header
+        let (header, first) = match (self.no_header, src.next_row()) {
+            (_, None) => (vec![], None),
+            (false, Some(r)) => (r?, None),
+            (true, Some(r)) => {
+                let r = r?;
+                ((0..r.len()).map(|i| format!("col{i}")).collect(), Some(r))
+            }
+        };
+
+        Ok(Rows { src, first, header })
+    }
+}
+
+// Setters of reading options for an operation with an <input> field.
+macro_rules! impl_input_options {
+    ($op:ident) => {
+        impl $op {
+            /// Field separator of CSV and TXT files, default to a comma.
+            pub fn sep(mut self, sep: char) -> Self {
+                self.input.sep = sep;
+                self
+            }
+
+            /// Quote char of CSV and TXT files, default to a double-quote.
+            pub fn quote(mut self, quote: char) -> Self {
+                self.input.quote = quote;
+                self
+            }
+
+            /// Encoding of CSV and TXT files, e.g., gbk, default to auto detection.
+            pub fn encoding(mut self, enc: $crate::utils::encoding::TextEncoding) -> Self {
+                self.input.enc = enc;
+                self
+            }
+
+            /// The nth worksheet of an EXCEL file, default to the first one.
+            pub fn sheet(mut self, sheet: usize) -> Self {
+                self.input.sheet = sheet;
+                self
+            }
+
+            /// Whether the file has no header, in which case columns are named col0, col1...
+            pub fn no_header(mut self, no_header: bool) -> Self {
+                self.input.no_header = no_header;
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use impl_input_options;
+
+// Data rows of an input, after the header.
+struct Rows {
+    src: Box<dyn RecordSource>,
+    first: Option<Vec<String>>,
+    header: Vec<String>,
+}
+
+impl Rows {
+    fn next_row(&mut self) -> Option<RsvResult<Vec<String>>> {
+        match self.first.take() {
+            Some(r) => Some(Ok(r)),
+            None => self.src.next_row().map(|r| r.map_err(RsvError::from)),
+        }
+    }
+
+    // an empty chunk at the end of the input
+    fn next_chunk(&mut self) -> RsvResult<Vec<Vec<String>>> {
+        let mut rows = Vec::with_capacity(CHUNK_SIZE);
+        rows.extend(self.first.take());
+        rows.extend(self.src.next_chunk(CHUNK_SIZE - rows.len())?);
+
+        Ok(rows)
+    }
+
+    fn columns<'a>(&self, spec: &'a str) -> RsvResult<Columns<'a>> {
+        let cols = Columns::new(spec).header(self.header.clone()).parse()?;
+        if !cols.select_all && cols.max >= self.header.len() {
+            return Err(RsvError::ColumnOutOfRange {
+                col: cols.max,
+                total: self.header.len(),
+            });
+        }
+
+        Ok(cols)
+    }
+
+    fn selected_header(&self, cols: &Columns) -> Vec<String> {
+        match cols.select_all {
+            true => self.header.clone(),
+            false => cols.select_owned_vector(&self.header),
+        }
+    }
+
+    // output rows in the separator and quote char of the input, quoted as needed
+    fn dialect(&self) -> Dialect {
+        Dialect::input(self.src.sep(), self.src.quote())
+    }
+}
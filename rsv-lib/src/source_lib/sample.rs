@@ -0,0 +1,46 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, priority_queue::Sampler, return_result::ResultData};
+
+/// Random sample of n rows by a priority queue, e.g.,
+/// `Sample::new("data.csv", 10).seed(1).run()`. Sampled rows keep their order
+/// in the file.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    input: Input,
+    n: usize,
+    seed: Option<u64>,
+}
+
+impl_input_options!(Sample);
+
+impl Sample {
+    pub fn new(file: &str, n: usize) -> Self {
+        Sample {
+            input: Input::new(file),
+            n,
+            seed: None,
+        }
+    }
+
+    /// Seed of the random generator, so that the sample is reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut rows = self.input.open()?;
+        let mut sampler = Sampler::new(self.n, self.seed);
+        while let Some(r) = rows.next_row() {
+            sampler.push(r?);
+        }
+
+        let data = sampler
+            .into_sorted_items()
+            .into_iter()
+            .map(|i| i.item)
+            .collect();
+
+        Ok(ResultData::from(rows.header, data))
+    }
+}
@@ -0,0 +1,73 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, regex::Re, return_result::ResultData};
+use rayon::prelude::*;
+
+/// Search rows with a regex, e.g.,
+/// `Search::new("data.csv", "^a").cols("name").out("id,name").run()`.
+/// All columns are searched and kept by default.
+#[derive(Debug, Clone)]
+pub struct Search {
+    input: Input,
+    pattern: String,
+    cols: String,
+    out: String,
+}
+
+impl_input_options!(Search);
+
+impl Search {
+    pub fn new(file: &str, pattern: &str) -> Self {
+        Search {
+            input: Input::new(file),
+            pattern: pattern.to_owned(),
+            cols: String::new(),
+            out: String::new(),
+        }
+    }
+
+    /// Columns to search in.
+    pub fn cols(mut self, cols: &str) -> Self {
+        self.cols = cols.to_owned();
+        self
+    }
+
+    /// Columns of matched rows to keep.
+    pub fn out(mut self, out: &str) -> Self {
+        self.out = out.to_owned();
+        self
+    }
+
+    /// Stream the matched rows to a callback, and return the selected header.
+    pub fn for_each<F: FnMut(Vec<String>)>(&self, mut f: F) -> RsvResult<Vec<String>> {
+        let re = Re::new(&self.pattern)?;
+        let mut rows = self.input.open()?;
+        let cols = rows.columns(&self.cols)?;
+        let out = rows.columns(&self.out)?;
+
+        loop {
+            let chunk = rows.next_chunk()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let matched = chunk
+                .into_par_iter()
+                .filter(|r| re.is_match_in(r, &cols))
+                .map(|r| match out.select_all {
+                    true => r,
+                    false => out.select_owned_vector(&r),
+                })
+                .collect::<Vec<_>>();
+            matched.into_iter().for_each(&mut f);
+        }
+
+        Ok(rows.selected_header(&out))
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut data = vec![];
+        let header = self.for_each(|r| data.push(r))?;
+
+        Ok(ResultData::from(header, data))
+    }
+}
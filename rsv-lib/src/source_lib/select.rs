@@ -0,0 +1,72 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, filter::Filter, return_result::ResultData};
+use rayon::prelude::*;
+
+/// Select rows by a filter and columns by a column spec, e.g.,
+/// `Select::new("data.csv").filter("0=a,b").cols("0,2").run()`.
+/// An empty filter keeps all rows and an empty column spec keeps all columns.
+#[derive(Debug, Clone)]
+pub struct Select {
+    input: Input,
+    cols: String,
+    filter: String,
+}
+
+impl_input_options!(Select);
+
+impl Select {
+    pub fn new(file: &str) -> Self {
+        Select {
+            input: Input::new(file),
+            cols: String::new(),
+            filter: String::new(),
+        }
+    }
+
+    /// Columns to keep, e.g., 0,1-3 or id,amount.
+    pub fn cols(mut self, cols: &str) -> Self {
+        self.cols = cols.to_owned();
+        self
+    }
+
+    /// Filter of rows, e.g., 0=a,b&2N>10. Run <rsv select -h> for the syntax.
+    pub fn filter(mut self, filter: &str) -> Self {
+        self.filter = filter.to_owned();
+        self
+    }
+
+    /// Stream the selected rows to a callback, and return the selected header.
+    pub fn for_each<F: FnMut(Vec<String>)>(&self, mut f: F) -> RsvResult<Vec<String>> {
+        let mut rows = self.input.open()?;
+        let cols = rows.columns(&self.cols)?;
+        let filter = Filter::new(&self.filter)
+            .header(rows.header.clone())
+            .parse()?;
+
+        loop {
+            let chunk = rows.next_chunk()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let selected = chunk
+                .into_par_iter()
+                .filter(|r| filter.record_is_valid(r))
+                .map(|r| match cols.select_all {
+                    true => r,
+                    false => cols.select_owned_vector(&r),
+                })
+                .collect::<Vec<_>>();
+            selected.into_iter().for_each(&mut f);
+        }
+
+        Ok(rows.selected_header(&cols))
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut data = vec![];
+        let header = self.for_each(|r| data.push(r))?;
+
+        Ok(ResultData::from(header, data))
+    }
+}
@@ -0,0 +1,74 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, return_result::ResultData, util::slice_range};
+
+/// A slice of rows in [start, end), or a single row by index, e.g.,
+/// `Slice::new("data.csv").start(10).length(5).run()`. Reading stops at the end
+/// of the slice.
+#[derive(Debug, Clone)]
+pub struct Slice {
+    input: Input,
+    start: usize,
+    end: Option<usize>,
+    length: Option<usize>,
+    index: Option<usize>,
+}
+
+impl_input_options!(Slice);
+
+impl Slice {
+    pub fn new(file: &str) -> Self {
+        Slice {
+            input: Input::new(file),
+            start: 0,
+            end: None,
+            length: None,
+            index: None,
+        }
+    }
+
+    pub fn start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// End index, exclusive.
+    pub fn end(mut self, end: usize) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Length of the slice, i.e., the end index is start + len, when no end is given.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// A single row, in place of start and end.
+    pub fn index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Stream the rows of the slice to a callback, and return the header.
+    pub fn for_each<F: FnMut(Vec<String>)>(&self, mut f: F) -> RsvResult<Vec<String>> {
+        let (start, end) = slice_range(self.start, self.end, self.length, self.index)?;
+        let mut rows = self.input.open()?;
+
+        for i in 0..end {
+            let Some(r) = rows.next_row() else { break };
+            let r = r?;
+            if i >= start {
+                f(r)
+            }
+        }
+
+        Ok(rows.header)
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut data = vec![];
+        let header = self.for_each(|r| data.push(r))?;
+
+        Ok(ResultData::from(header, data))
+    }
+}
@@ -0,0 +1,40 @@
+use super::{Input, impl_input_options};
+use crate::utils::{
+    column::ColumnResolver, error::RsvResult, return_result::ResultData, sort::SortColumns,
+};
+
+/// Sort rows in memory by column(s) with flags, e.g.,
+/// `Sort::new("data.csv", "regionD,amountN").run()`. Run <rsv sort -h> for the flags.
+#[derive(Debug, Clone)]
+pub struct Sort {
+    input: Input,
+    cols: String,
+}
+
+impl_input_options!(Sort);
+
+impl Sort {
+    pub fn new(file: &str, cols: &str) -> Self {
+        Sort {
+            input: Input::new(file),
+            cols: cols.to_owned(),
+        }
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut rows = self.input.open()?;
+        let mut resolver = ColumnResolver::from_names(rows.header.clone());
+        let sort = SortColumns::from(&self.cols, &mut resolver)?;
+
+        let mut data = vec![];
+        loop {
+            let chunk = rows.next_chunk()?;
+            if chunk.is_empty() {
+                break;
+            }
+            data.extend(chunk);
+        }
+
+        Ok(ResultData::from(rows.header, sort.sort_rows(data)))
+    }
+}
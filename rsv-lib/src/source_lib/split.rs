@@ -0,0 +1,128 @@
+use super::{Input, impl_input_options};
+use crate::utils::{
+    column::ColumnResolver,
+    error::{RsvError, RsvResult},
+    filename::{dir_file, split_chunk_file, split_dir, split_value_file},
+    return_result::ResultData,
+    writer::Writer,
+};
+use ahash::HashMap;
+use std::{
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+};
+
+/// Split a file into files by values of a column, or sequentially by a number
+/// of rows, e.g., `Split::new("data.csv").col("region").run()`. Files are saved
+/// to a new directory next to the file by default, each with the header, and
+/// the result lists the files and their numbers of rows.
+#[derive(Debug, Clone)]
+pub struct Split {
+    input: Input,
+    col: String,
+    size: Option<usize>,
+    dir: Option<PathBuf>,
+}
+
+impl_input_options!(Split);
+
+impl Split {
+    pub fn new(file: &str) -> Self {
+        Split {
+            input: Input::new(file),
+            col: "0".to_owned(),
+            size: None,
+            dir: None,
+        }
+    }
+
+    /// Column to split upon, default to the first column.
+    pub fn col(mut self, col: &str) -> Self {
+        self.col = col.to_owned();
+        self
+    }
+
+    /// Split sequentially, with n rows in each file.
+    pub fn size(mut self, n: usize) -> Self {
+        self.size = Some(n);
+        self
+    }
+
+    /// Directory to save files to.
+    pub fn dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        if self.size == Some(0) {
+            return Err(RsvError::InvalidArgument(
+                "size should be bigger than 0.".to_owned(),
+            ));
+        }
+
+        let path = &self.input.path;
+        let dir = self.dir.clone().unwrap_or_else(|| split_dir(Some(path)));
+        create_dir_all(&dir)?;
+
+        let mut rows = self.input.open()?;
+        let col = match self.size {
+            Some(_) => 0,
+            None => {
+                let mut resolver = ColumnResolver::from_names(rows.header.clone());
+                let col = resolver.resolve_one(&self.col)?;
+                if col >= rows.header.len() {
+                    return Err(RsvError::ColumnOutOfRange {
+                        col,
+                        total: rows.header.len(),
+                    });
+                }
+                col
+            }
+        };
+        let dialect = rows.dialect();
+
+        // files and their numbers of rows, appended chunk by chunk
+        let mut files: HashMap<String, usize> = HashMap::default();
+        let mut n = 0;
+        loop {
+            let chunk = rows.next_chunk()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let mut groups: HashMap<String, Vec<Vec<String>>> = HashMap::default();
+            for r in chunk {
+                let name = match self.size {
+                    Some(size) => split_chunk_file(Some(path), n / size + 1, ""),
+                    None => split_value_file(r.get(col).map_or("", |f| f.as_str()), ""),
+                };
+                groups.entry(name).or_default().push(r);
+                n += 1;
+            }
+
+            for (name, lines) in groups {
+                let mut wtr = Writer::append_to(&dir_file(&dir, &name))?.dialect(dialect.clone());
+                let count = files.entry(name).or_insert(0);
+                if *count == 0 && !self.input.no_header {
+                    wtr.write_fields(&rows.header)?;
+                }
+                for r in &lines {
+                    wtr.write_fields(r)?;
+                }
+                *count += lines.len();
+            }
+        }
+
+        let mut data = files
+            .into_iter()
+            .map(|(f, n)| vec![dir_file(&dir, &f).display().to_string(), n.to_string()])
+            .collect::<Vec<_>>();
+        data.sort();
+
+        Ok(ResultData::from(
+            vec!["file".to_owned(), "rows".to_owned()],
+            data,
+        ))
+    }
+}
@@ -0,0 +1,54 @@
+use super::{Input, impl_input_options};
+use crate::utils::{error::RsvResult, return_result::ResultData, unique::UniqueRows};
+
+/// Drop duplicated rows, e.g., `Unique::new("data.csv").cols("id").keep_last(true).run()`.
+/// Rows are compared by the given columns, or by all columns when no column is
+/// given. The first row of duplicates is kept by default.
+#[derive(Debug, Clone)]
+pub struct Unique {
+    input: Input,
+    cols: String,
+    keep_last: bool,
+}
+
+impl_input_options!(Unique);
+
+impl Unique {
+    pub fn new(file: &str) -> Self {
+        Unique {
+            input: Input::new(file),
+            cols: String::new(),
+            keep_last: false,
+        }
+    }
+
+    pub fn cols(mut self, cols: &str) -> Self {
+        self.cols = cols.to_owned();
+        self
+    }
+
+    /// Keep the last row of duplicates, in the order of their last positions.
+    pub fn keep_last(mut self, keep_last: bool) -> Self {
+        self.keep_last = keep_last;
+        self
+    }
+
+    pub fn run(&self) -> RsvResult<ResultData> {
+        let mut rows = self.input.open()?;
+        let cols = rows.columns(&self.cols)?;
+        let key = |r: &Vec<String>| match cols.select_all {
+            true => r.clone(),
+            false => cols.select_owned_vector(r),
+        };
+
+        let mut unique = UniqueRows::new(self.keep_last);
+        let mut data = vec![];
+        while let Some(r) = rows.next_row() {
+            let r = r?;
+            data.extend(unique.push(key(&r), r));
+        }
+        data.extend(unique.finish());
+
+        Ok(ResultData::from(rows.header, data))
+    }
+}
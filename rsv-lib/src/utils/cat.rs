@@ -0,0 +1,84 @@
+use ahash::HashMap;
+
+/// Align columns of files by header names, where the output header is the
+/// union of headers in order of first appearance, and a column missing in a
+/// file is filled with empty values. A name repeated in a file is aligned by
+/// position, i.e., its nth column goes to the nth column of the name.
+pub struct ColumnAligner {
+    names: Vec<String>,
+    // position in a file of each output column
+    maps: Vec<Vec<Option<usize>>>,
+}
+
+impl ColumnAligner {
+    pub fn new(headers: &[Vec<String>]) -> Self {
+        let mut names: Vec<String> = vec![];
+        // output column of each name and its occurrence in a file
+        let mut index = HashMap::default();
+        let occurrences = headers
+            .iter()
+            .map(|h| {
+                let mut seen = HashMap::default();
+                h.iter()
+                    .map(|name| {
+                        let n = seen.entry(name).or_insert(0);
+                        *n += 1;
+                        let key = (name.to_owned(), *n);
+                        if !index.contains_key(&key) {
+                            index.insert(key.clone(), names.len());
+                            names.push(name.to_owned());
+                        }
+                        key
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let maps = occurrences
+            .iter()
+            .map(|keys| {
+                let mut m = vec![None; names.len()];
+                keys.iter().enumerate().for_each(|(i, key)| {
+                    m[index[key]] = Some(i);
+                });
+                m
+            })
+            .collect();
+
+        ColumnAligner { names, maps }
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn align<'a, T: AsRef<str>>(&self, file: usize, row: &'a [T]) -> Vec<&'a str> {
+        self.maps[file]
+            .iter()
+            .map(|m| m.and_then(|i| row.get(i)).map_or("", |f| f.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_aligner() {
+        let headers = [vec!["id", "name"], vec!["name", "id", "age"]]
+            .map(|h| h.into_iter().map(String::from).collect::<Vec<_>>());
+        let a = ColumnAligner::new(&headers);
+        assert_eq!(a.header(), ["id", "name", "age"]);
+        assert_eq!(a.align(0, &["1", "ann"]), ["1", "ann", ""]);
+        assert_eq!(a.align(1, &["bob", "2", "30"]), ["2", "bob", "30"]);
+
+        // repeated names are kept by position
+        let headers = [vec!["id", "x", "x"], vec!["x", "id"]]
+            .map(|h| h.into_iter().map(String::from).collect::<Vec<_>>());
+        let a = ColumnAligner::new(&headers);
+        assert_eq!(a.header(), ["id", "x", "x"]);
+        assert_eq!(a.align(0, &["1", "a", "b"]), ["1", "a", "b"]);
+        assert_eq!(a.align(1, &["c", "2"]), ["2", "c", ""]);
+    }
+}
@@ -1,17 +1,3 @@
-use crate::utils::util::werr_exit;
-pub type CliResult = Result<(), Box<dyn std::error::Error>>;
+use super::error::RsvResult;
 
-pub trait E {
-    fn handle_err(&self) {}
-}
-
-impl E for CliResult {
-    fn handle_err(&self) {
-        match self {
-            Ok(_) => {}
-            Err(msg) => {
-                werr_exit!("Error: {}", msg);
-            }
-        }
-    }
-}
+pub type CliResult = RsvResult<()>;
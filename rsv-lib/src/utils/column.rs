@@ -1,137 +1,330 @@
+use super::compress;
+use super::encoding::TextEncoding;
+use super::error::{RsvError, RsvResult};
 use super::excel::write_datatype_to_string;
+use super::reader::RecordReader;
 use super::row_split::CsvRowSplitter;
-use crate::utils::util::werr_exit;
 use calamine::Data;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use regex::Regex;
+use std::io::BufReader;
 use std::path::Path;
 
+const COLUMN_SYNTAX_ERROR: &str = "Column syntax error: can be something like 0,1,2,5 or 0-2,5 or -1 or -3--1, \
+     or column names like id,amount or id-amount or \"first name\" or amount_* or /^amount/.";
+
+// Resolves column references against the header row of a file. A column can be
+// referred to by:
+// 1. index: 0 (first column), -1 (last column)
+// 2. header name: amount, or a quoted name "first name" / 'first-name'
+// 3. range of indexes or names: 0-4, -3--1, id-amount
+// 4. glob pattern of names: amount_* or amount_?
+// 5. regex pattern of names enclosed by slashes: /^amount_\d+$/
 #[derive(Debug)]
-pub struct Columns<'a> {
+pub struct ColumnResolver<'a> {
     path: Option<&'a Path>,
     sep: char,
     quote: char,
-    pub cols: Vec<usize>,
-    pub max: usize,
-    pub select_all: bool,
-    raw: &'a str,
+    enc: TextEncoding,
     total: Option<usize>,
-    pub parsed: bool,
+    names: Option<Vec<String>>,
 }
 
-fn parse_col_usize(col: &str) -> usize {
-    col.parse().unwrap_or_else(|_| {
-        werr_exit!(
-            "{}",
-            "Column syntax error: can be something like 0,1,2,5 or 0-2,5 or -1 or -3--1."
-        );
-    })
+impl Default for ColumnResolver<'_> {
+    fn default() -> Self {
+        ColumnResolver {
+            path: None,
+            sep: ',',
+            quote: '"',
+            enc: TextEncoding::Auto,
+            total: None,
+            names: None,
+        }
+    }
+}
+
+// split a column spec by comma, except for commas within quotes or a /regex/
+pub fn split_column_spec(raw: &str) -> Vec<&str> {
+    let mut out = vec![];
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+
+    for (i, c) in raw.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '/' && raw[start..i].trim().is_empty() => quote = Some(c),
+            None if c == ',' => {
+                out.push(raw[start..i].trim());
+                start = i + 1;
+            }
+            None => {}
+        }
+    }
+    out.push(raw[start..].trim());
+
+    out.into_iter().filter(|i| !i.is_empty()).collect()
+}
+
+pub fn unquote(s: &str) -> Option<&str> {
+    let s = s.trim();
+    if s.len() >= 2
+        && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')))
+    {
+        Some(&s[1..s.len() - 1])
+    } else {
+        None
+    }
+}
+
+fn glob_to_regex(glob: &str) -> Regex {
+    let mut re = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    Regex::new(&re).unwrap()
+}
+
+impl<'a> ColumnResolver<'a> {
+    pub fn from_names(names: Vec<String>) -> Self {
+        ColumnResolver {
+            total: Some(names.len()),
+            names: Some(names),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_total(total: usize) -> Self {
+        ColumnResolver {
+            total: Some(total),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_total(&mut self, total: usize) {
+        self.total = Some(total);
+    }
+
+    pub fn set_path(&mut self, path: &'a Path, sep: char, quote: char, enc: TextEncoding) {
+        self.path = Some(path);
+        self.sep = sep;
+        self.quote = quote;
+        self.enc = enc;
+    }
+
+    pub fn set_names(&mut self, names: Vec<String>) {
+        self.total = Some(names.len());
+        self.names = Some(names);
+    }
+
+    fn read_header(&mut self) -> RsvResult<()> {
+        let Some(path) = self.path else {
+            return Ok(());
+        };
+
+        let f = compress::open(path, self.enc)?;
+        let names = match RecordReader::new(BufReader::new(f), self.sep, self.quote).next() {
+            Some(r) => CsvRowSplitter::new(&r?, self.sep, self.quote).collect_owned(),
+            None => vec![],
+        };
+        self.set_names(names);
+
+        Ok(())
+    }
+
+    /// Number of columns, read from the header of the file when not known.
+    pub fn total(&mut self) -> RsvResult<usize> {
+        if self.total.is_none() {
+            self.read_header()?;
+        }
+        Ok(self.total.unwrap_or_default())
+    }
+
+    fn names(&mut self) -> RsvResult<&[String]> {
+        if self.names.is_none() {
+            self.read_header()?;
+        }
+        Ok(self.names.as_deref().unwrap_or_default())
+    }
+
+    fn name_position(&mut self, name: &str) -> RsvResult<Option<usize>> {
+        Ok(self.names()?.iter().position(|i| i == name))
+    }
+
+    /// Resolve a single column reference, i.e., an index or a name.
+    pub fn try_resolve_one(&mut self, col: &str) -> RsvResult<Option<usize>> {
+        let col = col.trim();
+        if let Some(name) = unquote(col) {
+            return self.name_position(name);
+        }
+
+        match col.parse::<i32>() {
+            Ok(i) if col.starts_with('-') => {
+                let i = self.total()? as i32 + i;
+                if i < 0 {
+                    return Err(RsvError::ColumnNotFound(format!(
+                        "Column {col} does not exist."
+                    )));
+                }
+                Ok(Some(i as usize))
+            }
+            Ok(i) => {
+                let (i, total) = (i as usize, self.total()?);
+                if total > 0 && i >= total {
+                    return Err(RsvError::ColumnOutOfRange { col: i, total });
+                }
+                Ok(Some(i))
+            }
+            Err(_) if col.is_empty() => Ok(None),
+            Err(_) => self.name_position(col),
+        }
+    }
+
+    pub fn resolve_one(&mut self, col: &str) -> RsvResult<usize> {
+        self.try_resolve_one(col)?.ok_or_else(|| not_found(col))
+    }
+
+    /// Resolve a column reference that might be a range or a pattern.
+    pub fn resolve(&mut self, col: &str) -> RsvResult<Vec<usize>> {
+        let col = col.trim();
+
+        // regex
+        if col.len() >= 2 && col.starts_with('/') && col.ends_with('/') {
+            let re = Regex::new(&col[1..col.len() - 1]).map_err(|e| {
+                RsvError::InvalidArgument(format!(
+                    "Column pattern <{col}> is not a valid regex: {e}"
+                ))
+            })?;
+            return self.matched_columns(col, &re);
+        }
+
+        // glob
+        if unquote(col).is_none() && col.contains(['*', '?']) {
+            return self.matched_columns(col, &glob_to_regex(col));
+        }
+
+        // single column
+        if let Some(i) = self.try_resolve_one(col)? {
+            return Ok(vec![i]);
+        }
+
+        // range, try each hyphen as the range separator, e.g., -3--1, 0-2, id-amount
+        for (i, _) in col.match_indices('-').filter(|&(i, _)| i > 0) {
+            let (first, second) = (&col[..i], &col[i + 1..]);
+            if let (Some(min), Some(max)) =
+                (self.try_resolve_one(first)?, self.try_resolve_one(second)?)
+            {
+                if min > max {
+                    return Err(RsvError::InvalidArgument(
+                        "Min column is bigger than max column.".to_owned(),
+                    ));
+                }
+                return Ok((min..=max).collect());
+            }
+        }
+
+        Err(not_found(col))
+    }
+
+    fn matched_columns(&mut self, pattern: &str, re: &Regex) -> RsvResult<Vec<usize>> {
+        let cols = self
+            .names()?
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| re.is_match(name))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        if cols.is_empty() {
+            return Err(RsvError::ColumnNotFound(format!(
+                "No column name matches the pattern <{pattern}>."
+            )));
+        }
+
+        Ok(cols)
+    }
 }
 
-fn parse_i32(col: &str) -> i32 {
-    col.parse().unwrap_or_else(|_| {
-        werr_exit!(
-            "{}",
-            "Column syntax error: can be something like 0,1,2,5 or 0-2,5 or -1 or -3--1."
-        );
-    })
+fn not_found(col: &str) -> RsvError {
+    RsvError::ColumnNotFound(format!(
+        "Column <{col}> does not exist. {COLUMN_SYNTAX_ERROR}"
+    ))
 }
 
-fn split_pat_at<'a>(source: &'a str, pat: &'a str, n: usize) -> (&'a str, &'a str) {
-    let (i, _) = source.match_indices(pat).nth(n).unwrap();
-    let (first, second) = source.split_at(i);
-    (first, &second[pat.len()..])
+#[derive(Debug)]
+pub struct Columns<'a> {
+    resolver: ColumnResolver<'a>,
+    pub cols: Vec<usize>,
+    pub max: usize,
+    pub select_all: bool,
+    raw: &'a str,
+    pub parsed: bool,
 }
 
 impl<'a> Columns<'a> {
     pub fn new(raw: &'_ str) -> Columns<'_> {
         Columns {
-            path: None,
-            sep: ',',
-            quote: '"',
+            resolver: ColumnResolver::default(),
             cols: vec![],
             max: 0,
             select_all: true,
             raw,
-            total: None,
             parsed: false,
         }
     }
 
     pub fn total_col(mut self, total: usize) -> Self {
-        self.total = Some(total);
+        self.resolver.set_total(total);
         self
     }
 
-    pub fn total_col_of(mut self, path: &'a Path, sep: char, quote: char) -> Self {
-        self.path = Some(path);
-        self.sep = sep;
-        self.quote = quote;
+    pub fn total_col_of(
+        mut self,
+        path: &'a Path,
+        sep: char,
+        quote: char,
+        enc: TextEncoding,
+    ) -> Self {
+        self.resolver.set_path(path, sep, quote, enc);
+        self
+    }
+
+    /// Header names used to resolve column names, e.g., the first row of an Excel file.
+    pub fn header(mut self, names: Vec<String>) -> Self {
+        self.resolver.set_names(names);
         self
     }
 
-    pub fn parse(mut self) -> Self {
+    pub fn parse(mut self) -> RsvResult<Self> {
         self.parsed = true;
 
         if self.raw.is_empty() {
-            return self;
+            return Ok(self);
         }
 
-        self.raw.split(',').for_each(|i| {
-            if !i.trim().is_empty() {
-                self.parse_col(i)
-            }
-        });
-        self.update_status();
-
-        self
-    }
-
-    fn parse_col(&mut self, col: &str) {
-        match (col.starts_with('-'), col.matches('-').count()) {
-            // positive
-            (true, 1) => {
-                let c = { self.true_col(col) };
+        for i in split_column_spec(self.raw) {
+            for c in self.resolver.resolve(i)? {
                 self.push(c)
             }
-            // col range
-            (true, _) => {
-                let (first, second) = split_pat_at(col, "-", 1);
-                let min = { self.true_col(first) };
-                let max = { self.true_col(second) };
-                self.push_range(min, max);
-            }
-            (false, 0) => {
-                let c = { self.true_col(col) };
-                self.push(c)
-            }
-            (false, _) => {
-                let (first, second) = split_pat_at(col, "-", 0);
-                let min = { self.true_col(first) };
-                let max = { self.true_col(second) };
-                self.push_range(min, max);
-            }
-        };
-    }
+        }
+        self.update_status();
 
-    fn true_col(&mut self, col: &str) -> usize {
-        if col.starts_with('-') {
-            if self.total.is_none() {
-                let mut first_line = String::new();
-                let f = File::open(self.path.unwrap()).expect("unable to open file.");
-                BufReader::new(f)
-                    .read_line(&mut first_line)
-                    .expect("read error.");
-                self.total = Some(CsvRowSplitter::new(&first_line, self.sep, self.quote).count());
-            }
-            let i = (self.total.unwrap() as i32) + parse_i32(col);
-            if i < 0 {
-                werr_exit!("Column {} does not exist.", col);
-            }
-            i as usize
-        } else {
-            parse_col_usize(col)
+        // an unknown or empty header leaves columns unchecked
+        let total = self.resolver.total()?;
+        if total > 0 && self.max >= total {
+            return Err(RsvError::ColumnOutOfRange {
+                col: self.max,
+                total,
+            });
         }
+
+        Ok(self)
     }
 
     fn push(&mut self, col: usize) {
@@ -140,17 +333,8 @@ impl<'a> Columns<'a> {
         }
     }
 
-    fn push_range(&mut self, min: usize, max: usize) {
-        if min > max {
-            werr_exit!("Min column is bigger than max column.");
-        }
-        for i in min..=max {
-            self.push(i)
-        }
-    }
-
     fn update_status(&mut self) {
-        self.max = *self.cols.iter().max().unwrap();
+        self.max = self.cols.iter().max().copied().unwrap_or_default();
         self.select_all = self.cols.is_empty();
     }
 
@@ -169,8 +353,18 @@ impl<'a> Columns<'a> {
         (0..n).map(|i| format!("col{i}")).collect::<Vec<_>>()
     }
 
-    pub fn select_owned_string(&self, all: &[&str]) -> String {
-        self.iter().map(|&i| all[i]).collect::<Vec<_>>().join(",")
+    pub fn select_owned_string<T: AsRef<str>>(&self, all: &[T]) -> String {
+        self.iter()
+            .map(|&i| all[i].as_ref())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Fields of the columns, where a missing field of a short row is empty.
+    pub fn select_owned_vector<T: AsRef<str>>(&self, all: &[T]) -> Vec<String> {
+        self.iter()
+            .map(|&i| all.get(i).map_or("", |f| f.as_ref()).to_owned())
+            .collect()
     }
 
     pub fn select_owned_string_from_excel_datatype(&self, all: &[Data]) -> String {
@@ -194,10 +388,10 @@ impl<'a> Columns<'a> {
             .collect::<Vec<_>>()
     }
 
-    pub fn select_owned_vector_and_append_n(&self, all: &[&str]) -> Vec<String> {
+    pub fn select_owned_vector_and_append_n<T: AsRef<str>>(&self, all: &[T]) -> Vec<String> {
         self.cols
             .iter()
-            .map(|&i| all[i].to_owned())
+            .map(|&i| all[i].as_ref().to_owned())
             .chain(std::iter::once("n".to_owned()))
             .collect::<Vec<_>>()
     }
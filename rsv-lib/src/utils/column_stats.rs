@@ -1,12 +1,13 @@
 use super::{
     column_type::{ColumnType, ColumnTypes},
     row_split::CsvRowSplitter,
-    util,
+    sketch::{KllSketch, Moments},
+    util::{self, fmt_f64},
 };
 use ahash::HashSet;
 use calamine::Data;
 use get_fields::GetFields;
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
 use tabled::{Table, builder::Builder, settings::Style};
 
 #[derive(Debug)]
@@ -15,6 +16,14 @@ pub struct ColumnStats {
     cols: Vec<usize>,
     pub stat: Vec<CStat>,
     pub rows: usize,
+    /// Rows too short for the columns, which are skipped.
+    pub bad_rows: usize,
+    // values violating the column type are skipped rather than widening it
+    fixed: bool,
+    // percentiles in [0, 100] reported besides the median
+    percentiles: Vec<f64>,
+    // number of histogram bins of numeric columns, none when 0
+    bins: usize,
 }
 
 #[derive(Debug, GetFields)]
@@ -31,6 +40,17 @@ pub struct CStat {
     null: usize,
     total: f64,
     unique_hashset: HashSet<String>,
+    stddev: f64,
+    variance: f64,
+    median: f64,
+    quantiles: Vec<(f64, f64)>,
+    // the median and quantiles are approximated by the sketch
+    approximate: bool,
+    min_length: usize,
+    max_length: usize,
+    histogram: Vec<(f64, f64, usize)>,
+    moments: Moments,
+    sketch: KllSketch,
 }
 
 impl ColumnStats {
@@ -40,6 +60,10 @@ impl ColumnStats {
             cols: vec![],
             stat: vec![],
             rows: 0,
+            bad_rows: 0,
+            fixed: false,
+            percentiles: vec![1.0, 25.0, 75.0, 99.0],
+            bins: 0,
         };
         col_type
             .iter()
@@ -49,6 +73,11 @@ impl ColumnStats {
     }
 
     fn push(&mut self, col_index: usize, col_type: ColumnType, name: &str) {
+        // dates, e.g., declared in a schema, are summarized as strings
+        let col_type = match col_type {
+            ColumnType::Date => ColumnType::String,
+            t => t,
+        };
         let stat = CStat {
             col_index,
             col_type,
@@ -62,6 +91,16 @@ impl ColumnStats {
             unique: 0,
             null: 0,
             unique_hashset: HashSet::default(),
+            stddev: 0.0,
+            variance: 0.0,
+            median: 0.0,
+            quantiles: vec![],
+            approximate: false,
+            min_length: usize::MAX,
+            max_length: 0,
+            histogram: vec![],
+            moments: Moments::default(),
+            sketch: KllSketch::default(),
         };
         self.cols.push(col_index);
         self.stat.push(stat);
@@ -71,16 +110,34 @@ impl ColumnStats {
         }
     }
 
-    pub fn parse_line_by_fields(&mut self, v: &[&str]) {
+    /// Keep column types as they are, e.g., inferred from all rows, so that a
+    /// value violating the type is skipped rather than widening the type.
+    pub fn fix_types(&mut self) {
+        self.fixed = true;
+    }
+
+    /// Percentiles in [0, 100] of numeric columns, [1, 25, 75, 99] by default.
+    pub fn percentiles(&mut self, p: &[f64]) {
+        self.percentiles = p.to_vec();
+    }
+
+    /// Histograms of numeric columns with equal-width bins.
+    pub fn histogram(&mut self, bins: usize) {
+        self.bins = bins;
+    }
+
+    pub fn parse_line_by_fields<T: AsRef<str> + Debug>(&mut self, v: &[T]) {
         if self.max_col >= v.len() {
-            println!("[info] ignore a bad line: {v:?}");
+            self.bad_rows += 1;
             return;
         }
 
+        let fixed = self.fixed;
         self.cols
             .iter()
             .zip(&mut self.stat)
-            .for_each(|(&i, c)| c.parse(v[i]));
+            .filter(|(i, c)| !fixed || c.fits(v[**i].as_ref()))
+            .for_each(|(&i, c)| c.parse(v[i].as_ref()));
 
         self.rows += 1;
     }
@@ -92,7 +149,7 @@ impl ColumnStats {
 
     pub fn parse_excel_row(&mut self, v: &[Data]) {
         if self.max_col >= v.len() {
-            println!("[info] ignore a bad line: {v:?}");
+            self.bad_rows += 1;
             return;
         }
 
@@ -107,7 +164,14 @@ impl ColumnStats {
         self.rows += 1;
     }
 
+    /// Unique counts and means, as well as the spread, quantiles and
+    /// histograms of numeric columns, after all rows are parsed and merged.
     pub fn cal_unique_and_mean(&mut self) {
+        let qs = self
+            .percentiles
+            .iter()
+            .map(|p| p / 100.0)
+            .collect::<Vec<_>>();
         self.stat.iter_mut().for_each(|s| {
             s.unique = s.unique_hashset.len();
 
@@ -117,6 +181,7 @@ impl ColumnStats {
                     if n != 0 {
                         s.mean = s.total / n as f64;
                     }
+                    s.cal_spread(&qs, self.bins);
                 }
                 _ => {}
             }
@@ -129,6 +194,7 @@ impl ColumnStats {
 
     pub fn merge(&mut self, other: ColumnStats) {
         self.rows += other.rows;
+        self.bad_rows += other.bad_rows;
 
         other
             .stat
@@ -137,11 +203,22 @@ impl ColumnStats {
             .for_each(|(o, c)| c.merge(o));
     }
 
+    // a quantile label prefixed with ~ when any quantile is approximated, e.g., ~median
+    fn quantile_label(&self, label: &str) -> String {
+        match self.iter().any(|c| c.approximate) {
+            true => format!("~{label}"),
+            false => label.to_owned(),
+        }
+    }
+
     fn print_table_vertical(&self) -> Table {
         let mut builder = Builder::default();
 
         // header
-        let r = ["col", "type", "min", "max", "mean", "unique", "null"];
+        let median = self.quantile_label("median");
+        let r = [
+            "col", "type", "min", "max", "mean", "stddev", &median, "unique", "null",
+        ];
         builder.push_record(r);
 
         // columns
@@ -152,6 +229,8 @@ impl ColumnStats {
             r.push(c.min_fmt());
             r.push(c.max_fmt());
             r.push(c.mean_fmt());
+            r.push(c.number_fmt(c.stddev));
+            r.push(c.number_fmt(c.median));
             r.push(c.unique_fmt());
             r.push(c.null.to_string());
             builder.push_record(r);
@@ -169,6 +248,78 @@ impl ColumnStats {
     pub fn print(&self) {
         let table = self.print_table_vertical();
         println!("{table}");
+        self.print_histograms();
+    }
+
+    // a bar of # per bin, scaled to the largest bin
+    fn print_histograms(&self) {
+        for c in self.iter().filter(|c| !c.histogram.is_empty()) {
+            println!("{}", c.name);
+            let most = c.histogram.iter().map(|h| h.2).max().unwrap_or(0).max(1);
+            let last = c.histogram.len() - 1;
+            let edges = c
+                .histogram
+                .iter()
+                .enumerate()
+                .map(|(i, (lo, hi, _))| {
+                    let close = if i == last { ']' } else { ')' };
+                    format!("[{}, {}{close}", c.number_fmt(*lo), c.number_fmt(*hi))
+                })
+                .collect::<Vec<_>>();
+            let width = edges.iter().map(|e| e.len()).max().unwrap_or(0);
+            for (e, (_, _, n)) in edges.iter().zip(&c.histogram) {
+                let bar = "#".repeat((n * 40).div_ceil(most));
+                println!("  {e:<width$}  {bar} {n}");
+            }
+        }
+    }
+
+    /// Statistics as CSV, with a row per column, e.g., exported by stats -E.
+    pub fn to_csv(&self) -> String {
+        let mut header = [
+            "col", "type", "min", "max", "mean", "sum", "stddev", "variance",
+        ]
+        .map(String::from)
+        .to_vec();
+        header.push(self.quantile_label("median"));
+        header.extend(
+            self.percentiles
+                .iter()
+                .map(|p| self.quantile_label(&format!("p{}", fmt_f64(*p)))),
+        );
+        header.extend(["unique", "null", "min_length", "max_length"].map(String::from));
+        if self.bins > 0 {
+            header.push("histogram".to_owned());
+        }
+
+        let mut lines = vec![header.join(",")];
+        for c in self.iter() {
+            let mut r = vec![
+                c.name.to_owned(),
+                c.col_type.to_string(),
+                c.min_fmt(),
+                c.max_fmt(),
+                c.mean_fmt(),
+                c.number_fmt(c.total),
+                c.number_fmt(c.stddev),
+                c.number_fmt(c.variance),
+                c.number_fmt(c.median),
+            ];
+            match c.quantiles.is_empty() {
+                true => r.extend(self.percentiles.iter().map(|_| "-".to_owned())),
+                false => r.extend(c.quantiles.iter().map(|(_, v)| c.number_fmt(*v))),
+            }
+            r.push(c.unique_fmt());
+            r.push(c.null.to_string());
+            r.push(c.length_fmt(c.min_length));
+            r.push(c.length_fmt(c.max_length));
+            if self.bins > 0 {
+                r.push(c.histogram_fmt());
+            }
+            lines.push(r.join(","));
+        }
+
+        lines.join("\n") + "\n"
     }
 }
 
@@ -179,6 +330,10 @@ impl Clone for ColumnStats {
             stat: vec![],
             cols: vec![],
             rows: 0,
+            bad_rows: 0,
+            fixed: self.fixed,
+            percentiles: self.percentiles.clone(),
+            bins: self.bins,
         };
 
         self.iter()
@@ -212,14 +367,33 @@ impl CStat {
             self.null.to_string(),
             self.total.to_string(),
             String::new(),
+            self.number_fmt(self.stddev),
+            self.number_fmt(self.variance),
+            self.number_fmt(self.median),
+            self.quantiles_fmt(),
+            self.length_fmt(self.min_length),
+            self.length_fmt(self.max_length),
+            self.histogram_fmt(),
+            String::new(),
+            String::new(),
         ]
     }
 
+    fn fits(&self, f: &str) -> bool {
+        match self.col_type {
+            _ if util::is_null(f) => true,
+            ColumnType::Int => f.parse::<i64>().is_ok(),
+            ColumnType::Float => f.parse::<f64>().is_ok(),
+            _ => true,
+        }
+    }
+
     pub fn parse(&mut self, f: &str) {
         if util::is_null(f) {
             self.null += 1;
             return;
         }
+        self.update_length(f);
         match self.col_type {
             ColumnType::Int => {
                 if let Ok(v) = f.parse::<i64>() {
@@ -284,13 +458,48 @@ impl CStat {
             self.min = v
         }
         self.total += v;
+        self.moments.update(v);
+        self.sketch.update(v);
+    }
+
+    fn update_length(&mut self, v: &str) {
+        let n = v.chars().count();
+        self.min_length = self.min_length.min(n);
+        self.max_length = self.max_length.max(n);
+    }
+
+    // spread, quantiles and histogram from the merged moments and sketch
+    fn cal_spread(&mut self, qs: &[f64], bins: usize) {
+        self.variance = self.moments.variance();
+        self.stddev = self.moments.stddev();
+        self.median = self.sketch.quantile(0.5).unwrap_or(0.0);
+        self.approximate = !self.sketch.is_exact();
+        self.quantiles = qs
+            .iter()
+            .zip(self.sketch.quantiles(qs))
+            .map(|(&q, v)| (q * 100.0, v.unwrap_or(0.0)))
+            .collect();
+
+        if bins > 0 && self.sketch.count() > 0 {
+            let width = (self.max - self.min) / bins as f64;
+            self.histogram = self
+                .sketch
+                .histogram(self.min, self.max, bins)
+                .into_iter()
+                .enumerate()
+                .map(|(i, n)| {
+                    let lo = self.min + width * i as f64;
+                    (lo, lo + width, n)
+                })
+                .collect();
+        }
     }
 
     fn update_string_stat(&mut self, v: &str) {
-        if self.min_string.is_empty() || v < &self.min_string {
+        if self.min_string.is_empty() || v < self.min_string.as_str() {
             self.min_string = v.to_owned();
         }
-        if v > &self.max_string {
+        if v > self.max_string.as_str() {
             self.max_string = v.to_owned();
         }
     }
@@ -313,6 +522,10 @@ impl CStat {
         }
         self.null += o.null;
         self.total += o.total;
+        self.min_length = self.min_length.min(o.min_length);
+        self.max_length = self.max_length.max(o.max_length);
+        self.moments.merge(&o.moments);
+        self.sketch.merge(&o.sketch);
         self.unique_hashset.extend(o.unique_hashset)
     }
 
@@ -351,4 +564,40 @@ impl CStat {
             self.unique.to_string()
         }
     }
+
+    // a number as the column type, or - for a string column
+    fn number_fmt(&self, v: f64) -> String {
+        if self.is_string() {
+            "-".to_owned()
+        } else if self.is_int() && v.fract() == 0.0 {
+            format!("{v:.0}")
+        } else {
+            format!("{v:.2}")
+        }
+    }
+
+    fn length_fmt(&self, n: usize) -> String {
+        match self.max_length {
+            0 => "-".to_owned(),
+            _ => n.to_string(),
+        }
+    }
+
+    // e.g., p25=1 p75=3
+    fn quantiles_fmt(&self) -> String {
+        self.quantiles
+            .iter()
+            .map(|(p, v)| format!("p{}={}", fmt_f64(*p), self.number_fmt(*v)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // e.g., 0~2:10 2~4:5, with lower and upper edges and the count of a bin
+    fn histogram_fmt(&self) -> String {
+        self.histogram
+            .iter()
+            .map(|(lo, hi, n)| format!("{}~{}:{n}", fmt_f64(*lo), fmt_f64(*hi)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
@@ -1,19 +1,20 @@
 use super::{
-    cli_result::CliResult, column::Columns, reader::ExcelReader, row_split::CsvRowSplitter,
+    cli_result::CliResult,
+    column::Columns,
+    compress,
+    reader::{ExcelReader, RecordReader},
+    row_split::CsvRowSplitter,
+    schema::Schema,
     util::is_null,
 };
 use crate::utils::column;
+use crate::utils::encoding::TextEncoding;
+use crate::utils::error::RsvResult;
 use calamine::{Data, DataType};
 use rust_xlsxwriter::*;
-use std::{
-    error::Error,
-    fmt::Display,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-};
+use std::{fmt::Display, io::BufReader, path::Path};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ColumnTypes(Vec<CType>);
 
 #[derive(Debug)]
@@ -33,7 +34,7 @@ pub enum ColumnType {
 }
 
 impl ColumnTypes {
-    fn push(&mut self, col_index: usize, col_type: ColumnType, max_length: usize) {
+    pub(crate) fn push(&mut self, col_index: usize, col_type: ColumnType, max_length: usize) {
         self.0.push(CType {
             col_index,
             col_type,
@@ -46,17 +47,19 @@ impl ColumnTypes {
     }
 
     // parallel guess based on columns
+    #[allow(clippy::too_many_arguments)]
     pub fn guess_from_csv(
         path: &Path,
         sep: char,
         quote: char,
+        enc: TextEncoding,
         no_header: bool,
         cols: &column::Columns,
         text_columns: &[usize],
         date_columns: &[usize],
-    ) -> Result<Option<Self>, Box<dyn Error>> {
+    ) -> RsvResult<Option<Self>> {
         // reader
-        let rdr = BufReader::new(File::open(path)?).lines();
+        let rdr = RecordReader::new(BufReader::new(compress::open(path, enc)?), sep, quote);
         let lines = rdr
             .skip(1 - no_header as usize)
             .take(5000)
@@ -73,16 +76,30 @@ impl ColumnTypes {
             .map(|r| CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>())
             .collect::<Vec<_>>();
 
-        let guess = cols
-            .col_index_vec(lines[0].len())
+        Ok(Some(Self::guess_from_lines(
+            &lines,
+            cols,
+            text_columns,
+            date_columns,
+        )))
+    }
+
+    // guess based on split lines, which should not be empty
+    pub fn guess_from_lines<T: AsRef<str>>(
+        lines: &[Vec<T>],
+        cols: &column::Columns,
+        text_columns: &[usize],
+        date_columns: &[usize],
+    ) -> Self {
+        cols.col_index_vec(lines[0].len())
             .into_iter()
             .map(|n| {
                 if text_columns.contains(&n) {
-                    (n, ColumnType::String, max_length_at(n, &lines))
+                    (n, ColumnType::String, max_length_at(n, lines))
                 } else if date_columns.contains(&n) {
-                    (n, ColumnType::Date, max_length_at(n, &lines))
+                    (n, ColumnType::Date, max_length_at(n, lines))
                 } else {
-                    (n, parse_col_type_at(n, &lines), max_length_at(n, &lines))
+                    (n, parse_col_type_at(n, lines), max_length_at(n, lines))
                 }
             })
             .collect::<Vec<_>>()
@@ -90,9 +107,7 @@ impl ColumnTypes {
             .fold(ColumnTypes(vec![]), |mut a, b| {
                 a.push(b.0, b.1.clone(), b.2);
                 a
-            });
-
-        Ok(Some(guess))
+            })
     }
 
     // sequential guess given that excel is usually small
@@ -117,8 +132,8 @@ impl ColumnTypes {
     }
 
     // sequential guess given that io is usually small
-    pub fn guess_from_io(
-        v: &[Vec<&str>],
+    pub fn guess_from_io<T: AsRef<str>>(
+        v: &[Vec<T>],
         cols: &Columns,
         text_columns: &[usize],
         date_columns: &[usize],
@@ -140,6 +155,23 @@ impl ColumnTypes {
         guess
     }
 
+    /// Override guessed types by the types declared in a schema, so that a
+    /// column is typed by all rows rather than the first ones.
+    pub fn apply_schema<T: AsRef<str>>(
+        &mut self,
+        schema: &Schema,
+        names: &[T],
+        no_header: bool,
+    ) -> RsvResult<()> {
+        for (c, f) in schema.resolve_all(names, no_header)? {
+            if let Some(t) = self.0.iter_mut().find(|t| t.col_index == c) {
+                t.col_type = f.col_type.clone();
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn update_excel_column_width(&self, sheet: &mut Worksheet) -> CliResult {
         for c in self.iter() {
             sheet.set_column_width(c.col_index as u16, c.excel_col_width())?;
@@ -149,17 +181,17 @@ impl ColumnTypes {
     }
 }
 
-fn parse_col_type_at(n: usize, v: &[Vec<&str>]) -> ColumnType {
+fn parse_col_type_at<T: AsRef<str>>(n: usize, v: &[Vec<T>]) -> ColumnType {
     let mut ctype = ColumnType::Null;
     for r in v {
         if ctype.is_string() {
             break;
         }
-        let f = r[n];
-        if is_null(f) {
-            continue;
+        // a missing field of a short row is skipped like a null
+        match r.get(n).map(|f| f.as_ref()) {
+            Some(f) if !is_null(f) => ctype.update(f),
+            _ => continue,
         }
-        ctype.update(f);
     }
 
     ctype
@@ -171,14 +203,21 @@ fn parse_excel_col_type_at(n: usize, v: &[&[Data]]) -> ColumnType {
         if ctype.is_string() {
             break;
         }
+        if r[n].is_empty() {
+            continue;
+        }
         ctype.update_by_excel_cell(&r[n]);
     }
 
     ctype
 }
 
-fn max_length_at(n: usize, v: &[Vec<&str>]) -> usize {
-    v.iter().map(|r| r[n].len()).max().unwrap_or(0)
+fn max_length_at<T: AsRef<str>>(n: usize, v: &[Vec<T>]) -> usize {
+    v.iter()
+        .filter_map(|r| r.get(n))
+        .map(|f| f.as_ref().len())
+        .max()
+        .unwrap_or(0)
 }
 
 impl Display for ColumnType {
@@ -219,20 +258,14 @@ impl ColumnType {
                     ColumnType::String
                 }
             }
-            ColumnType::Int => {
-                if f.parse::<i64>().is_err() {
-                    *self = if f.parse::<f64>().is_ok() {
-                        ColumnType::Float
-                    } else {
-                        ColumnType::String
-                    }
-                }
-            }
-            ColumnType::Float => {
-                if f.parse::<f64>().is_err() {
-                    *self = ColumnType::String
+            ColumnType::Int if f.parse::<i64>().is_err() => {
+                *self = if f.parse::<f64>().is_ok() {
+                    ColumnType::Float
+                } else {
+                    ColumnType::String
                 }
             }
+            ColumnType::Float if f.parse::<f64>().is_err() => *self = ColumnType::String,
             _ => {}
         }
     }
@@ -248,20 +281,14 @@ impl ColumnType {
                     ColumnType::String
                 };
             }
-            ColumnType::Int => {
-                if !f.is_int() {
-                    *self = if f.is_float() {
-                        ColumnType::Float
-                    } else {
-                        ColumnType::String
-                    }
-                };
-            }
-            ColumnType::Float => {
-                if !f.is_float() {
-                    *self = ColumnType::String;
+            ColumnType::Int if !f.is_int() => {
+                *self = if f.is_float() {
+                    ColumnType::Float
+                } else {
+                    ColumnType::String
                 }
             }
+            ColumnType::Float if !f.is_float() && !f.is_int() => *self = ColumnType::String,
             _ => {}
         }
     }
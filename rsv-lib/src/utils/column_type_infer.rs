@@ -0,0 +1,281 @@
+use super::{
+    column::Columns,
+    column_type::{ColumnType, ColumnTypes},
+    date_format_infer::DateSmartParser,
+    error::RsvResult,
+    row_split::CsvRowSplitter,
+    source::RecordSource,
+    util::is_null,
+};
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use std::thread;
+
+// types of the lattice Null < Int < Float < Date < String, from narrow to wide
+const LATTICE: [ColumnType; 5] = [
+    ColumnType::Null,
+    ColumnType::Int,
+    ColumnType::Float,
+    ColumnType::Date,
+    ColumnType::String,
+];
+
+fn rank(t: &ColumnType) -> usize {
+    LATTICE
+        .iter()
+        .position(|i| i == t)
+        .unwrap_or(LATTICE.len() - 1)
+}
+
+/// Numbers of values of a column by their narrowest type in the lattice
+/// Null < Int < Float < Date < String. A value fits a type that is not narrower
+/// than its own type.
+#[derive(Debug, Clone)]
+pub struct TypeCounts {
+    pub col_index: usize,
+    counts: [usize; 5],
+    max_length: usize,
+}
+
+impl TypeCounts {
+    fn new(col_index: usize) -> Self {
+        TypeCounts {
+            col_index,
+            counts: [0; 5],
+            max_length: 0,
+        }
+    }
+
+    fn add(&mut self, f: &str, parser: &DateSmartParser) {
+        self.counts[rank(&type_of(f, parser))] += 1;
+        self.max_length = self.max_length.max(f.len());
+    }
+
+    fn merge(&mut self, other: &TypeCounts) {
+        self.counts
+            .iter_mut()
+            .zip(other.counts)
+            .for_each(|(a, b)| *a += b);
+        self.max_length = self.max_length.max(other.max_length);
+    }
+
+    pub fn nulls(&self) -> usize {
+        self.counts[0]
+    }
+
+    pub fn non_nulls(&self) -> usize {
+        self.counts[1..].iter().sum()
+    }
+
+    /// The narrowest type that at least the confidence ratio of non-null values
+    /// fit, e.g., Int for a column of integers with a few N/A at 0.99.
+    pub fn col_type(&self, confidence: f64) -> ColumnType {
+        let n = self.non_nulls();
+        if n == 0 {
+            return ColumnType::Null;
+        }
+
+        let mut fit = 0;
+        for (i, c) in self.counts.iter().enumerate().skip(1) {
+            fit += c;
+            if fit as f64 >= confidence * n as f64 {
+                return LATTICE[i].clone();
+            }
+        }
+
+        ColumnType::String
+    }
+
+    /// Number of non-null values that do not fit the type.
+    pub fn violations(&self, t: &ColumnType) -> usize {
+        self.counts[rank(t) + 1..].iter().sum()
+    }
+}
+
+// the narrowest type of a value
+fn type_of(f: &str, parser: &DateSmartParser) -> ColumnType {
+    if is_null(f) {
+        ColumnType::Null
+    } else if f.parse::<i64>().is_ok() {
+        ColumnType::Int
+    } else if f.parse::<f64>().is_ok() {
+        ColumnType::Float
+    } else if maybe_date(f) && parser.smart_parse(f, None).is_some() {
+        ColumnType::Date
+    } else {
+        ColumnType::String
+    }
+}
+
+// a quick check before date parsing, which tries many formats
+fn maybe_date(f: &str) -> bool {
+    f.len() <= 40 && f.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// Column types inferred from all rows rather than the first 5000 rows, where
+/// rows are counted in parallel by chunks and the counts are merged.
+#[derive(Debug, Clone)]
+pub struct TypeInference {
+    pub cols: Vec<TypeCounts>,
+    max_col: usize,
+}
+
+impl TypeInference {
+    pub fn new(cols: &Columns, total: usize) -> Self {
+        let cols = cols
+            .col_index_vec(total)
+            .into_iter()
+            .map(TypeCounts::new)
+            .collect::<Vec<_>>();
+        let max_col = cols.iter().map(|c| c.col_index).max().unwrap_or(0);
+
+        TypeInference { cols, max_col }
+    }
+
+    // a row with fewer fields is ignored, as in stats
+    pub fn add_fields<T: AsRef<str>>(&mut self, v: &[T], parser: &DateSmartParser) {
+        if self.max_col >= v.len() {
+            return;
+        }
+
+        for c in &mut self.cols {
+            c.add(v[c.col_index].as_ref(), parser);
+        }
+    }
+
+    pub fn merge(&mut self, other: &TypeInference) {
+        self.cols
+            .iter_mut()
+            .zip(&other.cols)
+            .for_each(|(a, b)| a.merge(b));
+    }
+
+    /// Scan the remaining rows of a source, whose rows have total fields.
+    pub fn from_source(
+        src: &mut dyn RecordSource,
+        cols: &Columns,
+        total: usize,
+    ) -> RsvResult<Self> {
+        let (sep, quote) = (src.sep(), src.quote());
+        let parser = DateSmartParser::new();
+        let empty = TypeInference::new(cols, total);
+        let mut infer = empty.clone();
+
+        // read chunks in a thread, and count each chunk in parallel
+        thread::scope(|s| -> RsvResult<()> {
+            let (tx, rx) = bounded(2);
+            let reader = s.spawn(|| src.send_to_channel_by_chunks(tx, 50_000));
+            for task in rx {
+                let r = task
+                    .lines
+                    .par_chunks(1000)
+                    .map(|chunk| {
+                        let mut o = empty.clone();
+                        for l in chunk {
+                            let v = CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>();
+                            o.add_fields(&v, &parser);
+                        }
+                        o
+                    })
+                    .collect::<Vec<_>>();
+                r.iter().for_each(|o| infer.merge(o));
+            }
+            reader.join().unwrap()?;
+            Ok(())
+        })?;
+
+        Ok(infer)
+    }
+
+    /// Scan split rows, which should not be empty, e.g., all rows of stdin.
+    pub fn from_lines<T: AsRef<str> + Sync>(lines: &[Vec<T>], cols: &Columns) -> Self {
+        let parser = DateSmartParser::new();
+        let empty = TypeInference::new(cols, lines[0].len());
+        let r = lines
+            .par_chunks(1000)
+            .map(|chunk| {
+                let mut o = empty.clone();
+                chunk.iter().for_each(|v| o.add_fields(v, &parser));
+                o
+            })
+            .collect::<Vec<_>>();
+
+        let mut infer = empty;
+        r.iter().for_each(|o| infer.merge(o));
+        infer
+    }
+
+    /// Types at the confidence, where text and date columns are fixed as given.
+    pub fn column_types(
+        &self,
+        confidence: f64,
+        text_columns: &[usize],
+        date_columns: &[usize],
+    ) -> ColumnTypes {
+        let mut ctypes = ColumnTypes::default();
+        for c in &self.cols {
+            let n = c.col_index;
+            let t = if text_columns.contains(&n) {
+                ColumnType::String
+            } else if date_columns.contains(&n) {
+                ColumnType::Date
+            } else {
+                c.col_type(confidence)
+            };
+            ctypes.push(n, t, c.max_length);
+        }
+
+        ctypes
+    }
+
+    /// A line for each column with values that violate its type, e.g., a few
+    /// N/A in an int column at a confidence less than 1.
+    pub fn report(&self, ctypes: &ColumnTypes, names: &[String]) -> Vec<String> {
+        self.cols
+            .iter()
+            .zip(ctypes.iter())
+            .filter_map(|(c, t)| {
+                let v = c.violations(&t.col_type);
+                let name = match names.get(c.col_index) {
+                    Some(n) => n.to_owned(),
+                    None => format!("col{}", c.col_index),
+                };
+                (v > 0).then(|| {
+                    format!(
+                        "[info] column <{name}> is inferred as {}, with {v} of {} values violating the type.",
+                        t.col_type,
+                        c.non_nulls()
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_inference() {
+        let lines = [
+            vec!["1", "1", "a", "2024-01-02", ""],
+            vec!["2", "1.5", "b", "2024/01/03", ""],
+            vec!["N/A", "3", "1", "4", ""],
+        ];
+        let cols = Columns::new("").total_col(5).parse().unwrap();
+        let infer = TypeInference::from_lines(&lines, &cols);
+
+        let types = |confidence| {
+            infer
+                .column_types(confidence, &[], &[])
+                .iter()
+                .map(|c| c.col_type.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(types(1.0), ["string", "float", "string", "date", "null"]);
+        assert_eq!(types(0.6), ["int", "int", "string", "date", "null"]);
+        assert_eq!(infer.cols[0].violations(&ColumnType::Int), 1);
+        assert_eq!(infer.cols[1].violations(&ColumnType::Float), 0);
+    }
+}
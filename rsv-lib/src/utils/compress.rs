@@ -0,0 +1,207 @@
+use crate::utils::encoding::{self, TextEncoding};
+use crate::utils::error::{RsvError, RsvResult};
+use bzip2::{Compression as BzCompression, read::MultiBzDecoder, write::BzEncoder};
+use flate2::{Compression as GzCompression, read::MultiGzDecoder, write::GzEncoder};
+use std::{
+    fmt::Display,
+    fs::{File, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// Compression formats that are read and written transparently.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Codec {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Codec {
+    /// Codec implied by the file extension, e.g., data.csv.gz.
+    pub fn from_extension(path: &Path) -> Option<Codec> {
+        match path.extension()?.to_str()? {
+            "gz" | "gzip" => Some(Codec::Gzip),
+            "zst" | "zstd" => Some(Codec::Zstd),
+            "bz2" => Some(Codec::Bzip2),
+            _ => None,
+        }
+    }
+
+    /// Codec by the file extension, or by magic bytes at the start of a file
+    /// that has an unknown extension.
+    pub fn detect(path: &Path) -> io::Result<Option<Codec>> {
+        if let Some(c) = Codec::from_extension(path) {
+            return Ok(Some(c));
+        }
+
+        let mut buf = [0; 4];
+        let n = File::open(path)?.read(&mut buf)?;
+        Ok(Codec::from_magic(&buf[..n]))
+    }
+
+    fn from_magic(buf: &[u8]) -> Option<Codec> {
+        match buf {
+            [0x1f, 0x8b, ..] => Some(Codec::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Codec::Zstd),
+            [b'B', b'Z', b'h', ..] => Some(Codec::Bzip2),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Codec::Gzip => "gz",
+            Codec::Zstd => "zst",
+            Codec::Bzip2 => "bz2",
+        }
+    }
+
+    // concatenated streams are read as one, so that appended outputs are valid
+    fn decoder<R: Read + Send + 'static>(&self, rdr: R) -> io::Result<Box<dyn Read + Send>> {
+        let rdr = match self {
+            Codec::Gzip => Box::new(MultiGzDecoder::new(rdr)) as Box<dyn Read + Send>,
+            Codec::Zstd => Box::new(zstd::Decoder::new(rdr)?),
+            Codec::Bzip2 => Box::new(MultiBzDecoder::new(rdr)),
+        };
+
+        Ok(rdr)
+    }
+
+    // streams are finished when the encoder is dropped
+    fn encoder<W: Write + 'static>(&self, wtr: W) -> io::Result<Box<dyn Write>> {
+        let wtr = match self {
+            Codec::Gzip => {
+                Box::new(GzEncoder::new(wtr, GzCompression::default())) as Box<dyn Write>
+            }
+            Codec::Zstd => Box::new(zstd::Encoder::new(wtr, 0)?.auto_finish()),
+            Codec::Bzip2 => Box::new(BzEncoder::new(wtr, BzCompression::default())),
+        };
+
+        Ok(wtr)
+    }
+}
+
+impl FromStr for Codec {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = match s.trim().to_lowercase().as_str() {
+            "gz" | "gzip" => Codec::Gzip,
+            "zst" | "zstd" => Codec::Zstd,
+            "bz2" | "bzip2" => Codec::Bzip2,
+            _ => {
+                return Err(
+                    format!("unknown compression <{s}>, can be one of gz, zst and bz2.").into(),
+                );
+            }
+        };
+
+        Ok(c)
+    }
+}
+
+impl Display for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// Path without the compression extension, e.g., data.csv for data.csv.gz.
+pub fn strip_extension(path: &Path) -> PathBuf {
+    match Codec::from_extension(path) {
+        Some(_) => path.with_extension(""),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Extension of output files, e.g., .gz, picked by a codec name, or by the
+/// compression of the input file when no codec is given.
+pub fn output_extension(codec: Option<&str>, input: Option<&Path>) -> RsvResult<String> {
+    let codec = match (codec, input) {
+        (Some(c), _) => Some(c.parse::<Codec>()?),
+        (None, Some(p)) => Codec::detect(p)?,
+        (None, None) => None,
+    };
+
+    Ok(codec.map(|c| format!(".{c}")).unwrap_or_default())
+}
+
+/// Open a file for reading, decompressing it on the fly when it is compressed,
+/// and transcoding it to UTF-8 by the given encoding.
+pub fn open<P: AsRef<Path>>(path: P, enc: TextEncoding) -> io::Result<Box<dyn Read + Send>> {
+    Ok(open_counted(path, enc)?.0)
+}
+
+/// Same as open, but the bytes are not transcoded, e.g., to check the encoding.
+pub fn open_raw<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Read + Send>> {
+    Ok(decompressed(path.as_ref())?.0)
+}
+
+/// Same as open, with a counter of raw bytes consumed from the file, which is
+/// used to estimate the size of decompressed data.
+pub fn open_counted<P: AsRef<Path>>(
+    path: P,
+    enc: TextEncoding,
+) -> io::Result<(Box<dyn Read + Send>, Arc<AtomicU64>)> {
+    let (rdr, n) = decompressed(path.as_ref())?;
+    Ok((encoding::decode(rdr, true, enc)?, n))
+}
+
+fn decompressed(path: &Path) -> io::Result<(Box<dyn Read + Send>, Arc<AtomicU64>)> {
+    let codec = Codec::detect(path)?;
+    let f = File::open(path)?;
+
+    let n = Arc::new(AtomicU64::new(0));
+    let rdr = CountingReader {
+        rdr: f,
+        n: n.clone(),
+    };
+    let rdr = match codec {
+        Some(c) => c.decoder(rdr)?,
+        None => Box::new(rdr),
+    };
+
+    Ok((rdr, n))
+}
+
+/// Create a file for writing, compressed by the codec of its extension.
+pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Write>> {
+    let path = path.as_ref();
+    wrap(path, File::create(path)?)
+}
+
+/// Open a file for appending, creating it if needed. A compressed file is
+/// appended with a new stream, which is read along with previous ones.
+pub fn append<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Write>> {
+    let path = path.as_ref();
+    wrap(
+        path,
+        OpenOptions::new().append(true).create(true).open(path)?,
+    )
+}
+
+fn wrap(path: &Path, f: File) -> io::Result<Box<dyn Write>> {
+    match Codec::from_extension(path) {
+        Some(c) => c.encoder(f),
+        None => Ok(Box::new(f)),
+    }
+}
+
+struct CountingReader<R> {
+    rdr: R,
+    n: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.rdr.read(buf)?;
+        self.n.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
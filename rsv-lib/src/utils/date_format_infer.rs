@@ -57,6 +57,11 @@ impl DateSmartParser {
         }
     }
 
+    // formats to try in order, dates before datetimes
+    pub(crate) fn formats(&self) -> impl Iterator<Item = &String> {
+        self.date_fmts.iter().chain(&self.datetime_fmts)
+    }
+
     pub(crate) fn smart_parse(&self, input: &str, fmt: Option<&String>) -> Option<NaiveDateTime> {
         let input = input.trim();
 
@@ -94,8 +99,9 @@ impl DateSmartParser {
         clean = clean.replace("am", "AM").replace("pm", "PM");
 
         // Fix non-standard Postgres offsets (e.g., "-08" -> "-0800")
-        // If string ends with -XX or +XX, append 00
-        if self.re_short_offset.is_match(&clean) {
+        // If string ends with -XX or +XX, append 00. A date without time,
+        // e.g., 2024-01-04, is not regarded as having an offset.
+        if clean.contains(':') && self.re_short_offset.is_match(&clean) {
             clean = format!("{}00", clean);
         }
 
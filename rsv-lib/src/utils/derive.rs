@@ -0,0 +1,818 @@
+use super::{
+    column::{ColumnResolver, unquote},
+    date_format_infer::DateSmartParser,
+    math_expr_parser::{AST, CompiledExpr},
+    util::{fmt_datetime, fmt_f64},
+};
+use crate::utils::error::{RsvError, RsvResult};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use std::{borrow::Cow, cmp::Ordering, fmt::Write, iter::Peekable, str::CharIndices};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Func {
+    Concat,
+    Upper,
+    Lower,
+    Trim,
+    Len,
+    Substr,
+    Replace,
+    If,
+    Coalesce,
+    Round,
+    Abs,
+    Date,
+    Year,
+    Month,
+    Day,
+    DateFormat,
+}
+
+// name, function, min and max number of arguments
+const FUNCTIONS: [(&str, Func, usize, usize); 16] = [
+    ("concat", Func::Concat, 1, usize::MAX),
+    ("upper", Func::Upper, 1, 1),
+    ("lower", Func::Lower, 1, 1),
+    ("trim", Func::Trim, 1, 1),
+    ("len", Func::Len, 1, 1),
+    ("substr", Func::Substr, 2, 3),
+    ("replace", Func::Replace, 3, 3),
+    ("if", Func::If, 2, 3),
+    ("coalesce", Func::Coalesce, 1, usize::MAX),
+    ("round", Func::Round, 1, 2),
+    ("abs", Func::Abs, 1, 1),
+    ("date", Func::Date, 1, 2),
+    ("year", Func::Year, 1, 1),
+    ("month", Func::Month, 1, 1),
+    ("day", Func::Day, 1, 1),
+    ("date_format", Func::DateFormat, 2, 2),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Str(String),
+    Col(usize),
+    Name(String),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+#[derive(Debug)]
+enum Node {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+    Col(usize),
+    Neg(Box<Node>),
+    Not(Box<Node>),
+    Binary(BinOp, Box<Node>, Box<Node>),
+    Call(Func, Vec<Node>),
+}
+
+/// Value of an expression. Fields are strings, and are converted to numbers
+/// by arithmetic, and to dates by date functions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Null,
+    Num(f64),
+    Str(Cow<'a, str>),
+    Bool(bool),
+    Date(NaiveDateTime),
+}
+
+enum Expr {
+    // pure arithmetic of numbers and columns, e.g., c3*c4+1
+    Math(Box<CompiledExpr>),
+    Node(Node),
+}
+
+enum Target {
+    Replace(usize),
+    Append,
+}
+
+struct DerivedColumn {
+    target: Target,
+    expr: Expr,
+}
+
+// Computes columns of a record from expressions like total=c3*c4. A column
+// that already exists is replaced in place, otherwise it is appended to the
+// record. Expressions are evaluated in order, so later expressions can refer
+// to columns derived by earlier ones.
+pub struct Deriver {
+    cols: Vec<DerivedColumn>,
+    names: Vec<String>,
+    total: usize,
+    date_parser: DateSmartParser,
+}
+
+impl Deriver {
+    pub fn new(exprs: &[String], names: Vec<String>) -> RsvResult<Self> {
+        let total = names.len();
+        let mut names = names;
+        let mut cols = vec![];
+
+        for e in exprs {
+            let syntax_error = || format!("expression <{e}> should be like name=expression.");
+            let Some((name, src)) = e.split_once('=') else {
+                return Err(RsvError::ExprSyntax(syntax_error()));
+            };
+            let name = name.trim();
+            if name.is_empty() || name.contains(['(', ')', '<', '>', '!']) || src.starts_with('=') {
+                return Err(RsvError::ExprSyntax(syntax_error()));
+            }
+
+            let mut resolver = ColumnResolver::from_names(names.clone());
+            let (node, plain) = Parser::parse(src, &mut resolver)?;
+            let expr = match plain && node.is_arithmetic() {
+                true => Expr::Math(Box::new(AST::parse(src)?)),
+                false => Expr::Node(node),
+            };
+
+            let target = match resolver.try_resolve_one(name).ok().flatten() {
+                Some(i) if i < names.len() => Target::Replace(i),
+                _ => {
+                    names.push(unquote(name).unwrap_or(name).to_owned());
+                    Target::Append
+                }
+            };
+
+            cols.push(DerivedColumn { target, expr });
+        }
+
+        Ok(Deriver {
+            cols,
+            names,
+            total,
+            date_parser: DateSmartParser::new(),
+        })
+    }
+
+    /// Header with derived columns.
+    pub fn header(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn derive<'a, T: AsRef<str>>(&self, row: &'a [T]) -> Vec<Cow<'a, str>> {
+        let mut r = row
+            .iter()
+            .map(|i| Cow::Borrowed(i.as_ref()))
+            .collect::<Vec<_>>();
+        // missing fields are regarded as empty, to keep derived columns aligned
+        if r.len() < self.total {
+            r.resize(self.total, Cow::Borrowed(""));
+        }
+
+        for c in &self.cols {
+            let v = match &c.expr {
+                Expr::Math(ast) => evaluate_math(ast, &r),
+                Expr::Node(n) => n.eval(&r, &self.date_parser).text().into_owned(),
+            };
+            match c.target {
+                Target::Replace(i) => r[i] = Cow::Owned(v),
+                Target::Append => r.push(Cow::Owned(v)),
+            }
+        }
+
+        r
+    }
+}
+
+fn evaluate_math<T: AsRef<str>>(ast: &CompiledExpr, row: &[T]) -> String {
+    let cols = (0..=ast.max_column())
+        .map(|i| match ast.contains_column(&i) {
+            true => row.get(i)?.as_ref().trim().parse::<f64>().ok(),
+            false => Some(0.0),
+        })
+        .collect::<Option<Vec<_>>>();
+
+    match cols {
+        Some(cols) => Value::Num(ast.evaluate(Some(&cols))).text().into_owned(),
+        None => String::new(),
+    }
+}
+
+// Tokens are numbers, quoted strings, columns (c1 or @1 for the second column,
+// or `first name` by header name), identifiers (functions, names, true, false
+// and null), operators, parentheses and commas.
+fn tokenize(src: &str) -> RsvResult<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = src.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let s = take_while(src, &mut chars, |c| {
+                    c.is_ascii_digit() || c == '.' || c == '_'
+                });
+                let v = s
+                    .replace('_', "")
+                    .parse::<f64>()
+                    .map_err(|_| RsvError::BadNumber(format!("<{s}> is not a valid number.")))?;
+                tokens.push(Token::Num(v));
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, e)) => s.push(e),
+                            None => {
+                                return Err(RsvError::ExprSyntax(
+                                    "unterminated string in expression.".to_owned(),
+                                ));
+                            }
+                        },
+                        Some((_, q)) if q == c => break,
+                        Some((_, o)) => s.push(o),
+                        None => {
+                            return Err(RsvError::ExprSyntax(
+                                "unterminated string in expression.".to_owned(),
+                            ));
+                        }
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '`' => {
+                chars.next();
+                let s = take_while(src, &mut chars, |c| c != '`');
+                if chars.next().is_none() {
+                    return Err(RsvError::ExprSyntax(
+                        "unterminated column name in expression.".to_owned(),
+                    ));
+                }
+                tokens.push(Token::Name(s.to_owned()));
+            }
+            '@' => {
+                chars.next();
+                let s = take_while(src, &mut chars, |c| c.is_ascii_digit());
+                let col = s
+                    .parse::<usize>()
+                    .map_err(|_| RsvError::ExprSyntax(format!("<@{s}> is not a valid column.")))?;
+                tokens.push(Token::Col(col));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let s = take_while(src, &mut chars, |c| c.is_alphanumeric() || c == '_');
+                match s.strip_prefix('c').map(|i| i.parse::<usize>()) {
+                    Some(Ok(col)) => tokens.push(Token::Col(col)),
+                    _ => tokens.push(Token::Ident(s.to_owned())),
+                }
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                });
+            }
+            _ => {
+                // matching order is important
+                let op = [
+                    "==", "!=", ">=", "<=", "&&", "||", "+", "-", "*", "/", "%", "^", ">", "<",
+                    "!", "=", "&", "|",
+                ]
+                .into_iter()
+                .find(|op| src[i..].starts_with(op))
+                .ok_or_else(|| {
+                    RsvError::ExprSyntax(format!("<{c}> is not recognized in expression."))
+                })?;
+                op.chars().for_each(|_| {
+                    chars.next();
+                });
+                tokens.push(Token::Op(op));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn take_while<'a>(
+    src: &'a str,
+    chars: &mut Peekable<CharIndices>,
+    f: impl Fn(char) -> bool,
+) -> &'a str {
+    let start = chars.peek().map_or(src.len(), |(i, _)| *i);
+    while chars.next_if(|(_, c)| f(*c)).is_some() {}
+    let end = chars.peek().map_or(src.len(), |(i, _)| *i);
+    &src[start..end]
+}
+
+// Precedence climbing parser, from the lowest precedence to the highest:
+//   || (or |), && (or &), comparisons, + -, * / %, unary - and !, ^
+struct Parser<'a, 'b> {
+    tokens: Vec<Token>,
+    pos: usize,
+    resolver: &'b mut ColumnResolver<'a>,
+}
+
+impl<'a, 'b> Parser<'a, 'b> {
+    // Also returns whether the expression is made up of numbers, columns like c1
+    // or @1, operators and parentheses only, i.e., the syntax of math_expr_parser.
+    fn parse(src: &str, resolver: &'b mut ColumnResolver<'a>) -> RsvResult<(Node, bool)> {
+        let mut p = Parser {
+            tokens: tokenize(src)?,
+            pos: 0,
+            resolver,
+        };
+        if p.tokens.is_empty() {
+            return Err(RsvError::ExprSyntax(format!(
+                "expression <{src}> is empty."
+            )));
+        }
+
+        let plain = !src.contains('_')
+            && p.tokens.iter().all(|t| {
+                matches!(
+                    t,
+                    Token::Num(_) | Token::Col(_) | Token::Op(_) | Token::LParen | Token::RParen
+                )
+            });
+
+        let node = p.parse_or()?;
+        match p.peek() {
+            None => Ok((node, plain)),
+            Some(t) => Err(RsvError::ExprSyntax(format!(
+                "unexpected {t:?} in expression <{src}>."
+            ))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eat_op(&mut self, ops: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                let op = *op;
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_or(&mut self) -> RsvResult<Node> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_op(&["||", "|"]).is_some() {
+            lhs = Node::Binary(BinOp::Or, Box::new(lhs), Box::new(self.parse_and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> RsvResult<Node> {
+        let mut lhs = self.parse_cmp()?;
+        while self.eat_op(&["&&", "&"]).is_some() {
+            lhs = Node::Binary(BinOp::And, Box::new(lhs), Box::new(self.parse_cmp()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_cmp(&mut self) -> RsvResult<Node> {
+        let lhs = self.parse_add()?;
+        let op = match self.eat_op(&["==", "=", "!=", ">=", "<=", ">", "<"]) {
+            Some("==" | "=") => BinOp::Eq,
+            Some("!=") => BinOp::Ne,
+            Some(">=") => BinOp::Ge,
+            Some("<=") => BinOp::Le,
+            Some(">") => BinOp::Gt,
+            Some(_) => BinOp::Lt,
+            None => return Ok(lhs),
+        };
+        Ok(Node::Binary(op, Box::new(lhs), Box::new(self.parse_add()?)))
+    }
+
+    fn parse_add(&mut self) -> RsvResult<Node> {
+        let mut lhs = self.parse_mul()?;
+        while let Some(op) = self.eat_op(&["+", "-"]) {
+            let op = if op == "+" { BinOp::Add } else { BinOp::Sub };
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(self.parse_mul()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_mul(&mut self) -> RsvResult<Node> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(op) = self.eat_op(&["*", "/", "%"]) {
+            let op = match op {
+                "*" => BinOp::Mul,
+                "/" => BinOp::Div,
+                _ => BinOp::Rem,
+            };
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(self.parse_unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> RsvResult<Node> {
+        match self.eat_op(&["-", "!"]) {
+            Some("-") => Ok(Node::Neg(Box::new(self.parse_unary()?))),
+            Some(_) => Ok(Node::Not(Box::new(self.parse_unary()?))),
+            None => self.parse_pow(),
+        }
+    }
+
+    fn parse_pow(&mut self) -> RsvResult<Node> {
+        let lhs = self.parse_primary()?;
+        match self.eat_op(&["^"]) {
+            Some(_) => Ok(Node::Binary(
+                BinOp::Pow,
+                Box::new(lhs),
+                Box::new(self.parse_unary()?),
+            )),
+            None => Ok(lhs),
+        }
+    }
+
+    fn parse_primary(&mut self) -> RsvResult<Node> {
+        let node = match self.next() {
+            Some(Token::Num(v)) => Node::Num(v),
+            Some(Token::Str(s)) => Node::Str(s),
+            Some(Token::Col(c)) => Node::Col(self.column(&c.to_string(), &format!("c{c}"))?),
+            Some(Token::Name(n)) => Node::Col(self.column(&format!("\"{n}\""), &n)?),
+            Some(Token::LParen) => {
+                let node = self.parse_or()?;
+                if self.next() != Some(Token::RParen) {
+                    return Err(RsvError::ExprSyntax(
+                        "unclosed parenthesis in expression.".to_owned(),
+                    ));
+                }
+                node
+            }
+            Some(Token::Ident(s)) if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                self.parse_call(&s)?
+            }
+            Some(Token::Ident(s)) => match s.to_lowercase().as_str() {
+                "true" => Node::Bool(true),
+                "false" => Node::Bool(false),
+                "null" => Node::Null,
+                _ => Node::Col(self.column(&s, &s)?),
+            },
+            Some(t) => {
+                return Err(RsvError::ExprSyntax(format!(
+                    "unexpected {t:?} in expression."
+                )));
+            }
+            None => return Err(RsvError::ExprSyntax("incomplete expression.".to_owned())),
+        };
+
+        Ok(node)
+    }
+
+    fn parse_call(&mut self, name: &str) -> RsvResult<Node> {
+        let Some(&(_, f, min, max)) = FUNCTIONS
+            .iter()
+            .find(|(n, ..)| n.eq_ignore_ascii_case(name))
+        else {
+            return Err(RsvError::ExprSyntax(format!(
+                "unknown function <{name}> in expression."
+            )));
+        };
+
+        let mut args = vec![];
+        if self.peek() != Some(&Token::RParen) {
+            loop {
+                args.push(self.parse_or()?);
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+        if self.next() != Some(Token::RParen) {
+            return Err(RsvError::ExprSyntax(format!(
+                "unclosed parenthesis of function <{name}>."
+            )));
+        }
+
+        if args.len() < min || args.len() > max {
+            return Err(RsvError::ExprSyntax(format!(
+                "wrong number of arguments for function <{name}>."
+            )));
+        }
+
+        Ok(Node::Call(f, args))
+    }
+
+    fn column(&mut self, spec: &str, name: &str) -> RsvResult<usize> {
+        self.resolver
+            .try_resolve_one(spec)?
+            .ok_or_else(|| RsvError::ColumnNotFound(format!("column <{name}> does not exist.")))
+    }
+}
+
+impl Node {
+    // arithmetic of numbers and columns only, which is evaluated by math_expr_parser,
+    // except ^ that is left-associative there rather than right-associative
+    fn is_arithmetic(&self) -> bool {
+        match self {
+            Node::Num(_) | Node::Col(_) => true,
+            Node::Binary(op, a, b) => {
+                matches!(
+                    op,
+                    BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem
+                ) && a.is_arithmetic()
+                    && b.is_arithmetic()
+            }
+            _ => false,
+        }
+    }
+
+    fn eval<'a, T: AsRef<str>>(&'a self, row: &'a [T], p: &DateSmartParser) -> Value<'a> {
+        match self {
+            Node::Num(v) => Value::Num(*v),
+            Node::Str(s) => Value::Str(Cow::Borrowed(s)),
+            Node::Bool(b) => Value::Bool(*b),
+            Node::Null => Value::Null,
+            Node::Col(i) => match row.get(*i).map(|f| f.as_ref()) {
+                Some(f) if !f.trim().is_empty() => Value::Str(Cow::Borrowed(f)),
+                _ => Value::Null,
+            },
+            Node::Neg(a) => a.eval(row, p).num().map_or(Value::Null, |v| Value::Num(-v)),
+            Node::Not(a) => Value::Bool(!a.eval(row, p).truthy()),
+            Node::Binary(BinOp::And, a, b) => {
+                Value::Bool(a.eval(row, p).truthy() && b.eval(row, p).truthy())
+            }
+            Node::Binary(BinOp::Or, a, b) => {
+                Value::Bool(a.eval(row, p).truthy() || b.eval(row, p).truthy())
+            }
+            Node::Binary(op, a, b) => binary(*op, a.eval(row, p), b.eval(row, p)),
+            Node::Call(f, args) => call(*f, args, row, p),
+        }
+    }
+}
+
+fn binary<'a>(op: BinOp, a: Value<'a>, b: Value<'a>) -> Value<'a> {
+    let o = match op {
+        BinOp::Eq => return Value::Bool(a.compare(&b).is_eq()),
+        BinOp::Ne => return Value::Bool(a.compare(&b).is_ne()),
+        BinOp::Gt => return Value::Bool(a.compare(&b).is_gt()),
+        BinOp::Ge => return Value::Bool(a.compare(&b).is_ge()),
+        BinOp::Lt => return Value::Bool(a.compare(&b).is_lt()),
+        BinOp::Le => return Value::Bool(a.compare(&b).is_le()),
+        _ => op,
+    };
+
+    // date arithmetic in days, e.g., date(c1) + 30 or date(c1) - date(c2), and
+    // null for a date out of range
+    match (o, &a, &b) {
+        (BinOp::Add, Value::Date(d), v) | (BinOp::Add, v, Value::Date(d)) => {
+            return v
+                .num()
+                .and_then(days)
+                .and_then(|n| d.checked_add_signed(n))
+                .map_or(Value::Null, Value::Date);
+        }
+        (BinOp::Sub, Value::Date(x), Value::Date(y)) => {
+            return Value::Num((*x - *y).num_seconds() as f64 / 86400.0);
+        }
+        (BinOp::Sub, Value::Date(d), v) => {
+            return v
+                .num()
+                .and_then(days)
+                .and_then(|n| d.checked_sub_signed(n))
+                .map_or(Value::Null, Value::Date);
+        }
+        _ => {}
+    }
+
+    let (Some(x), Some(y)) = (a.num(), b.num()) else {
+        return Value::Null;
+    };
+    Value::Num(match o {
+        BinOp::Add => x + y,
+        BinOp::Sub => x - y,
+        BinOp::Mul => x * y,
+        BinOp::Div => x / y,
+        BinOp::Rem => x % y,
+        _ => x.powf(y),
+    })
+}
+
+fn days(n: f64) -> Option<TimeDelta> {
+    let secs = (n * 86400.0).round();
+    match secs.is_finite() && secs.abs() < i64::MAX as f64 {
+        true => TimeDelta::try_seconds(secs as i64),
+        false => None,
+    }
+}
+
+fn call<'a, T: AsRef<str>>(
+    f: Func,
+    args: &'a [Node],
+    row: &'a [T],
+    p: &DateSmartParser,
+) -> Value<'a> {
+    let arg = |i: usize| args.get(i).map_or(Value::Null, |a| a.eval(row, p));
+    let owned = |s: String| Value::Str(Cow::Owned(s));
+
+    match f {
+        Func::If => match arg(0).truthy() {
+            true => arg(1),
+            false => arg(2),
+        },
+        Func::Coalesce => args
+            .iter()
+            .map(|a| a.eval(row, p))
+            .find(|v| *v != Value::Null)
+            .unwrap_or(Value::Null),
+        Func::Concat => {
+            let mut s = String::new();
+            args.iter().for_each(|a| s.push_str(&a.eval(row, p).text()));
+            owned(s)
+        }
+        Func::Upper => owned(arg(0).text().to_uppercase()),
+        Func::Lower => owned(arg(0).text().to_lowercase()),
+        Func::Trim => owned(arg(0).text().trim().to_owned()),
+        Func::Len => Value::Num(arg(0).text().chars().count() as f64),
+        Func::Substr => {
+            // start counts from the end when negative
+            let s = arg(0).text().chars().collect::<Vec<_>>();
+            let n = s.len() as i64;
+            let start = arg(1).num().unwrap_or_default() as i64;
+            let start = if start < 0 { n + start } else { start }.clamp(0, n) as usize;
+            let len = arg(2).num().map_or(s.len(), |v| v.max(0.0) as usize);
+            owned(s.iter().skip(start).take(len).collect())
+        }
+        Func::Replace => owned(arg(0).text().replace(&*arg(1).text(), &arg(2).text())),
+        Func::Round => {
+            let n = 10f64.powi(arg(1).num().unwrap_or_default() as i32);
+            arg(0)
+                .num()
+                .map_or(Value::Null, |v| Value::Num((v * n).round() / n))
+        }
+        Func::Abs => arg(0).num().map_or(Value::Null, |v| Value::Num(v.abs())),
+        Func::Date => {
+            let fmt = args.get(1).map(|_| arg(1).text().into_owned());
+            arg(0)
+                .date(p, fmt.as_ref())
+                .map_or(Value::Null, Value::Date)
+        }
+        Func::Year => date_part(arg(0), p, |d| d.year() as f64),
+        Func::Month => date_part(arg(0), p, |d| d.month() as f64),
+        Func::Day => date_part(arg(0), p, |d| d.day() as f64),
+        Func::DateFormat => {
+            let Some(d) = arg(0).date(p, None) else {
+                return Value::Null;
+            };
+            let mut s = String::new();
+            match write!(s, "{}", d.format(&arg(1).text())) {
+                Ok(_) => owned(s),
+                Err(_) => Value::Null,
+            }
+        }
+    }
+}
+
+fn parse_by_format(s: &str, fmt: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, fmt)
+        .or_else(|_| NaiveDate::parse_from_str(s, fmt).map(|d| d.and_time(NaiveTime::MIN)))
+        .ok()
+}
+
+fn date_part<'a>(v: Value, p: &DateSmartParser, f: impl Fn(NaiveDateTime) -> f64) -> Value<'a> {
+    v.date(p, None).map_or(Value::Null, |d| Value::Num(f(d)))
+}
+
+impl Value<'_> {
+    fn num(&self) -> Option<f64> {
+        match self {
+            Value::Num(v) => Some(*v),
+            Value::Str(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+
+    // a string is parsed strictly by the format if given, otherwise guessed
+    fn date(&self, p: &DateSmartParser, fmt: Option<&String>) -> Option<NaiveDateTime> {
+        match (self, fmt) {
+            (Value::Date(d), _) => Some(*d),
+            (Value::Str(s), Some(f)) => parse_by_format(s.trim(), f),
+            (Value::Str(s), None) => p.smart_parse(s, None),
+            _ => None,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Num(v) => *v != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::Date(_) => true,
+        }
+    }
+
+    // numerically when both are numbers, otherwise as strings
+    fn compare(&self, other: &Value) -> Ordering {
+        if let (Value::Date(a), Value::Date(b)) = (self, other) {
+            return a.cmp(b);
+        }
+        match (self.num(), other.num()) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => self.text().cmp(&other.text()),
+        }
+    }
+
+    /// Output of the value, dates are formatted as %Y-%m-%d or %Y-%m-%d %H:%M:%S.
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Value::Null => Cow::Borrowed(""),
+            Value::Num(v) if !v.is_finite() => Cow::Borrowed(""),
+            Value::Num(v) => Cow::Owned(fmt_f64(*v)),
+            Value::Str(s) => Cow::Borrowed(s),
+            Value::Bool(b) => Cow::Borrowed(if *b { "true" } else { "false" }),
+            Value::Date(d) => Cow::Owned(fmt_datetime(d)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deriver;
+    use crate::utils::error::RsvError;
+
+    fn derive(expr: &str, row: &[&str]) -> String {
+        let names = ["id", "name", "price", "qty", "date"]
+            .map(String::from)
+            .to_vec();
+        let d = Deriver::new(&[format!("out={expr}")], names).unwrap();
+        d.derive(row).last().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_derive_expr() {
+        let row = ["1", "Apple pie", "2.5", "4", "2024-01-30"];
+
+        assert_eq!(derive("c2*c3", &row), "10");
+        assert_eq!(derive("price * qty - 1", &row), "9");
+        assert_eq!(derive("-price + 2^3", &row), "5.5");
+        assert_eq!(derive("2^2^3", &row), "256");
+        assert_eq!(derive("-2^2", &row), "-4");
+        assert_eq!(derive("if(qty > 3, \"many\", 'few')", &row), "many");
+        assert_eq!(derive("concat(upper(name), '-', id)", &row), "APPLE PIE-1");
+        assert_eq!(derive("substr(name, -3)", &row), "pie");
+        assert_eq!(
+            derive("replace(lower(name), 'apple', 'cherry')", &row),
+            "cherry pie"
+        );
+        assert_eq!(derive("date(date) + 2", &row), "2024-02-01");
+        assert_eq!(derive("date('05/01/2024', '%d/%m/%Y')", &row), "2024-01-05");
+        assert_eq!(derive("date(date, '%d/%m/%Y')", &row), "");
+        assert_eq!(derive("date(date) - date('2024-01-01')", &row), "29");
+        assert_eq!(derive("date(date) + 1000000000000", &row), "");
+        assert_eq!(derive("date(date) - 10 ^ 300", &row), "");
+        assert_eq!(derive("month(date)", &row), "1");
+        assert_eq!(derive("round(price / 3, 2)", &row), "0.83");
+        assert_eq!(derive("coalesce(c4, name)", &row[..3]), "Apple pie");
+        assert_eq!(derive("c1 * 2", &row), "");
+    }
+
+    #[test]
+    fn test_derive_column_out_of_range() {
+        let names = vec!["id".to_owned(), "name".to_owned()];
+        let r = Deriver::new(&["out=coalesce(c9, name)".to_owned()], names);
+        assert!(matches!(
+            r,
+            Err(RsvError::ColumnOutOfRange { col: 9, total: 2 })
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::utils::error::{RsvError, RsvResult};
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use std::{
+    fmt::Display,
+    io::{self, BufReader, Cursor, Read, Write, stdin},
+    str::FromStr,
+};
+
+// bytes at the start of a file to detect its encoding
+const SNIFF_SIZE: usize = 64 * 1024;
+
+/// Character encoding of text files. Input files are transcoded to UTF-8 on the
+/// fly, and an output file can be transcoded from UTF-8, e.g., to GBK.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextEncoding {
+    /// By the BOM, or guessed from the first 64KB of a file when there is no BOM.
+    /// A file that is valid UTF-8 in its first 64KB is read as UTF-8, so invalid
+    /// bytes after that are an error, and the encoding should be given instead.
+    #[default]
+    Auto,
+    Fixed(&'static Encoding),
+}
+
+impl FromStr for TextEncoding {
+    type Err = RsvError;
+
+    // labels of the WHATWG Encoding Standard, e.g., utf-8, gbk, gb18030, latin1,
+    // utf-16le, shift_jis and big5
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(TextEncoding::Auto);
+        }
+
+        match Encoding::for_label(s.as_bytes()) {
+            Some(e) => Ok(TextEncoding::Fixed(e)),
+            None => Err(RsvError::InvalidArgument(format!(
+                "unknown encoding <{s}>, can be auto, utf-8, gbk, gb18030, latin1, utf-16le, utf-16be and so on."
+            ))),
+        }
+    }
+}
+
+impl Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextEncoding::Auto => f.write_str("auto"),
+            TextEncoding::Fixed(e) => f.write_str(e.name()),
+        }
+    }
+}
+
+/// Transcode a reader to UTF-8 by the given encoding. Valid UTF-8 without a BOM
+/// is passed through as it is. When fill is false, the encoding is detected by
+/// the first read only, so that a pipe is not blocked.
+pub fn decode<R: Read + Send + 'static>(
+    mut rdr: R,
+    fill: bool,
+    enc: TextEncoding,
+) -> io::Result<Box<dyn Read + Send>> {
+    let mut head = vec![0; SNIFF_SIZE];
+    let mut n = 0;
+    loop {
+        let m = rdr.read(&mut head[n..])?;
+        n += m;
+        if m == 0 || n == head.len() || !fill {
+            break;
+        }
+    }
+    head.truncate(n);
+
+    let bom = Encoding::for_bom(&head).is_some();
+    let enc = match enc {
+        _ if bom => UTF_8,
+        TextEncoding::Fixed(e) => e,
+        TextEncoding::Auto => detect(&head, n < SNIFF_SIZE),
+    };
+
+    let rdr = Cursor::new(head).chain(rdr);
+    if enc == UTF_8 && !bom {
+        return Ok(Box::new(rdr));
+    }
+
+    // a BOM overrides the given encoding, and is stripped
+    let rdr = DecodeReaderBytesBuilder::new()
+        .encoding(Some(enc))
+        .bom_override(true)
+        .strip_bom(true)
+        .build(rdr);
+
+    Ok(Box::new(rdr))
+}
+
+// UTF-8 unless the bytes are invalid, in which case the encoding is guessed.
+// Only the first SNIFF_SIZE bytes are checked, so that a large file is not read
+// twice.
+fn detect(head: &[u8], last: bool) -> &'static Encoding {
+    match std::str::from_utf8(head) {
+        Ok(_) => return UTF_8,
+        // a char cut at the end of the bytes
+        Err(e) if e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(head, last);
+    detector.guess(None, true)
+}
+
+/// Stdin transcoded to UTF-8 by the given encoding.
+pub fn stdin_reader(enc: TextEncoding) -> io::Result<BufReader<Box<dyn Read + Send>>> {
+    Ok(BufReader::new(decode(stdin(), false, enc)?))
+}
+
+/// Transcode UTF-8 text written to a writer, e.g., to GBK for legacy systems. A
+/// char that cannot be encoded is an error rather than being replaced.
+pub fn encode(wtr: Box<dyn Write>, enc: TextEncoding) -> Box<dyn Write> {
+    match enc {
+        TextEncoding::Fixed(e) if e != UTF_8 => Box::new(EncodingWriter {
+            wtr,
+            enc: e,
+            pending: vec![],
+            started: false,
+        }),
+        _ => wtr,
+    }
+}
+
+struct EncodingWriter {
+    wtr: Box<dyn Write>,
+    enc: &'static Encoding,
+    // bytes of a char that is cut between two writes
+    pending: Vec<u8>,
+    started: bool,
+}
+
+impl EncodingWriter {
+    fn encode(&mut self, s: &str) -> RsvResult<Vec<u8>> {
+        // UTF-16 files start with a BOM
+        let mut out = match (self.started, self.enc) {
+            (false, e) if e == UTF_16LE => vec![0xff, 0xfe],
+            (false, e) if e == UTF_16BE => vec![0xfe, 0xff],
+            _ => vec![],
+        };
+        self.started = true;
+
+        if self.enc == UTF_16LE {
+            out.extend(s.encode_utf16().flat_map(|u| u.to_le_bytes()));
+        } else if self.enc == UTF_16BE {
+            out.extend(s.encode_utf16().flat_map(|u| u.to_be_bytes()));
+        } else {
+            let (bytes, _, unmappable) = self.enc.encode(s);
+            if unmappable {
+                let c = s
+                    .chars()
+                    .find(|c| self.enc.encode(c.encode_utf8(&mut [0; 4])).2)
+                    .unwrap_or_default();
+                return Err(RsvError::Encoding(format!(
+                    "<{c}> cannot be encoded in {}.",
+                    self.enc.name()
+                )));
+            }
+            out.extend_from_slice(&bytes);
+        }
+
+        Ok(out)
+    }
+}
+
+impl Write for EncodingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        let rest = self.pending.split_off(valid);
+        let text = std::mem::replace(&mut self.pending, rest);
+        let s = std::str::from_utf8(&text).unwrap_or_default();
+        let bytes = self.encode(s).map_err(io::Error::other)?;
+        self.wtr.write_all(&bytes)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.wtr.flush()
+    }
+}
@@ -0,0 +1,177 @@
+use std::{error::Error, fmt::Display, io, num, string::FromUtf8Error};
+
+/// Errors of the library, which are returned to the caller rather than having
+/// the process exited. The binary converts them to exit codes by category.
+#[derive(Debug)]
+pub enum RsvError {
+    Io(io::Error),
+    /// Data that is not valid in the encoding, e.g., a non-UTF-8 file.
+    Encoding(String),
+    /// A bad EXCEL file, or a sheet that does not exist.
+    Excel(String),
+    /// A bad Parquet file, or data that cannot be written to one.
+    Parquet(String),
+    /// A bad JSON or NDJSON file, e.g., a line that is not an object.
+    Json(String),
+    /// A column spec that matches no column.
+    ColumnNotFound(String),
+    /// A column index that is not less than the number of columns.
+    ColumnOutOfRange {
+        col: usize,
+        total: usize,
+    },
+    /// A filter that is wrong at the byte position.
+    FilterSyntax {
+        filter: String,
+        pos: usize,
+    },
+    /// A math or derive expression that cannot be parsed.
+    ExprSyntax(String),
+    /// A value that should be a number, e.g., in a numeric filter.
+    BadNumber(String),
+    Regex(regex::Error),
+    /// An invalid argument of an operation, e.g., an empty column spec.
+    InvalidArgument(String),
+    /// A file with problems found by validation.
+    Validation(String),
+    Other(String),
+}
+
+pub type RsvResult<T> = Result<T, RsvError>;
+
+impl Display for RsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RsvError::Io(e) => write!(f, "{e}"),
+            RsvError::Encoding(s) => write!(f, "{s}"),
+            RsvError::Excel(s) => write!(f, "{s}"),
+            RsvError::Parquet(s) => write!(f, "{s}"),
+            RsvError::Json(s) => write!(f, "{s}"),
+            RsvError::ColumnNotFound(s) => write!(f, "{s}"),
+            RsvError::ColumnOutOfRange { col, total } => {
+                write!(
+                    f,
+                    "column {col} is out of range, the file has {total} columns."
+                )
+            }
+            RsvError::FilterSyntax { filter, pos } => write!(
+                f,
+                "filter syntax is wrong at position {pos} <{}>, run <rsv select -h> for help.",
+                &filter[*pos..]
+            ),
+            RsvError::ExprSyntax(s) => write!(f, "{s}"),
+            RsvError::BadNumber(s) => write!(f, "{s}"),
+            RsvError::Regex(e) => write!(f, "{e}"),
+            RsvError::InvalidArgument(s) => write!(f, "{s}"),
+            RsvError::Validation(s) => write!(f, "{s}"),
+            RsvError::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Error for RsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RsvError::Io(e) => Some(e),
+            RsvError::Regex(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// invalid UTF-8 in a text reader is an encoding error, and an RsvError wrapped
+// in an io::Error, e.g., by a writer, is unwrapped
+impl From<io::Error> for RsvError {
+    fn from(e: io::Error) -> Self {
+        if e.get_ref().is_some_and(|i| i.is::<RsvError>()) {
+            if let Some(Ok(e)) = e.into_inner().map(|i| i.downcast::<RsvError>()) {
+                return *e;
+            }
+            unreachable!()
+        }
+
+        // the encoding is detected by the start of a file, so invalid bytes can
+        // come later in a file that is taken as UTF-8
+        match e.kind() == io::ErrorKind::InvalidData && e.to_string().contains("UTF-8") {
+            true => RsvError::Encoding(format!(
+                "{e}, use --encoding to set the encoding of the input."
+            )),
+            false => RsvError::Io(e),
+        }
+    }
+}
+
+impl From<FromUtf8Error> for RsvError {
+    fn from(e: FromUtf8Error) -> Self {
+        RsvError::Encoding(e.to_string())
+    }
+}
+
+impl From<regex::Error> for RsvError {
+    fn from(e: regex::Error) -> Self {
+        RsvError::Regex(e)
+    }
+}
+
+impl From<calamine::Error> for RsvError {
+    fn from(e: calamine::Error) -> Self {
+        RsvError::Excel(e.to_string())
+    }
+}
+
+impl From<calamine::XlsxError> for RsvError {
+    fn from(e: calamine::XlsxError) -> Self {
+        RsvError::Excel(e.to_string())
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for RsvError {
+    fn from(e: rust_xlsxwriter::XlsxError) -> Self {
+        RsvError::Excel(e.to_string())
+    }
+}
+
+impl From<parquet::errors::ParquetError> for RsvError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        RsvError::Parquet(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for RsvError {
+    fn from(e: serde_json::Error) -> Self {
+        RsvError::Json(e.to_string())
+    }
+}
+
+impl From<num::ParseIntError> for RsvError {
+    fn from(e: num::ParseIntError) -> Self {
+        RsvError::BadNumber(e.to_string())
+    }
+}
+
+impl From<num::ParseFloatError> for RsvError {
+    fn from(e: num::ParseFloatError) -> Self {
+        RsvError::BadNumber(e.to_string())
+    }
+}
+
+impl From<String> for RsvError {
+    fn from(s: String) -> Self {
+        RsvError::Other(s)
+    }
+}
+
+impl From<&str> for RsvError {
+    fn from(s: &str) -> Self {
+        RsvError::Other(s.to_owned())
+    }
+}
+
+impl From<Box<dyn Error>> for RsvError {
+    fn from(e: Box<dyn Error>) -> Self {
+        match e.downcast::<io::Error>() {
+            Ok(e) => RsvError::from(*e),
+            Err(e) => RsvError::Other(e.to_string()),
+        }
+    }
+}
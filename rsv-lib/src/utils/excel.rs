@@ -1,31 +1,12 @@
-use calamine::Data;
-use std::{borrow::Cow, fmt::Write};
+use super::writer::Dialect;
+use calamine::{Data, Range};
+use chrono::Timelike;
+use std::{borrow::Cow, fmt::Write, io};
 
 pub fn datatype_vec_to_string_vec(data: &[Data]) -> Vec<String> {
     data.iter().map(|i| i.to_string()).collect()
 }
 
-pub fn datatype_vec_to_str_vec(data: &'_ [Data]) -> Vec<Cow<'_, str>> {
-    data.iter()
-        .map(|i| match i {
-            Data::String(s) => Cow::Borrowed(s.as_str()),
-            _ => Cow::from(i.to_string()),
-        })
-        .collect()
-}
-
-pub fn datatype_vec_to_string(data: &[Data]) -> String {
-    let mut o = String::new();
-    let mut iter = data.iter().peekable();
-    while let Some(i) = iter.next() {
-        write_datatype_to_string(&mut o, i);
-        if iter.peek().is_some() {
-            o.push(',');
-        }
-    }
-    o
-}
-
 pub fn write_datatype_to_string(s: &mut String, d: &Data) {
     let _ = match d {
         Data::String(v) => write!(s, "{}", v),
@@ -39,3 +20,50 @@ pub fn write_datatype_to_string(s: &mut String, d: &Data) {
         Data::Empty => Ok(()),
     };
 }
+
+/// A cell as plain text. Dates are written as %Y-%m-%d, with the time when it
+/// is not midnight.
+pub fn cell_to_str(data: &Data) -> Cow<'_, str> {
+    match data {
+        Data::String(v) => Cow::Borrowed(v.as_str()),
+        Data::DateTime(v) => match v.as_datetime() {
+            Some(a) if a.hour() == 0 && a.minute() == 0 && a.second() == 0 => {
+                Cow::Owned(a.format("%Y-%m-%d").to_string())
+            }
+            Some(a) => Cow::Owned(a.format("%Y-%m-%d %H:%M:%S").to_string()),
+            None => Cow::Borrowed(""),
+        },
+        _ => Cow::Owned(data.to_string()),
+    }
+}
+
+/// Write a cell as a CSV field, which is quoted as in RFC 4180 when it has a
+/// comma, a double-quote or a line break, e.g., "say ""hi""".
+pub fn write_csv_field<W: io::Write>(w: &mut W, data: &Data) -> io::Result<()> {
+    let f = cell_to_str(data);
+    w.write_all(Dialect::default().quote_field(&f, false).as_bytes())
+}
+
+/// A range of cells with names as the first row, e.g., to process a Parquet or
+/// JSON file the same way as an EXCEL sheet. Short rows are padded with empty cells.
+pub fn rows_to_range(
+    names: Vec<String>,
+    rows: impl Iterator<Item = Vec<Data>>,
+    n: usize,
+) -> Range<Data> {
+    if names.is_empty() {
+        return Range::empty();
+    }
+
+    let mut range = Range::new((0, 0), (n as u32, names.len() as u32 - 1));
+    for (c, name) in names.into_iter().enumerate() {
+        range.set_value((0, c as u32), Data::String(name));
+    }
+    for (r, row) in rows.enumerate() {
+        for (c, v) in row.into_iter().enumerate() {
+            range.set_value((r as u32 + 1, c as u32), v);
+        }
+    }
+
+    range
+}
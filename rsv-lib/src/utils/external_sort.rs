@@ -0,0 +1,203 @@
+use super::{
+    cli_result::CliResult,
+    reader::RecordReader,
+    row_split::CsvRowSplitter,
+    sort::{SortColumns, SortKey},
+    util::datetime_str,
+    writer::Writer,
+};
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    fs::{File, create_dir_all, remove_dir_all},
+    io::{BufReader, Write},
+    mem,
+    path::PathBuf,
+    process,
+};
+
+// Approximate memory held by a buffered line besides its content, i.e.,
+// the String itself and its sort keys.
+const LINE_OVERHEAD: usize = 64;
+
+// Sorts lines within a memory budget. Lines are buffered in memory until the
+// budget is exceeded, then the buffer is sorted and spilled to a temporary
+// file as a sorted run. Sorted runs are k-way merged in the end.
+pub struct ExternalSorter<'a> {
+    cols: &'a SortColumns,
+    sep: char,
+    quote: char,
+    budget: usize,
+    dir: PathBuf,
+    runs: Vec<PathBuf>,
+    buffer: Vec<String>,
+    buffer_bytes: usize,
+}
+
+impl<'a> ExternalSorter<'a> {
+    pub fn new(cols: &'a SortColumns, sep: char, quote: char, budget: usize) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("rsv-sort-{}-{}", datetime_str(), process::id()));
+
+        ExternalSorter {
+            cols,
+            sep,
+            quote,
+            budget,
+            dir,
+            runs: vec![],
+            buffer: vec![],
+            buffer_bytes: 0,
+        }
+    }
+
+    /// Directory to hold temporary sorted runs, default to the system temp directory.
+    pub fn temp_dir(mut self, dir: Option<&str>) -> Self {
+        if let Some(dir) = dir {
+            self.dir = PathBuf::from(dir).join(self.dir.file_name().unwrap());
+        }
+        self
+    }
+
+    pub fn run_n(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn push(&mut self, lines: Vec<String>) -> CliResult {
+        self.buffer_bytes += lines.iter().map(|l| l.len() + LINE_OVERHEAD).sum::<usize>();
+        self.buffer.extend(lines);
+
+        if self.buffer_bytes > self.budget {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    fn spill(&mut self) -> CliResult {
+        if self.runs.is_empty() {
+            create_dir_all(&self.dir)?;
+        }
+
+        let out = self.dir.join(format!("run{}.csv", self.runs.len()));
+        let mut wtr = Writer::new(&out)?;
+        self.cols
+            .sort_and_write(&self.buffer, self.sep, self.quote, &mut wtr)?;
+        wtr.0.flush()?;
+
+        self.runs.push(out);
+        self.buffer = vec![];
+        self.buffer_bytes = 0;
+
+        Ok(())
+    }
+
+    pub fn finish(mut self, wtr: &mut Writer) -> CliResult {
+        // all lines fit in memory
+        if self.runs.is_empty() {
+            let lines = mem::take(&mut self.buffer);
+            return self.cols.sort_and_write(&lines, self.sep, self.quote, wtr);
+        }
+
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+
+        self.merge(wtr)
+    }
+
+    fn merge(&self, wtr: &mut Writer) -> CliResult {
+        let mut readers = self
+            .runs
+            .iter()
+            .map(|p| {
+                Ok(RecordReader::new(
+                    BufReader::new(File::open(p)?),
+                    self.sep,
+                    self.quote,
+                ))
+            })
+            .collect::<Result<Vec<_>, std::io::Error>>()?;
+
+        let mut heap = BinaryHeap::with_capacity(readers.len());
+        for (run, rdr) in readers.iter_mut().enumerate() {
+            if let Some(line) = rdr.next() {
+                heap.push(self.merge_item(line?, run));
+            }
+        }
+
+        while let Some(item) = heap.pop() {
+            // e.g., broken pipe, temporary files are removed before the error
+            if let Err(e) = wtr.write_str(&item.line) {
+                self.remove_runs();
+                return Err(e);
+            }
+            if let Some(line) = readers[item.run].next() {
+                heap.push(self.merge_item(line?, item.run));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_runs(&self) {
+        if !self.runs.is_empty() {
+            let _ = remove_dir_all(&self.dir);
+        }
+    }
+
+    fn merge_item(&self, line: String, run: usize) -> MergeItem<'_> {
+        let f = CsvRowSplitter::new(&line, self.sep, self.quote).collect::<Vec<_>>();
+        let keys = self
+            .cols
+            .keys(&f)
+            .into_iter()
+            .map(SortKey::into_owned)
+            .collect();
+
+        MergeItem {
+            cols: self.cols,
+            keys,
+            line,
+            run,
+        }
+    }
+}
+
+impl Drop for ExternalSorter<'_> {
+    fn drop(&mut self) {
+        self.remove_runs();
+    }
+}
+
+struct MergeItem<'a> {
+    cols: &'a SortColumns,
+    keys: Vec<SortKey<'static>>,
+    line: String,
+    run: usize,
+}
+
+impl PartialEq for MergeItem<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MergeItem<'_> {}
+
+impl PartialOrd for MergeItem<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MergeItem<'_> {
+    // reversed so that the max-heap pops the smallest record first; ties are
+    // broken by the run order to keep the sort stable.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cols
+            .compare(&self.keys, &other.keys)
+            .then(self.run.cmp(&other.run))
+            .reverse()
+    }
+}
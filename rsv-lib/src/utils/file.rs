@@ -1,20 +1,25 @@
-use std::error::Error;
-use std::io::BufWriter;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader, Write},
-    path::Path,
-};
+use crate::utils::error::RsvResult;
+use std::{io::BufReader, path::Path};
 
+use super::cli_result::CliResult;
+use super::compress;
 use super::constants::MB_USIZE;
+use super::encoding::TextEncoding;
+use super::reader::RecordReader;
 use super::row_split::CsvRowSplitter;
+use super::writer::Writer;
 
-pub fn estimate_row_bytes(path: &Path) -> Result<f64, Box<dyn Error>> {
+pub fn estimate_row_bytes(
+    path: &Path,
+    sep: char,
+    quote: char,
+    enc: TextEncoding,
+) -> RsvResult<f64> {
     // read 20000 lines to estimate bytes per line
     let mut n = 0;
     let mut bytes = 0;
-    let file = File::open(path)?;
-    for l in BufReader::new(file).lines().skip(1) {
+    let file = compress::open(path, enc)?;
+    for l in RecordReader::new(BufReader::new(file), sep, quote).skip(1) {
         bytes += l.unwrap().len() + 1;
         n += 1;
 
@@ -27,11 +32,15 @@ pub fn estimate_row_bytes(path: &Path) -> Result<f64, Box<dyn Error>> {
     Ok((bytes as f64) / (n as f64))
 }
 
-pub fn column_n(path: &Path, sep: char, quote: char) -> Result<Option<usize>, Box<dyn Error>> {
+pub fn column_n(
+    path: &Path,
+    sep: char,
+    quote: char,
+    enc: TextEncoding,
+) -> RsvResult<Option<usize>> {
     // read
-    let rdr = BufReader::new(File::open(path)?);
+    let mut rdr = RecordReader::new(BufReader::new(compress::open(path, enc)?), sep, quote);
     let n = rdr
-        .lines()
         .next()
         .map(|i| i.ok())
         .unwrap_or_default()
@@ -41,26 +50,38 @@ pub fn column_n(path: &Path, sep: char, quote: char) -> Result<Option<usize>, Bo
 }
 
 #[allow(dead_code)]
-pub fn estimate_line_count_by_mb(path: &Path, mb: Option<usize>) -> usize {
-    match estimate_row_bytes(path) {
+pub fn estimate_line_count_by_mb(
+    path: &Path,
+    sep: char,
+    quote: char,
+    enc: TextEncoding,
+    mb: Option<usize>,
+) -> usize {
+    match estimate_row_bytes(path, sep, quote, enc) {
         // default chunk-size to 200mb or 10_0000 lines
         Ok(v) => ((mb.unwrap_or(200) * MB_USIZE) as f64 / v) as usize,
         Err(_) => 100_000,
     }
 }
 
-pub fn write_frequency_to_csv(path: &Path, names: &[String], freq: Vec<(String, usize)>) {
-    let mut wtr = BufWriter::new(File::create(path).unwrap());
-
+/// Write a frequency table, i.e., the values of the columns and their counts.
+pub fn write_frequency(
+    wtr: &mut Writer,
+    names: &[String],
+    freq: Vec<(Vec<String>, usize)>,
+) -> CliResult {
     // header
     if !names.is_empty() {
-        writeln!(wtr, "{}", names.join(",")).unwrap();
+        wtr.write_fields(names)?;
     }
 
     // content
-    for (k, v) in freq {
-        writeln!(wtr, "{k},{v}").unwrap();
+    for (mut k, n) in freq {
+        k.push(n.to_string());
+        wtr.write_fields(&k)?;
     }
+
+    Ok(())
 }
 
 pub fn is_excel(p: &Path) -> bool {
@@ -69,3 +90,23 @@ pub fn is_excel(p: &Path) -> bool {
         None => false,
     }
 }
+
+pub fn is_parquet(p: &Path) -> bool {
+    match p.extension() {
+        Some(e) => e == "parquet",
+        None => false,
+    }
+}
+
+pub fn is_json(p: &Path) -> bool {
+    match p.extension() {
+        Some(e) => e == "json" || e == "ndjson" || e == "jsonl",
+        None => false,
+    }
+}
+
+/// Files that are read into memory as a sheet, and processed the same way as
+/// EXCEL files.
+pub fn is_sheet(p: &Path) -> bool {
+    is_excel(p) || is_parquet(p) || is_json(p)
+}
@@ -1,8 +1,23 @@
-use std::path::{Path, PathBuf};
+use super::compress::{Codec, strip_extension};
+use super::file::is_sheet;
+use super::util::datetime_str;
+use std::{
+    fs::{create_dir_all, remove_dir_all},
+    io,
+    path::{Path, PathBuf},
+    process,
+};
 
 const BAD_FILENAME_CHARACTERS: [char; 9] = ['<', '>', ':', '\\', '/', '\\', '"', '?', '*'];
 
+// the compression extension is kept, e.g., data-selected.csv.gz for data.csv.gz
 pub fn new_path(path: &Path, suffix: &str) -> PathBuf {
+    if let Some(c) = Codec::from_extension(path) {
+        let mut p = new_path(&strip_extension(path), suffix).into_os_string();
+        p.push(format!(".{}", c.extension()));
+        return p.into();
+    }
+
     let p = path.with_file_name(format!(
         "{}{}",
         path.file_stem().unwrap().to_str().unwrap(),
@@ -27,6 +42,44 @@ pub fn str_to_filename(s: &str) -> String {
     s.replace(BAD_FILENAME_CHARACTERS, "")
 }
 
+/// Directory of split files, e.g., data-split-20240102150405 next to data.csv.gz,
+/// or split-20240102150405 in the current directory for stdin.
+pub fn split_dir(path: Option<&Path>) -> PathBuf {
+    match path {
+        Some(p) => {
+            let plain = strip_extension(p);
+            let stem = plain.file_stem().unwrap_or_default().to_string_lossy();
+            p.with_file_name(format!("{stem}-split-{}", datetime_str()))
+        }
+        None => new_file(&format!("split-{}", datetime_str())),
+    }
+}
+
+/// The nth file of a sequential split, e.g., data-split1.txt.gz for data.txt.gz,
+/// data-split1.csv for data.xlsx, or split1.csv for stdin.
+pub fn split_chunk_file(path: Option<&Path>, n: usize, compress_ext: &str) -> String {
+    let Some(p) = path else {
+        return format!("split{n}.csv{compress_ext}");
+    };
+
+    let plain = strip_extension(p);
+    let stem = plain.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = match is_sheet(p) {
+        true => None,
+        false => plain.extension().map(|e| e.to_string_lossy()),
+    };
+
+    format!(
+        "{stem}-split{n}.{}{compress_ext}",
+        extension.as_deref().unwrap_or("csv")
+    )
+}
+
+/// File of the rows with a value of the split column, e.g., east.csv.gz.
+pub fn split_value_file(value: &str, compress_ext: &str) -> String {
+    str_to_filename(value) + ".csv" + compress_ext
+}
+
 pub fn dir_file(dir: &Path, name: &str) -> PathBuf {
     let mut out = dir.to_path_buf();
     out.push(name);
@@ -41,3 +94,26 @@ pub fn full_path(f: &str) -> PathBuf {
 
     path
 }
+
+/// A directory in the system temp directory, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(prefix: &str) -> Result<Self, io::Error> {
+        let dir =
+            std::env::temp_dir().join(format!("{}-{}-{}", prefix, datetime_str(), process::id()));
+        create_dir_all(&dir)?;
+
+        Ok(TempDir(dir))
+    }
+
+    pub fn file(&self, name: &str) -> PathBuf {
+        dir_file(&self.0, name)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.0);
+    }
+}
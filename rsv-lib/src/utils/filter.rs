@@ -1,16 +1,27 @@
+use super::error::{RsvError, RsvResult};
 use super::{
+    column::{ColumnResolver, unquote},
+    encoding::TextEncoding,
     math_expr_parser::{AST, CompiledExpr},
     row_split::CsvRowSplitter,
 };
-use crate::utils::util::werr_exit;
-use regex::Regex;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-};
+use regex::{Regex, RegexBuilder};
+use std::{borrow::Cow, cmp::Ordering, path::Path};
+
+const FUNCTIONS: [&str; 4] = ["contains", "starts_with", "ends_with", "is_null"];
+
+// matching order is important
+const OPERATORS: [(&str, Op); 7] = [
+    ("!=", Op::NotEqual),
+    ("==", Op::Equal),
+    (">=", Op::Ge),
+    ("<=", Op::Le),
+    ("=", Op::Equal),
+    (">", Op::Gt),
+    ("<", Op::Lt),
+];
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Op {
     Equal,
     NotEqual,
@@ -21,312 +32,606 @@ enum Op {
 }
 
 impl Op {
-    fn evaluate(&self, a: f64, b: f64) -> bool {
+    fn evaluate(&self, o: Ordering) -> bool {
         match self {
-            Op::Equal => a == b,
-            Op::NotEqual => a != b,
-            Op::Gt => a > b,
-            Op::Ge => a >= b,
-            Op::Lt => a < b,
-            Op::Le => a <= b,
+            Op::Equal => o.is_eq(),
+            Op::NotEqual => o.is_ne(),
+            Op::Gt => o.is_gt(),
+            Op::Ge => o.is_ge(),
+            Op::Lt => o.is_lt(),
+            Op::Le => o.is_le(),
         }
     }
 }
 
-struct FilterItem {
-    col: usize,
-    is_numeric: bool,
-    op: Op,
-    f64_value: f64,
-    str_value: String,
-    f64_values: Vec<f64>,
-    str_values: Vec<String>,
-    is_math_expr: bool,
-    ast: Box<CompiledExpr>,
+enum Value {
+    Str(String),
+    Num(f64),
+    Math(Box<CompiledExpr>),
 }
 
-pub struct Filter<'a> {
-    raw: &'a str,
-    total: Option<usize>,
-    path: Option<&'a Path>,
-    sep: char,
-    quote: char,
-    filters: Vec<FilterItem>,
-    pub parsed: bool,
+enum Condition {
+    // = matches any of the values, != matches none of them
+    Compare(Op, Vec<Value>),
+    Between(Value, Value),
+    Regex(Regex),
+    Contains(Vec<String>),
+    StartsWith(Vec<String>),
+    EndsWith(Vec<String>),
+    IsNull,
 }
 
-fn parse_col_usize(col: &str) -> usize {
-    col.parse().unwrap_or_else(|_| {
-        werr_exit!(
-            "{}",
-            "Column syntax error: can be something like 0 (first column), -1 (last column)."
-        );
-    })
+struct Predicate {
+    col: usize,
+    ignore_case: bool,
+    cond: Condition,
 }
 
-fn parse_i32(col: &str) -> i32 {
-    col.parse().unwrap_or_else(|_| {
-        werr_exit!(
-            "{}",
-            "Column syntax error: can be something like 0 (first column), -1 (last column)."
-        );
-    })
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Predicate(Predicate),
+}
+
+// a valid row, with its fields when they are split by the filter
+type ValidRow<'b> = (Option<&'b str>, Option<Vec<Cow<'b, str>>>);
+
+pub struct Filter<'a> {
+    raw: &'a str,
+    resolver: ColumnResolver<'a>,
+    expr: Option<Expr>,
+    pub parsed: bool,
 }
 
 impl<'a> Filter<'a> {
     pub fn new(raw: &'a str) -> Self {
         Filter {
             raw,
-            total: None,
-            path: None,
-            sep: ',',
-            quote: '"',
-            filters: vec![],
+            resolver: ColumnResolver::default(),
+            expr: None,
             parsed: false,
         }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.filters.is_empty()
+        self.expr.is_none()
     }
 
     pub fn total_col(mut self, total: usize) -> Self {
-        self.total = Some(total);
+        self.resolver.set_total(total);
         self
     }
 
-    pub fn total_col_of(mut self, path: &'a Path, sep: char, quote: char) -> Self {
-        self.path = Some(path);
-        self.sep = sep;
-        self.quote = quote;
+    pub fn total_col_of(
+        mut self,
+        path: &'a Path,
+        sep: char,
+        quote: char,
+        enc: TextEncoding,
+    ) -> Self {
+        self.resolver.set_path(path, sep, quote, enc);
         self
     }
 
-    fn true_col(&mut self, col: &str) -> usize {
-        if col.starts_with('-') {
-            if self.total.is_none() {
-                let mut first_line = String::new();
-                let f = File::open(self.path.unwrap()).expect("unable to open file.");
-                BufReader::new(f)
-                    .read_line(&mut first_line)
-                    .expect("read error.");
-                self.total = Some(CsvRowSplitter::new(&first_line, self.sep, self.quote).count());
-            }
-            let i = (self.total.unwrap() as i32) + parse_i32(col);
-            if i < 0 {
-                werr_exit!("Column {} does not exist.", col);
-            }
-            i as usize
+    /// Header names used to resolve column names, e.g., the first row of an Excel file.
+    pub fn header(mut self, names: Vec<String>) -> Self {
+        self.resolver.set_names(names);
+        self
+    }
+
+    pub fn parse(mut self) -> RsvResult<Self> {
+        self.parsed = true;
+
+        if self.raw.trim().is_empty() {
+            return Ok(self);
+        }
+
+        let mut p = Parser {
+            src: self.raw,
+            pos: 0,
+            resolver: &mut self.resolver,
+        };
+        self.expr = Some(p.parse()?);
+
+        Ok(self)
+    }
+
+    pub fn record_is_valid<T: AsRef<str>>(&self, row: &[T]) -> bool {
+        self.expr.as_ref().is_none_or(|e| e.evaluate(row))
+    }
+
+    pub fn record_valid_map<'b>(
+        &self,
+        row: &'b str,
+        sep: char,
+        quote: char,
+    ) -> Option<ValidRow<'b>> {
+        if self.is_empty() {
+            return Some((Some(row), None));
+        }
+
+        let v = CsvRowSplitter::new(row, sep, quote).collect::<Vec<_>>();
+        if self.record_is_valid(&v) {
+            Some((Some(row), Some(v)))
         } else {
-            parse_col_usize(col)
+            None
         }
     }
 
-    pub fn parse(mut self) -> Self {
-        self.parsed = true;
+    pub fn excel_record_is_valid<T: AsRef<str>>(&self, row: &[T]) -> bool {
+        self.record_is_valid(row)
+    }
+}
 
-        if self.raw.is_empty() {
-            return self;
+// Recursive descent parser of the filter expression:
+//   expr      := and ('|' and)*
+//   and       := unary ('&' unary)*
+//   unary     := '!' unary | '(' expr ')' | predicate
+//   predicate := column op values
+//              | column ['not'] 'in' '(' values ')'
+//              | column ['not'] 'between' value 'and' value
+//              | column ['!']'~' regex
+//              | function '(' column [',' values] ')'
+// A value is either quoted, or runs until the next top-level &, | or ).
+struct Parser<'a, 'b> {
+    src: &'b str,
+    pos: usize,
+    resolver: &'b mut ColumnResolver<'a>,
+}
+
+impl<'b> Parser<'_, 'b> {
+    fn parse(&mut self) -> RsvResult<Expr> {
+        let expr = self.parse_or()?;
+        self.skip_ws();
+        if self.pos < self.src.len() {
+            return Err(self.error());
         }
+        Ok(expr)
+    }
 
-        self.raw
-            .split('&')
-            .filter(|&i| !i.is_empty())
-            .for_each(|one| self.parse_one(one));
+    fn error(&self) -> RsvError {
+        RsvError::FilterSyntax {
+            filter: self.src.to_owned(),
+            pos: self.pos,
+        }
+    }
 
-        self
+    fn rest(&self) -> &'b str {
+        &self.src[self.pos..]
     }
 
-    fn parse_one(&mut self, one: &str) {
-        // matching order is important
-        let re = Regex::new("!=|>=|<=|=|>|<").unwrap();
-        let v = re.split(one).collect::<Vec<_>>();
+    fn skip_ws(&mut self) {
+        let r = self.rest();
+        self.pos += r.len() - r.trim_start().len();
+    }
 
-        if v.len() != 2 {
-            werr_exit!("Error: Filter syntax is wrong, run <rsv select -h> for help.");
+    fn eat(&mut self, s: &str) -> bool {
+        self.skip_ws();
+        let matched = self.rest().starts_with(s);
+        if matched {
+            self.pos += s.len();
         }
+        matched
+    }
 
-        // parse column
-        let mut col = v[0].to_owned();
-        let is_numeric = col.ends_with(['n', 'N']);
-        if is_numeric {
-            col.pop();
+    fn eat_keyword(&mut self, kw: &str) -> bool {
+        self.skip_ws();
+        let r = self.rest();
+        let matched = r
+            .get(..kw.len())
+            .is_some_and(|i| i.eq_ignore_ascii_case(kw))
+            && !r[kw.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if matched {
+            self.pos += kw.len();
         }
-        let col = { self.true_col(&col) };
+        matched
+    }
 
-        // check whether rhs is a math expr
-        // @1 or c1 represents first column
-        let is_math_expr = v[1].contains(['+', '*', '/', '%', '^', '(', '@', 'c'])
-            || v[1].rfind('-').unwrap_or(0) > 0;
+    fn parse_or(&mut self) -> RsvResult<Expr> {
+        let mut lhs = self.parse_and()?;
+        while self.eat("||") || self.eat("|") {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.parse_and()?));
+        }
+        Ok(lhs)
+    }
 
-        let mut item = FilterItem {
-            col,
-            is_numeric,
-            op: Op::NotEqual,
-            f64_value: 0.0,
-            f64_values: vec![],
-            str_value: String::new(),
-            str_values: vec![],
-            is_math_expr,
-            ast: Box::new(AST::parse("")),
+    fn parse_and(&mut self) -> RsvResult<Expr> {
+        let mut lhs = self.parse_unary()?;
+        while self.eat("&&") || self.eat("&") {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.parse_unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> RsvResult<Expr> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(self.error());
+            }
+            return Ok(expr);
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> RsvResult<Expr> {
+        self.skip_ws();
+
+        // function call, e.g., contains(name, abc)
+        let start = self.pos;
+        let word = self
+            .rest()
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if FUNCTIONS.contains(&word.as_str()) {
+            self.pos += word.len();
+            if self.eat("(") {
+                return self.parse_function(&word);
+            }
+            self.pos = start;
+        }
+
+        let (col, numeric, ignore_case) = self.parse_column()?;
+        let predicate = |cond| {
+            Expr::Predicate(Predicate {
+                col,
+                ignore_case,
+                cond,
+            })
         };
 
-        // parse filter, matching order is important
-        match (is_math_expr, is_numeric) {
-            (true, _) => {
-                item.op = if one.contains("!=") {
-                    Op::NotEqual
-                } else if one.contains(">=") {
-                    Op::Ge
-                } else if one.contains("<=") {
-                    Op::Le
-                } else if one.contains('=') {
-                    Op::Equal
-                } else if one.contains('>') {
-                    Op::Gt
-                } else if one.contains('<') {
-                    Op::Lt
-                } else {
-                    Op::NotEqual
-                };
-                item.ast = Box::new(AST::parse(v[1]));
+        // keyword operators
+        let negated = self.eat_keyword("not");
+        if self.eat_keyword("in") {
+            if !self.eat("(") {
+                return Err(self.error());
             }
-            (false, true) => {
-                if one.contains("!=") {
-                    item.op = Op::NotEqual;
-                    item.f64_values = parse_f64_vec(v[1]);
-                } else if one.contains(">=") {
-                    item.op = Op::Ge;
-                    item.f64_value = parse_f64(v[1]);
-                } else if one.contains("<=") {
-                    item.op = Op::Le;
-                    item.f64_value = parse_f64(v[1]);
-                } else if one.contains('=') {
-                    item.op = Op::Equal;
-                    item.f64_values = parse_f64_vec(v[1]);
-                } else if one.contains('>') {
-                    item.op = Op::Gt;
-                    item.f64_value = parse_f64(v[1]);
-                } else if one.contains('<') {
-                    item.op = Op::Lt;
-                    item.f64_value = parse_f64(v[1]);
-                }
+            let raw = self.read_value();
+            let values = self.values(raw, numeric, ignore_case)?;
+            if !self.eat(")") {
+                return Err(self.error());
             }
-            (false, false) => {
-                if one.contains("!=") {
-                    item.op = Op::NotEqual;
-                    item.str_values = v[1].split(',').map(String::from).collect::<Vec<_>>();
-                } else if one.contains(">=") {
-                    item.op = Op::Ge;
-                    item.str_value = v[1].to_owned();
-                } else if one.contains("<=") {
-                    item.op = Op::Le;
-                    item.str_value = v[1].to_owned();
-                } else if one.contains('=') {
-                    item.op = Op::Equal;
-                    item.str_values = v[1].split(',').map(String::from).collect::<Vec<_>>();
-                } else if one.contains('>') {
-                    item.op = Op::Gt;
-                    item.str_value = v[1].to_owned();
-                } else if one.contains('<') {
-                    item.op = Op::Lt;
-                    item.str_value = v[1].to_owned()
+            let op = if negated { Op::NotEqual } else { Op::Equal };
+            return Ok(predicate(Condition::Compare(op, values)));
+        }
+        if self.eat_keyword("between") {
+            let raw = self.read_value();
+            let Some((lo, hi)) = split_between(raw) else {
+                return Err(self.error());
+            };
+            let cond = Condition::Between(
+                self.value(lo, numeric, ignore_case)?,
+                self.value(hi, numeric, ignore_case)?,
+            );
+            return Ok(match negated {
+                true => Expr::Not(Box::new(predicate(cond))),
+                false => predicate(cond),
+            });
+        }
+        if negated {
+            return Err(self.error());
+        }
+
+        // regex
+        let negated = self.eat("!~");
+        if negated || self.eat("~") {
+            let raw = self.read_value();
+            let pattern = unquote(raw).unwrap_or(raw);
+            let re = RegexBuilder::new(pattern)
+                .case_insensitive(ignore_case)
+                .build()?;
+            return Ok(match negated {
+                true => Expr::Not(Box::new(predicate(Condition::Regex(re)))),
+                false => predicate(Condition::Regex(re)),
+            });
+        }
+
+        // comparison
+        let Some(&(_, op)) = OPERATORS.iter().find(|(s, _)| self.eat(s)) else {
+            return Err(self.error());
+        };
+        let raw = self.read_value();
+        let values = match op {
+            Op::Equal | Op::NotEqual => self.values(raw, numeric, ignore_case)?,
+            _ => vec![self.value(raw, numeric, ignore_case)?],
+        };
+        Ok(predicate(Condition::Compare(op, values)))
+    }
+
+    fn parse_function(&mut self, name: &str) -> RsvResult<Expr> {
+        let (col, _, ignore_case) = self.parse_column()?;
+
+        let cond = match name {
+            "is_null" => Condition::IsNull,
+            _ => {
+                if !self.eat(",") {
+                    return Err(self.error());
+                }
+                let values = split_values(self.read_value())
+                    .into_iter()
+                    .map(|v| if ignore_case { v.to_lowercase() } else { v })
+                    .collect();
+                match name {
+                    "contains" => Condition::Contains(values),
+                    "starts_with" => Condition::StartsWith(values),
+                    _ => Condition::EndsWith(values),
                 }
             }
+        };
+
+        if !self.eat(")") {
+            return Err(self.error());
         }
 
-        self.filters.push(item);
+        Ok(Expr::Predicate(Predicate {
+            col,
+            ignore_case,
+            cond,
+        }))
     }
 
-    // todo
-    pub fn record_is_valid<T: AsRef<str>>(&self, row: &[T]) -> bool {
-        self.filters.iter().all(|item| item.record_is_valid(row))
+    // A trailing n or N marks a numeric column, e.g., 0N or amountN, and a trailing
+    // i or I marks a case-insensitive column, e.g., nameI. A column name that itself
+    // ends with these letters, e.g., region, takes precedence over the markers.
+    fn parse_column(&mut self) -> RsvResult<(usize, bool, bool)> {
+        let col = self.read_column()?;
+        if let Some(i) = self.resolver.try_resolve_one(col)? {
+            return Ok((i, false, false));
+        }
+
+        match col.strip_suffix(['n', 'N', 'i', 'I']) {
+            Some(c) => {
+                let marker = col[c.len()..].to_ascii_uppercase();
+                Ok((self.resolver.resolve_one(c)?, marker == "N", marker == "I"))
+            }
+            None => Ok((self.resolver.resolve_one(col)?, false, false)),
+        }
     }
 
-    pub fn record_valid_map<'b>(
-        &self,
-        row: &'b str,
-        sep: char,
-        quote: char,
-    ) -> Option<(Option<&'b str>, Option<Vec<&'b str>>)> {
-        if self.is_empty() {
-            return Some((Some(row), None));
+    // a column is an index, a name, or a quoted name, followed by optional markers
+    fn read_column(&mut self) -> RsvResult<&'b str> {
+        self.skip_ws();
+        let r = self.rest();
+
+        let end = match r.chars().next() {
+            Some(q @ ('"' | '\'')) => match r[1..].find(q) {
+                Some(i) => {
+                    let end = i + 2;
+                    end + r[end..]
+                        .find(|c: char| !c.is_ascii_alphabetic())
+                        .unwrap_or(r.len() - end)
+                }
+                None => return Err(self.error()),
+            },
+            _ => r
+                .find(|c: char| c.is_whitespace() || "=!<>~&|(),".contains(c))
+                .unwrap_or(r.len()),
+        };
+        if end == 0 {
+            return Err(self.error());
         }
 
-        let v = CsvRowSplitter::new(row, sep, quote).collect::<Vec<_>>();
-        if self.record_is_valid(&v) {
-            Some((Some(row), Some(v)))
-        } else {
-            None
+        self.pos += end;
+        Ok(&r[..end])
+    }
+
+    // a value runs until the next top-level &, | or unmatched ), except within quotes
+    fn read_value(&mut self) -> &'b str {
+        self.skip_ws();
+        let r = self.rest();
+
+        let mut end = r.len();
+        let mut depth = 0;
+        let mut quote = None;
+        let mut item_start = 0;
+        for (i, c) in r.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '"' | '\'' if r[item_start..i].trim().is_empty() => quote = Some(c),
+                    ',' => item_start = i + 1,
+                    '(' => depth += 1,
+                    ')' if depth > 0 => depth -= 1,
+                    ')' | '&' | '|' if depth == 0 => {
+                        end = i;
+                        break;
+                    }
+                    _ => {}
+                },
+            }
         }
+
+        self.pos += end;
+        r[..end].trim()
     }
 
-    pub fn excel_record_is_valid<T: AsRef<str>>(&self, row: &[T]) -> bool {
-        if self.is_empty() {
-            return true;
+    fn values(&self, raw: &str, numeric: bool, ignore_case: bool) -> RsvResult<Vec<Value>> {
+        split_values(raw)
+            .iter()
+            .map(|v| self.value(v, numeric, ignore_case))
+            .collect()
+    }
+
+    fn value(&self, raw: &str, numeric: bool, ignore_case: bool) -> RsvResult<Value> {
+        let raw = raw.trim();
+
+        if let Some(v) = unquote(raw) {
+            return Ok(match (numeric, ignore_case) {
+                (true, _) => Value::Num(parse_f64(v)?),
+                (false, true) => Value::Str(v.to_lowercase()),
+                (false, false) => Value::Str(v.to_owned()),
+            });
+        }
+
+        if is_math_expr(raw, numeric) {
+            return Ok(Value::Math(Box::new(AST::parse(raw)?)));
         }
-        self.filters.iter().all(|item| item.record_is_valid(row))
+
+        Ok(match (numeric, ignore_case) {
+            (true, _) => Value::Num(parse_f64(raw)?),
+            (false, true) => Value::Str(raw.to_lowercase()),
+            (false, false) => Value::Str(raw.to_owned()),
+        })
     }
 }
 
-pub fn parse_f64(s: &str) -> f64 {
-    s.parse::<f64>().unwrap_or_else(|_| {
-        werr_exit!("Error: <{s}> is not a valid number, run <rsv select -h> for help.");
-    })
+// A math expression references other columns with @, e.g., 0>@1+1. For numeric
+// columns, plain arithmetic like 0N>10*2 is supported too. Otherwise values like
+// 2022-01-21 are compared as strings.
+fn is_math_expr(raw: &str, numeric: bool) -> bool {
+    let math_chars = raw
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_whitespace() || "@c.e_+-*/%^()".contains(c));
+    let has_op = raw.contains(['+', '*', '/', '%', '^', '(']) || raw.rfind('-').unwrap_or(0) > 0;
+
+    math_chars && (raw.contains('@') || (numeric && has_op))
 }
 
-fn parse_f64_vec(s: &str) -> Vec<f64> {
-    s.split(',')
+// split values by comma, except for commas within quotes
+fn split_values(raw: &str) -> Vec<String> {
+    let mut out = vec![];
+    let mut start = 0;
+    let mut quote = None;
+
+    for (i, c) in raw.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if (c == '"' || c == '\'') && raw[start..i].trim().is_empty() => quote = Some(c),
+            None if c == ',' => {
+                out.push(&raw[start..i]);
+                start = i + 1;
+            }
+            None => {}
+        }
+    }
+    out.push(&raw[start..]);
+
+    out.into_iter()
         .map(|i| {
-            i.parse::<f64>().unwrap_or_else(|_| {
-                werr_exit!("Error: <{i}> is not a number, run <rsv select -h> for help.")
-            })
+            let i = i.trim();
+            unquote(i).unwrap_or(i).to_owned()
         })
         .collect()
 }
 
-impl FilterItem {
-    fn record_is_valid<T: AsRef<str>>(&self, row: &[T]) -> bool {
-        match (self.is_math_expr, self.is_numeric, &self.op) {
-            (true, _, _) => match row[self.col].as_ref().parse::<f64>() {
-                Ok(v) => match self.ast.max_column() {
-                    0 => self.op.evaluate(v, self.ast.evaluate(None)),
-                    _ => {
-                        let f64_vec: Vec<f64> = (0..=self.ast.max_column())
-                            .map(|i| match self.ast.contains_column(&i) {
-                                true => parse_f64(row[i].as_ref()),
-                                false => 0.0,
-                            })
-                            .collect();
-                        self.op.evaluate(v, self.ast.evaluate(Some(&f64_vec)))
-                    }
-                },
-                Err(_) => false,
-            },
-            (false, true, Op::Equal) => match row[self.col].as_ref().parse::<f64>() {
-                Ok(v) => self.f64_values.contains(&v),
-                Err(_) => false,
-            },
-            (false, true, Op::NotEqual) => match row[self.col].as_ref().parse::<f64>() {
-                Ok(v) => !self.f64_values.contains(&v),
-                Err(_) => true,
-            },
-            (false, true, _) => match row[self.col].as_ref().parse::<f64>() {
-                Ok(v) => self.op.evaluate(v, self.f64_value),
-                Err(_) => false,
-            },
-            (false, false, Op::Equal) => self
-                .str_values
+// e.g., 10 and 20
+fn split_between(raw: &str) -> Option<(&str, &str)> {
+    let lower = raw.to_ascii_lowercase();
+    let i = lower.find(" and ")?;
+    Some((&raw[..i], &raw[i + 5..]))
+}
+
+pub fn parse_f64(s: &str) -> RsvResult<f64> {
+    s.trim().parse::<f64>().map_err(|_| {
+        RsvError::BadNumber(format!(
+            "<{s}> is not a valid number, run <rsv select -h> for help."
+        ))
+    })
+}
+
+impl Expr {
+    fn evaluate<T: AsRef<str>>(&self, row: &[T]) -> bool {
+        match self {
+            Expr::And(a, b) => a.evaluate(row) && b.evaluate(row),
+            Expr::Or(a, b) => a.evaluate(row) || b.evaluate(row),
+            Expr::Not(a) => !a.evaluate(row),
+            Expr::Predicate(p) => p.evaluate(row),
+        }
+    }
+}
+
+impl Predicate {
+    fn evaluate<T: AsRef<str>>(&self, row: &[T]) -> bool {
+        // a missing field is regarded as empty
+        let field = row.get(self.col).map_or("", |f| f.as_ref());
+        let field = match self.ignore_case {
+            true => Cow::Owned(field.to_lowercase()),
+            false => Cow::Borrowed(field),
+        };
+
+        match &self.cond {
+            Condition::Compare(Op::NotEqual, values) => !values
                 .iter()
-                .any(|i| i.as_str() == row[self.col].as_ref()),
-            (false, false, Op::NotEqual) => !self
-                .str_values
+                .any(|v| v.compare(&field, row) == Some(Ordering::Equal)),
+            Condition::Compare(op, values) => values
                 .iter()
-                .any(|i| i.as_str() == row[self.col].as_ref()),
-            (false, false, Op::Ge) => row[self.col].as_ref() >= &self.str_value,
-            (false, false, Op::Gt) => row[self.col].as_ref() > &self.str_value,
-            (false, false, Op::Le) => row[self.col].as_ref() <= &self.str_value,
-            (false, false, Op::Lt) => row[self.col].as_ref() < &self.str_value,
+                .any(|v| v.compare(&field, row).is_some_and(|o| op.evaluate(o))),
+            Condition::Between(lo, hi) => {
+                lo.compare(&field, row).is_some_and(Ordering::is_ge)
+                    && hi.compare(&field, row).is_some_and(Ordering::is_le)
+            }
+            Condition::Regex(re) => re.is_match(&field),
+            Condition::Contains(v) => v.iter().any(|i| field.contains(i.as_str())),
+            Condition::StartsWith(v) => v.iter().any(|i| field.starts_with(i.as_str())),
+            Condition::EndsWith(v) => v.iter().any(|i| field.ends_with(i.as_str())),
+            Condition::IsNull => field.trim().is_empty(),
         }
     }
 }
+
+impl Value {
+    // None when the field, or a column used in a math expression, is not a number
+    fn compare<T: AsRef<str>>(&self, field: &str, row: &[T]) -> Option<Ordering> {
+        match self {
+            Value::Str(v) => Some(field.cmp(v.as_str())),
+            Value::Num(v) => field.trim().parse::<f64>().ok()?.partial_cmp(v),
+            Value::Math(ast) => {
+                let f = field.trim().parse::<f64>().ok()?;
+                let cols = (0..=ast.max_column())
+                    .map(|i| match ast.contains_column(&i) {
+                        true => row.get(i)?.as_ref().trim().parse::<f64>().ok(),
+                        false => Some(0.0),
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                f.partial_cmp(&ast.evaluate(Some(&cols)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Filter;
+
+    fn matched(filter: &str, rows: &[[&str; 3]]) -> Vec<usize> {
+        let names = ["id", "name", "amount"].map(String::from).to_vec();
+        let f = Filter::new(filter).header(names).parse().unwrap();
+        (0..rows.len())
+            .filter(|&i| f.record_is_valid(&rows[i]))
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_expr() {
+        let rows = [
+            ["1", "apple", "10"],
+            ["2", "Banana", "200"],
+            ["3", "test_cherry", ""],
+            ["4", "date", "50"],
+        ];
+
+        assert_eq!(matched("0=1,3", &rows), [0, 2]);
+        assert_eq!(matched("2!=", &rows), [0, 1, 3]);
+        assert_eq!(matched("amountN>=50", &rows), [1, 3]);
+        assert_eq!(matched("(0=1 | 2N>100) & !(1 ~ \"^test\")", &rows), [0, 1]);
+        assert_eq!(matched("!(0=1|0=2)", &rows), [2, 3]);
+        assert_eq!(matched("contains(name, an, err)", &rows), [1, 2]);
+        assert_eq!(matched("starts_with(nameI, b)", &rows), [1]);
+        assert_eq!(matched("ends_with(name, e)", &rows), [0, 3]);
+        assert_eq!(matched("is_null(amount)", &rows), [2]);
+        assert_eq!(matched("name in (apple, 'date')", &rows), [0, 3]);
+        assert_eq!(matched("nameI not in (BANANA)", &rows), [0, 2, 3]);
+        assert_eq!(matched("amountN between 10 and 50", &rows), [0, 3]);
+        assert_eq!(matched("nameI=banana", &rows), [1]);
+        assert_eq!(matched("2>@0*20", &rows), [1]);
+        assert_eq!(matched("1>=c", &rows), [2, 3]);
+    }
+}
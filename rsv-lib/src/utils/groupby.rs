@@ -0,0 +1,424 @@
+use super::{
+    column::{ColumnResolver, split_column_spec},
+    util::{fmt_f64, is_null},
+};
+use crate::utils::error::{RsvError, RsvResult};
+use ahash::{HashMap, HashSet};
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggFunc {
+    Count,
+    CountDistinct,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Median,
+}
+
+impl FromStr for AggFunc {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let f = match s.trim().to_lowercase().as_str() {
+            "count" | "n" => AggFunc::Count,
+            "countd" | "nunique" => AggFunc::CountDistinct,
+            "sum" => AggFunc::Sum,
+            "mean" | "avg" => AggFunc::Mean,
+            "min" => AggFunc::Min,
+            "max" => AggFunc::Max,
+            "median" => AggFunc::Median,
+            _ => {
+                return Err(RsvError::ExprSyntax(format!(
+                    "unknown aggregation <{s}>, can be one of count, countd, sum, mean, min, max and median."
+                )));
+            }
+        };
+
+        Ok(f)
+    }
+}
+
+impl Display for AggFunc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AggFunc::Count => "count",
+            AggFunc::CountDistinct => "countd",
+            AggFunc::Sum => "sum",
+            AggFunc::Mean => "mean",
+            AggFunc::Min => "min",
+            AggFunc::Max => "max",
+            AggFunc::Median => "median",
+        };
+        f.write_str(s)
+    }
+}
+
+// An aggregation of a value column. count without a column counts rows.
+#[derive(Debug, Clone, Copy)]
+pub struct Aggregation {
+    func: AggFunc,
+    col: Option<usize>,
+}
+
+impl Aggregation {
+    pub(crate) fn new(func: AggFunc, col: Option<usize>) -> Self {
+        Aggregation { func, col }
+    }
+
+    pub(crate) fn func(&self) -> AggFunc {
+        self.func
+    }
+
+    pub(crate) fn col(&self) -> Option<usize> {
+        self.col
+    }
+
+    pub(crate) fn accumulator(&self) -> Accumulator {
+        match self.func {
+            AggFunc::Count => Accumulator::Count(0),
+            AggFunc::CountDistinct => Accumulator::Distinct(HashSet::default()),
+            AggFunc::Sum | AggFunc::Mean => Accumulator::Sum { total: 0.0, n: 0 },
+            AggFunc::Min | AggFunc::Max => Accumulator::Extreme {
+                max: self.func == AggFunc::Max,
+                num: None,
+                text: None,
+                numeric: true,
+            },
+            AggFunc::Median => Accumulator::Values(vec![]),
+        }
+    }
+}
+
+// Partial aggregate of a group, which can be merged with another partial
+// aggregate of the same group, e.g., computed from another chunk.
+#[derive(Debug)]
+pub(crate) enum Accumulator {
+    Count(usize),
+    Distinct(HashSet<String>),
+    Sum {
+        total: f64,
+        n: usize,
+    },
+    // min and max are compared numerically when all values are numbers,
+    // and lexicographically otherwise.
+    Extreme {
+        max: bool,
+        num: Option<f64>,
+        text: Option<String>,
+        numeric: bool,
+    },
+    Values(Vec<f64>),
+}
+
+pub struct GroupBy {
+    keys: Vec<usize>,
+    aggs: Vec<Aggregation>,
+    max_col: usize,
+    groups: HashMap<Vec<String>, Vec<Accumulator>>,
+    bad_rows: usize,
+}
+
+impl GroupBy {
+    // Keys follow the column syntax, e.g., 0,2 or region-date, and aggregations
+    // are comma-separated functions of columns, e.g., sum(5),mean(amount),count.
+    // A column pattern in an aggregation expands to one aggregation per column,
+    // e.g., sum(amount_*).
+    pub fn new(keys: &str, aggs: &str, resolver: &mut ColumnResolver) -> RsvResult<Self> {
+        let mut key_cols = vec![];
+        for i in split_column_spec(keys) {
+            key_cols.extend(resolver.resolve(i)?);
+        }
+        let keys = key_cols;
+        if keys.is_empty() {
+            return Err(RsvError::InvalidArgument(
+                "no key column is specified.".to_owned(),
+            ));
+        }
+
+        let mut r = vec![];
+        for i in split_aggregations(aggs) {
+            let syntax_error =
+                || format!("aggregation syntax error for <{i}>. Run <rsv groupby -h> for help.");
+
+            let (func, col) = match i.split_once('(') {
+                Some((f, c)) => match c.strip_suffix(')') {
+                    Some(c) => (f.parse::<AggFunc>()?, c.trim()),
+                    None => return Err(RsvError::ExprSyntax(syntax_error())),
+                },
+                None => (i.parse::<AggFunc>()?, ""),
+            };
+
+            if col.is_empty() || col == "*" {
+                if func != AggFunc::Count {
+                    return Err(RsvError::ExprSyntax(syntax_error()));
+                }
+                r.push(Aggregation { func, col: None });
+                continue;
+            }
+
+            for c in resolver.resolve(col)? {
+                r.push(Aggregation { func, col: Some(c) })
+            }
+        }
+
+        // default to count rows of groups
+        if r.is_empty() {
+            r.push(Aggregation {
+                func: AggFunc::Count,
+                col: None,
+            });
+        }
+
+        let max_col = keys
+            .iter()
+            .chain(r.iter().filter_map(|a| a.col.as_ref()))
+            .max()
+            .copied()
+            .unwrap_or_default();
+
+        Ok(GroupBy {
+            keys,
+            aggs: r,
+            max_col,
+            groups: HashMap::default(),
+            bad_rows: 0,
+        })
+    }
+
+    /// A GroupBy with the same keys and aggregations but no data, to hold partial
+    /// aggregates of a chunk.
+    pub fn empty(&self) -> Self {
+        GroupBy {
+            keys: self.keys.clone(),
+            aggs: self.aggs.clone(),
+            max_col: self.max_col,
+            groups: HashMap::default(),
+            bad_rows: 0,
+        }
+    }
+
+    /// Output header, e.g., region,sum(amount),count.
+    pub fn header(&self, names: &[String]) -> Vec<String> {
+        let name = |i: usize| names.get(i).cloned().unwrap_or_else(|| format!("col{i}"));
+
+        self.keys
+            .iter()
+            .map(|&i| name(i))
+            .chain(self.aggs.iter().map(|a| match a.col {
+                Some(c) => format!("{}({})", a.func, name(c)),
+                None => a.func.to_string(),
+            }))
+            .collect()
+    }
+
+    pub fn parse_line_by_fields<T: AsRef<str>>(&mut self, v: &[T]) {
+        if self.max_col >= v.len() {
+            self.bad_rows += 1;
+            return;
+        }
+
+        let key = self
+            .keys
+            .iter()
+            .map(|&i| v[i].as_ref().to_owned())
+            .collect::<Vec<_>>();
+        let aggs = &self.aggs;
+        let acc = self
+            .groups
+            .entry(key)
+            .or_insert_with(|| aggs.iter().map(|a| a.accumulator()).collect());
+
+        aggs.iter().zip(acc).for_each(|(a, acc)| match a.col {
+            Some(c) => acc.update(v[c].as_ref()),
+            None => acc.update_row(),
+        });
+    }
+
+    pub fn merge(&mut self, other: GroupBy) {
+        self.bad_rows += other.bad_rows;
+        for (k, o) in other.groups {
+            match self.groups.get_mut(&k) {
+                Some(acc) => acc.iter_mut().zip(o).for_each(|(a, b)| a.merge(b)),
+                None => {
+                    self.groups.insert(k, o);
+                }
+            }
+        }
+    }
+
+    pub fn group_n(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Number of rows ignored for having fewer columns than the keys and
+    /// aggregations need.
+    pub fn bad_rows(&self) -> usize {
+        self.bad_rows
+    }
+
+    /// Aggregated rows ordered by keys.
+    pub fn into_rows(self) -> Vec<Vec<String>> {
+        let mut groups = self.groups.into_iter().collect::<Vec<_>>();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+        groups
+            .into_iter()
+            .map(|(mut k, acc)| {
+                self.aggs
+                    .iter()
+                    .zip(acc)
+                    .for_each(|(a, acc)| k.push(acc.value(a.func)));
+                k
+            })
+            .collect()
+    }
+}
+
+impl Accumulator {
+    pub(crate) fn update_row(&mut self) {
+        if let Accumulator::Count(n) = self {
+            *n += 1
+        }
+    }
+
+    // null values are ignored, and so are non-numeric values for numeric aggregations
+    pub(crate) fn update(&mut self, f: &str) {
+        if is_null(f) {
+            return;
+        }
+
+        match self {
+            Accumulator::Count(n) => *n += 1,
+            Accumulator::Distinct(s) => {
+                if !s.contains(f) {
+                    s.insert(f.to_owned());
+                }
+            }
+            Accumulator::Sum { total, n } => {
+                if let Ok(v) = f.trim().parse::<f64>() {
+                    *total += v;
+                    *n += 1;
+                }
+            }
+            Accumulator::Extreme {
+                max,
+                num,
+                text,
+                numeric,
+            } => {
+                match f.trim().parse::<f64>() {
+                    Ok(v) if num.is_none_or(|n| better(v, n, *max)) => *num = Some(v),
+                    Ok(_) => {}
+                    Err(_) => *numeric = false,
+                }
+                if text.as_deref().is_none_or(|t| better(f, t, *max)) {
+                    *text = Some(f.to_owned())
+                }
+            }
+            Accumulator::Values(v) => {
+                if let Ok(f) = f.trim().parse::<f64>() {
+                    v.push(f)
+                }
+            }
+        }
+    }
+
+    pub(crate) fn merge(&mut self, o: Accumulator) {
+        match (self, o) {
+            (Accumulator::Count(a), Accumulator::Count(b)) => *a += b,
+            (Accumulator::Distinct(a), Accumulator::Distinct(b)) => a.extend(b),
+            (Accumulator::Sum { total, n }, Accumulator::Sum { total: t, n: m }) => {
+                *total += t;
+                *n += m;
+            }
+            (
+                Accumulator::Extreme {
+                    max,
+                    num,
+                    text,
+                    numeric,
+                },
+                Accumulator::Extreme {
+                    num: n,
+                    text: t,
+                    numeric: m,
+                    ..
+                },
+            ) => {
+                if let Some(n) = n.filter(|&n| num.is_none_or(|v| better(n, v, *max))) {
+                    *num = Some(n)
+                }
+                if let Some(t) =
+                    t.filter(|t| text.as_deref().is_none_or(|v| better(t.as_str(), v, *max)))
+                {
+                    *text = Some(t)
+                }
+                *numeric &= m;
+            }
+            (Accumulator::Values(a), Accumulator::Values(b)) => a.extend(b),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn value(self, func: AggFunc) -> String {
+        match (func, self) {
+            (_, Accumulator::Count(n)) => n.to_string(),
+            (_, Accumulator::Distinct(s)) => s.len().to_string(),
+            // empty without numeric values, like mean, min, max and median
+            (_, Accumulator::Sum { n: 0, .. }) => String::new(),
+            (AggFunc::Mean, Accumulator::Sum { total, n }) => fmt_f64(total / n as f64),
+            (_, Accumulator::Sum { total, .. }) => fmt_f64(total),
+            (_, Accumulator::Values(mut v)) => {
+                if v.is_empty() {
+                    return String::new();
+                }
+                v.sort_by(f64::total_cmp);
+                let m = v.len() / 2;
+                match v.len() % 2 {
+                    0 => fmt_f64((v[m - 1] + v[m]) / 2.0),
+                    _ => fmt_f64(v[m]),
+                }
+            }
+            (
+                _,
+                Accumulator::Extreme {
+                    num, text, numeric, ..
+                },
+            ) => match numeric {
+                true => num.map(fmt_f64).unwrap_or_default(),
+                false => text.unwrap_or_default(),
+            },
+        }
+    }
+}
+
+fn better<T: PartialOrd>(v: T, current: T, max: bool) -> bool {
+    match max {
+        true => v > current,
+        false => v < current,
+    }
+}
+
+// split aggregations by comma, except for commas within parentheses
+fn split_aggregations(raw: &str) -> Vec<&str> {
+    let mut out = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+
+    for (i, c) in raw.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                out.push(raw[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(raw[start..].trim());
+
+    out.into_iter().filter(|i| !i.is_empty()).collect()
+}
@@ -0,0 +1,499 @@
+use super::{
+    column::{ColumnResolver, Columns},
+    compress,
+    encoding::TextEncoding,
+    excel::datatype_vec_to_string_vec,
+    external_sort::ExternalSorter,
+    file::is_sheet,
+    reader::{ExcelReader, RecordReader},
+    row_split::CsvRowSplitter,
+    sort::SortColumns,
+    writer::Writer,
+};
+use crate::utils::error::{RsvError, RsvResult};
+use ahash::HashMap;
+use rayon::prelude::*;
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{self, BufReader, Write},
+    iter::Peekable,
+    mem,
+    path::Path,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
+};
+
+// separator of fields in a hash key, a control char that rarely appears in data
+const KEY_SEP: char = '\u{1f}';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Semi,
+    Anti,
+}
+
+impl FromStr for JoinType {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inner" => Ok(JoinType::Inner),
+            "left" => Ok(JoinType::Left),
+            "right" => Ok(JoinType::Right),
+            "full" | "outer" => Ok(JoinType::Full),
+            "semi" => Ok(JoinType::Semi),
+            "anti" => Ok(JoinType::Anti),
+            _ => Err(RsvError::InvalidArgument(format!(
+                "unknown join type <{s}>, can be one of inner, left, right, full, semi and anti."
+            ))),
+        }
+    }
+}
+
+impl JoinType {
+    fn keeps_unmatched_left(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Full | JoinType::Anti)
+    }
+
+    fn keeps_unmatched_right(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Full)
+    }
+
+    // semi and anti joins output columns of the left file only
+    fn is_filter(&self) -> bool {
+        matches!(self, JoinType::Semi | JoinType::Anti)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinStrategy {
+    Auto,
+    Hash,
+    Merge,
+}
+
+impl FromStr for JoinStrategy {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(JoinStrategy::Auto),
+            "hash" => Ok(JoinStrategy::Hash),
+            "merge" => Ok(JoinStrategy::Merge),
+            _ => Err(RsvError::InvalidArgument(format!(
+                "unknown join strategy <{s}>, can be one of auto, hash and merge."
+            ))),
+        }
+    }
+}
+
+/// A CSV, TXT, EXCEL, Parquet or JSON file to join.
+pub struct JoinFile<'a> {
+    pub path: &'a Path,
+    pub sep: char,
+    pub quote: char,
+    pub enc: TextEncoding,
+    pub sheet: usize,
+    pub no_header: bool,
+}
+
+impl JoinFile<'_> {
+    pub fn is_excel(&self) -> bool {
+        is_sheet(self.path)
+    }
+
+    /// First row of the file, used to resolve key columns by name.
+    pub fn header(&self) -> RsvResult<Vec<String>> {
+        if self.is_excel() {
+            return Ok(ExcelReader::new(self.path, self.sheet)?.header_names());
+        }
+
+        let mut rdr = RecordReader::new(
+            BufReader::new(compress::open(self.path, self.enc)?),
+            self.sep,
+            self.quote,
+        );
+        match rdr.next() {
+            Some(r) => Ok(CsvRowSplitter::new(&r?, self.sep, self.quote).collect_owned()),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Whether the hash strategy should be used for the file as the right side.
+    pub fn fits_in(&self, budget: usize) -> bool {
+        self.is_excel()
+            || self
+                .path
+                .metadata()
+                .map_or(true, |m| m.len() as usize <= budget)
+    }
+
+    /// All records of the file, excluding the header.
+    pub fn rows(&self) -> RsvResult<Vec<Vec<String>>> {
+        let skip = 1 - self.no_header as usize;
+
+        if self.is_excel() {
+            let rdr = ExcelReader::new(self.path, self.sheet)?;
+            let rows = rdr
+                .iter()
+                .skip(skip)
+                .map(datatype_vec_to_string_vec)
+                .collect();
+            return Ok(rows);
+        }
+
+        let rdr = RecordReader::new(
+            BufReader::new(compress::open(self.path, self.enc)?),
+            self.sep,
+            self.quote,
+        );
+        let mut rows = vec![];
+        for r in rdr.skip(skip) {
+            rows.push(CsvRowSplitter::new(&r?, self.sep, self.quote).collect_owned());
+        }
+
+        Ok(rows)
+    }
+
+    /// Records of the file sorted by key columns, excluding the header. CSV files
+    /// are sorted within the memory budget into the temporary file <out>.
+    pub fn sorted_rows(
+        &self,
+        cols: &SortColumns,
+        budget: usize,
+        out: &Path,
+    ) -> RsvResult<Box<dyn Iterator<Item = Vec<String>>>> {
+        if self.is_excel() {
+            let mut rows = self.rows()?;
+            rows.par_sort_by(|a, b| compare_rows(cols, a, cols, b));
+            return Ok(Box::new(rows.into_iter()));
+        }
+
+        let mut rdr = RecordReader::new(
+            BufReader::new(compress::open(self.path, self.enc)?),
+            self.sep,
+            self.quote,
+        );
+        if !self.no_header {
+            rdr.next();
+        }
+        let rows = sort_records_to_file(rdr, cols, self.sep, self.quote, budget, out)?;
+
+        Ok(Box::new(rows))
+    }
+}
+
+/// Sort records within the memory budget into the file <out>, and read the sorted
+/// records back.
+pub fn sort_records_to_file<I>(
+    records: I,
+    cols: &SortColumns,
+    sep: char,
+    quote: char,
+    budget: usize,
+    out: &Path,
+) -> RsvResult<impl Iterator<Item = Vec<String>> + use<I>>
+where
+    I: Iterator<Item = Result<String, io::Error>>,
+{
+    let mut sorter = ExternalSorter::new(cols, sep, quote, budget);
+    let mut lines = Vec::with_capacity(10_000);
+    for r in records {
+        lines.push(r?);
+        if lines.len() >= 10_000 {
+            sorter.push(mem::take(&mut lines))?;
+        }
+    }
+    sorter.push(lines)?;
+
+    let mut wtr = Writer::new(out)?;
+    sorter.finish(&mut wtr)?;
+    wtr.0.flush()?;
+
+    let rdr = RecordReader::new(BufReader::new(File::open(out)?), sep, quote);
+    let rows = rdr
+        .map_while(Result::ok)
+        .map(move |r| CsvRowSplitter::new(&r, sep, quote).collect_owned());
+
+    Ok(rows)
+}
+
+fn compare_rows(
+    a_cols: &SortColumns,
+    a: &[String],
+    b_cols: &SortColumns,
+    b: &[String],
+) -> Ordering {
+    a_cols.compare(&a_cols.keys(a), &b_cols.keys(b))
+}
+
+// Output columns are all columns of the left file followed by non-key columns
+// of the right file. For rows only in the right file, key columns of the left
+// file are filled with keys of the right file.
+pub struct Joiner {
+    pub how: JoinType,
+    left_keys: Vec<usize>,
+    right_keys: Vec<usize>,
+    left_n: usize,
+    right_n: usize,
+    left_sort: SortColumns,
+    right_sort: SortColumns,
+}
+
+impl Joiner {
+    pub fn new(
+        how: JoinType,
+        left_keys: &str,
+        right_keys: &str,
+        left_names: Vec<String>,
+        right_names: Vec<String>,
+    ) -> RsvResult<Self> {
+        let (left_n, right_n) = (left_names.len(), right_names.len());
+        let left_keys = Columns::new(left_keys).header(left_names).parse()?.cols;
+        let right_keys = Columns::new(right_keys).header(right_names).parse()?.cols;
+
+        if left_keys.is_empty() {
+            return Err(RsvError::InvalidArgument(
+                "no key column is specified.".to_owned(),
+            ));
+        }
+        if left_keys.len() != right_keys.len() {
+            return Err(RsvError::InvalidArgument(
+                "left and right files have different numbers of key columns.".to_owned(),
+            ));
+        }
+
+        Ok(Joiner {
+            how,
+            left_sort: key_sort_columns(&left_keys)?,
+            right_sort: key_sort_columns(&right_keys)?,
+            left_keys,
+            right_keys,
+            left_n,
+            right_n,
+        })
+    }
+
+    pub fn left_sort(&self) -> &SortColumns {
+        &self.left_sort
+    }
+
+    pub fn right_sort(&self) -> &SortColumns {
+        &self.right_sort
+    }
+
+    pub fn header<T: AsRef<str>>(&self, left: &[T], right: &[T]) -> Vec<String> {
+        self.joined(Some(left), Some(right))
+    }
+
+    fn joined<T: AsRef<str>, U: AsRef<str>>(
+        &self,
+        left: Option<&[T]>,
+        right: Option<&[U]>,
+    ) -> Vec<String> {
+        let mut row = match left {
+            Some(l) => (0..self.left_n.max(l.len()))
+                .map(|i| l.get(i).map_or("", |f| f.as_ref()).to_owned())
+                .collect::<Vec<_>>(),
+            None => {
+                let mut l = vec![String::new(); self.left_n];
+                if let Some(r) = right {
+                    for (&i, &j) in self.left_keys.iter().zip(&self.right_keys) {
+                        l[i] = r.get(j).map_or("", |f| f.as_ref()).to_owned();
+                    }
+                }
+                l
+            }
+        };
+
+        if !self.how.is_filter() {
+            let values = (0..self.right_n)
+                .filter(|i| !self.right_keys.contains(i))
+                .map(|i| {
+                    right
+                        .and_then(|r| r.get(i))
+                        .map_or("", |f| f.as_ref())
+                        .to_owned()
+                });
+            row.extend(values);
+        }
+
+        row
+    }
+
+    // None for a record with an empty key, which matches no record, like nulls in SQL
+    fn key<T: AsRef<str>>(row: &[T], cols: &[usize]) -> Option<String> {
+        let mut key = String::new();
+        for (n, &i) in cols.iter().enumerate() {
+            if n > 0 {
+                key.push(KEY_SEP);
+            }
+            let f = row.get(i).map_or("", |f| f.as_ref());
+            if f.trim().is_empty() {
+                return None;
+            }
+            key.push_str(f);
+        }
+        Some(key)
+    }
+
+    fn has_empty_key(row: &[String], cols: &[usize]) -> bool {
+        cols.iter()
+            .any(|&i| row.get(i).is_none_or(|f| f.trim().is_empty()))
+    }
+
+    /// Sort-merge join of records sorted by key columns, i.e., the output of
+    /// [`JoinFile::sorted_rows`].
+    pub fn merge_join<L, R, F>(&self, left: L, right: R, mut emit: F)
+    where
+        L: Iterator<Item = Vec<String>>,
+        R: Iterator<Item = Vec<String>>,
+        F: FnMut(Vec<String>),
+    {
+        let mut left = left.peekable();
+        let mut right = right.peekable();
+
+        loop {
+            let ordering = match (left.peek(), right.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                // a record with an empty key is unmatched
+                (Some(l), _) if Joiner::has_empty_key(l, &self.left_keys) => Ordering::Less,
+                (_, Some(r)) if Joiner::has_empty_key(r, &self.right_keys) => Ordering::Greater,
+                (Some(l), Some(r)) => self.compare(l, r),
+            };
+
+            match ordering {
+                Ordering::Less => {
+                    let l = left.next().unwrap();
+                    if self.how.keeps_unmatched_left() {
+                        emit(self.joined::<_, String>(Some(&l), None));
+                    }
+                }
+                Ordering::Greater => {
+                    let r = right.next().unwrap();
+                    if self.how.keeps_unmatched_right() {
+                        emit(self.joined::<String, _>(None, Some(&r)));
+                    }
+                }
+                Ordering::Equal => {
+                    let group = self.right_group(&mut right);
+                    while let Some(l) = left.next_if(|l| self.compare(l, &group[0]).is_eq()) {
+                        match self.how {
+                            JoinType::Semi => emit(l),
+                            JoinType::Anti => {}
+                            _ => group
+                                .iter()
+                                .for_each(|r| emit(self.joined(Some(&l), Some(r)))),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // consecutive records of the right file with the same keys
+    fn right_group<R>(&self, right: &mut Peekable<R>) -> Vec<Vec<String>>
+    where
+        R: Iterator<Item = Vec<String>>,
+    {
+        let first = right.next().unwrap();
+        let mut group = vec![];
+        while let Some(r) =
+            right.next_if(|r| compare_rows(&self.right_sort, &first, &self.right_sort, r).is_eq())
+        {
+            group.push(r);
+        }
+        group.insert(0, first);
+
+        group
+    }
+
+    fn compare(&self, left: &[String], right: &[String]) -> Ordering {
+        compare_rows(&self.left_sort, left, &self.right_sort, right)
+    }
+}
+
+// Key columns are sorted as strings, the same order for both files.
+fn key_sort_columns(keys: &[usize]) -> RsvResult<SortColumns> {
+    let spec = keys
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    SortColumns::from(&spec, &mut ColumnResolver::default())
+}
+
+/// Hash table of the right file for the hash join.
+pub struct HashJoinTable<'a> {
+    joiner: &'a Joiner,
+    rows: Vec<Vec<String>>,
+    index: HashMap<String, Vec<usize>>,
+    matched: Vec<AtomicBool>,
+}
+
+impl<'a> HashJoinTable<'a> {
+    pub fn new(joiner: &'a Joiner, rows: Vec<Vec<String>>) -> Self {
+        let mut index = HashMap::<String, Vec<usize>>::default();
+        for (i, r) in rows.iter().enumerate() {
+            if let Some(key) = Joiner::key(r, &joiner.right_keys) {
+                index.entry(key).or_default().push(i);
+            }
+        }
+        let matched = rows.iter().map(|_| AtomicBool::new(false)).collect();
+
+        HashJoinTable {
+            joiner,
+            rows,
+            index,
+            matched,
+        }
+    }
+
+    /// Joined records of a record in the left file.
+    pub fn probe<T: AsRef<str>>(&self, left: &[T]) -> Vec<Vec<String>> {
+        let how = self.joiner.how;
+        let key = Joiner::key(left, &self.joiner.left_keys);
+        let Some(ids) = key.and_then(|k| self.index.get(&k)) else {
+            return match how.keeps_unmatched_left() {
+                true => vec![self.joiner.joined::<_, String>(Some(left), None)],
+                false => vec![],
+            };
+        };
+
+        if how.keeps_unmatched_right() {
+            ids.iter()
+                .for_each(|&i| self.matched[i].store(true, AtomicOrdering::Relaxed));
+        }
+
+        match how {
+            JoinType::Semi => vec![self.joiner.joined::<_, String>(Some(left), None)],
+            JoinType::Anti => vec![],
+            _ => ids
+                .iter()
+                .map(|&i| self.joiner.joined(Some(left), Some(&self.rows[i])))
+                .collect(),
+        }
+    }
+
+    /// Records of the right file that match no record in the left file, for
+    /// right and full joins.
+    pub fn unmatched(&self) -> Vec<Vec<String>> {
+        if !self.joiner.how.keeps_unmatched_right() {
+            return vec![];
+        }
+
+        self.rows
+            .iter()
+            .zip(&self.matched)
+            .filter(|(_, m)| !m.load(AtomicOrdering::Relaxed))
+            .map(|(r, _)| self.joiner.joined::<String, _>(None, Some(r)))
+            .collect()
+    }
+}
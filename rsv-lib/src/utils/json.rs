@@ -0,0 +1,250 @@
+use super::{
+    cli_result::CliResult,
+    column_type::{ColumnType, ColumnTypes},
+    excel::rows_to_range,
+    util::is_null,
+};
+use crate::utils::error::{RsvError, RsvResult};
+use ahash::HashMap;
+use calamine::{Data, Range};
+use rayon::prelude::*;
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// Read a JSON file, either an array of objects or one object per line (NDJSON),
+/// into a range of cells, so that it can be processed the same way as an EXCEL
+/// sheet. Nested keys are flattened to dotted column names, e.g., user.name, and
+/// columns are ordered by their first appearance. NDJSON is read line by line,
+/// and lines are parsed in parallel by chunks.
+pub fn read_range(path: &Path) -> RsvResult<Range<Data>> {
+    let mut rdr = BufReader::new(File::open(path)?);
+    let skipped_lines = skip_whitespace(&mut rdr)?;
+
+    let records = match rdr.fill_buf()?.first() {
+        Some(b'[') => {
+            let Value::Array(v) = serde_json::from_reader(rdr)? else {
+                return Err(RsvError::Json(
+                    "a JSON file should be an array of objects or one object per line.".to_owned(),
+                ));
+            };
+            v.into_par_iter().map(flatten).collect::<Vec<_>>()
+        }
+        _ => read_lines(rdr, skipped_lines)?,
+    };
+
+    // columns by first appearance
+    let mut names = vec![];
+    let mut index = HashMap::default();
+    for (k, _) in records.iter().flatten() {
+        if !index.contains_key(k) {
+            index.insert(k.to_owned(), names.len());
+            names.push(k.to_owned());
+        }
+    }
+
+    let n = records.len();
+    let width = names.len();
+    let rows = records.into_iter().map(|r| {
+        let mut row = vec![Data::Empty; width];
+        for (k, v) in r {
+            row[index[&k]] = v;
+        }
+        row
+    });
+
+    Ok(rows_to_range(names, rows, n))
+}
+
+// records of NDJSON, where line numbers in errors start after the skipped lines
+fn read_lines(rdr: impl BufRead, skipped_lines: usize) -> RsvResult<Vec<Vec<(String, Data)>>> {
+    let mut records = vec![];
+    let mut lines = rdr.lines().enumerate();
+    loop {
+        let chunk = lines
+            .by_ref()
+            .take(10_000)
+            .map(|(i, l)| l.map(|l| (i + skipped_lines, l)))
+            .collect::<io::Result<Vec<_>>>()?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        let chunk = chunk
+            .into_par_iter()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| match serde_json::from_str(&l) {
+                Ok(v) => Ok(flatten(v)),
+                Err(e) => Err(format!("invalid JSON at line {}: {e}.", i + 1)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        records.extend(chunk);
+    }
+
+    Ok(records)
+}
+
+// skip leading whitespace, and return the number of skipped lines
+fn skip_whitespace(rdr: &mut impl BufRead) -> io::Result<usize> {
+    let mut lines = 0;
+    loop {
+        let buf = rdr.fill_buf()?;
+        let n = buf
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(buf.len());
+        lines += buf[..n].iter().filter(|&&b| b == b'\n').count();
+        let done = n < buf.len() || buf.is_empty();
+        rdr.consume(n);
+        if done {
+            return Ok(lines);
+        }
+    }
+}
+
+// an array is kept as JSON text, and a record that is not an object makes up a
+// single column named value
+fn flatten(v: Value) -> Vec<(String, Data)> {
+    let mut out = vec![];
+    match v {
+        Value::Object(_) => flatten_to(&mut out, "", v),
+        _ => out.push(("value".to_owned(), value_to_data(v))),
+    }
+    out
+}
+
+fn flatten_to(out: &mut Vec<(String, Data)>, prefix: &str, v: Value) {
+    match v {
+        Value::Object(m) => {
+            for (k, v) in m {
+                let key = match prefix {
+                    "" => k,
+                    _ => format!("{prefix}.{k}"),
+                };
+                flatten_to(out, &key, v)
+            }
+        }
+        _ => out.push((prefix.to_owned(), value_to_data(v))),
+    }
+}
+
+fn value_to_data(v: Value) -> Data {
+    match v {
+        Value::Null => Data::Empty,
+        Value::Bool(b) => Data::Bool(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Data::Int(i),
+            None => Data::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::String(s) => Data::String(s),
+        Value::Array(_) | Value::Object(_) => Data::String(v.to_string()),
+    }
+}
+
+/// A JSON writer of text records, writing an array of objects, or one object per
+/// line when <lines> is true. Header names are keys, values of int and float
+/// columns are written as numbers, and empty fields as null. Null values such as
+/// NA are null in numeric and date columns only, and are text in other columns.
+pub struct JsonWriter {
+    wtr: BufWriter<File>,
+    names: Vec<String>,
+    types: Vec<ColumnType>,
+    lines: bool,
+    n: usize,
+}
+
+impl JsonWriter {
+    pub fn new(
+        path: &Path,
+        names: &[String],
+        ctypes: &ColumnTypes,
+        lines: bool,
+    ) -> RsvResult<Self> {
+        let types = ctypes
+            .iter()
+            .map(|t| t.col_type.clone())
+            .collect::<Vec<_>>();
+        let names = (0..types.len())
+            .map(|c| names.get(c).cloned().unwrap_or_else(|| format!("col{c}")))
+            .collect();
+
+        Ok(JsonWriter {
+            wtr: BufWriter::new(File::create(path)?),
+            names,
+            types,
+            lines,
+            n: 0,
+        })
+    }
+
+    /// Missing fields of a short row are written as null.
+    pub fn write_row<T: AsRef<str>>(&mut self, row: &[T]) -> CliResult {
+        let w = &mut self.wtr;
+        match (self.lines, self.n) {
+            (true, _) => {}
+            (false, 0) => w.write_all(b"[\n")?,
+            (false, _) => w.write_all(b",\n")?,
+        }
+
+        w.write_all(b"{")?;
+        for (c, (name, t)) in self.names.iter().zip(&self.types).enumerate() {
+            if c > 0 {
+                w.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut *w, name)?;
+            w.write_all(b":")?;
+
+            let f = row.get(c).map_or("", |f| f.as_ref());
+            write_value(w, f, t)?;
+        }
+        w.write_all(b"}")?;
+
+        if self.lines {
+            w.write_all(b"\n")?;
+        }
+        self.n += 1;
+
+        Ok(())
+    }
+
+    pub fn close(mut self) -> CliResult {
+        match (self.lines, self.n) {
+            (true, _) => {}
+            (false, 0) => self.wtr.write_all(b"[]\n")?,
+            (false, _) => self.wtr.write_all(b"\n]\n")?,
+        }
+        self.wtr.flush()?;
+
+        Ok(())
+    }
+}
+
+// a number that cannot be represented in JSON, e.g., inf, is written as null,
+// and a value that is not a number in a numeric column is written as a string
+fn write_value(w: &mut BufWriter<File>, f: &str, t: &ColumnType) -> CliResult {
+    let v = f.trim();
+    if v.is_empty() || ((t.is_number() || *t == ColumnType::Date) && is_null(v)) {
+        w.write_all(b"null")?;
+        return Ok(());
+    }
+
+    if t.is_number() {
+        if let Ok(i) = v.parse::<i64>() {
+            write!(w, "{i}")?;
+            return Ok(());
+        }
+        if let Ok(i) = v.parse::<f64>() {
+            match i.is_finite() {
+                true => write!(w, "{i}")?,
+                false => w.write_all(b"null")?,
+            }
+            return Ok(());
+        }
+    }
+    serde_json::to_writer(&mut *w, f)?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::utils::util::werr_exit;
+use crate::utils::error::{RsvError, RsvResult};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Token {
@@ -18,7 +18,7 @@ struct Parser {
 }
 
 impl Parser {
-    fn parse(source: &str) -> Self {
+    fn parse(source: &str) -> RsvResult<Self> {
         let mut p = Parser {
             source: source.replace(" ", "").to_owned(),
             tokens: vec![],
@@ -29,20 +29,20 @@ impl Parser {
         };
         match p.source.chars().next() {
             Some(ch) => p.ch = ch,
-            None => return p,
+            None => return Ok(p),
         };
 
-        while let Some(tok) = p.next_tok() {
+        while let Some(tok) = p.next_tok()? {
             p.tokens.push(tok);
         }
 
-        p
+        Ok(p)
     }
 
-    fn next_tok(&mut self) -> Option<Token> {
+    fn next_tok(&mut self) -> RsvResult<Option<Token>> {
         // arrive at the end of expr
         if self.offset >= self.source.len() {
-            return None;
+            return Ok(None);
         }
 
         // ignore white spaces
@@ -52,30 +52,38 @@ impl Parser {
             '(' | ')' | '+' | '-' | '*' | '/' | '^' | '%' => {
                 let tok = Token::Operator(self.ch);
                 self.next_ch();
-                Some(tok)
+                Ok(Some(tok))
             }
             '0'..='9' => {
                 let start = self.offset;
                 while self.is_digit_num(self.ch) && self.next_ch() {}
                 let v = self.source.get(start..self.offset).unwrap();
-                let v: f64 = v.parse().unwrap();
-                Some(Token::Literal(v))
+                let v: f64 = v.parse().map_err(|_| {
+                    RsvError::BadNumber(format!("<{v}> is not a number in expr <{}>.", self.source))
+                })?;
+                Ok(Some(Token::Literal(v)))
             }
             '@' | 'c' => {
                 let start = self.offset;
                 self.next_ch();
                 while self.is_digit_num(self.ch) && self.next_ch() {}
                 let col = self.source.get(start + 1..self.offset).unwrap();
-                let col = match col.parse::<usize>() {
-                    Ok(v) => v,
-                    Err(err) => werr_exit!("{}", err),
-                };
+                let col = col.parse::<usize>().map_err(|_| {
+                    RsvError::ExprSyntax(format!(
+                        "<{}> is not a valid column in expr <{}>.",
+                        &self.source[start..self.offset],
+                        self.source
+                    ))
+                })?;
 
                 self.used_columns.push(col);
                 self.max_column = self.max_column.max(col);
-                Some(Token::Col(col))
+                Ok(Some(Token::Col(col)))
             }
-            _ => werr_exit!("{} is not recognized in Expr.", self.ch),
+            _ => Err(RsvError::ExprSyntax(format!(
+                "<{}> is not recognized in expr <{}>.",
+                self.ch, self.source
+            ))),
         }
     }
 
@@ -198,9 +206,9 @@ impl Node {
                     '-' => l - r,
                     '*' => l * r,
                     '/' => l / r,
-                    '%' => (l / r).floor(),
-                    '^' => l.powi(r as i32),
-                    _ => werr_exit!("Node evaluate error."),
+                    '%' => l % r,
+                    '^' => l.powf(r),
+                    _ => unreachable!("operators are checked in parsing"),
                 }
             }
             Token::Literal(v) => v,
@@ -215,11 +223,11 @@ pub struct AST {
 }
 
 impl AST {
-    pub fn parse(source: &str) -> CompiledExpr {
-        let p = Parser::parse(source);
+    pub fn parse(source: &str) -> RsvResult<CompiledExpr> {
+        let p = Parser::parse(source)?;
 
         if p.tokens.is_empty() {
-            return CompiledExpr::new();
+            return Ok(CompiledExpr::new());
         }
 
         let mut ast = AST {
@@ -228,61 +236,69 @@ impl AST {
             curr_index: 0,
         };
 
-        let node = ast.parse_expression().unwrap();
-        CompiledExpr {
+        let node = ast
+            .parse_expression()?
+            .ok_or_else(|| RsvError::ExprSyntax(format!("expr <{}> is incomplete.", p.source)))?;
+        Ok(CompiledExpr {
             node,
             used_columns: p.used_columns,
             max_column: p.max_column,
-        }
+        })
     }
 
-    fn parse_expression(&mut self) -> Option<Node> {
-        let lhs = self.parse_primary();
-        lhs.as_ref()?;
-        self.parse_bin_op_rhs(0, lhs.unwrap())
+    fn parse_expression(&mut self) -> RsvResult<Option<Node>> {
+        match self.parse_primary()? {
+            Some(lhs) => self.parse_bin_op_rhs(0, lhs),
+            None => Ok(None),
+        }
     }
 
-    fn parse_primary(&mut self) -> Option<Node> {
+    fn parse_primary(&mut self) -> RsvResult<Option<Node>> {
         let expr = match self.curr_tok {
             Some(Token::Col(col)) => Some(Node::from_col(col)),
             Some(Token::Literal(val)) => Some(Node::from_number(val)),
             Some(Token::Operator(op)) => {
                 if op != '(' {
-                    werr_exit!("start operation <{}> is not recognized", op)
+                    return Err(RsvError::ExprSyntax(format!(
+                        "start operation <{op}> is not recognized."
+                    )));
                 }
                 self.next_tok();
-                self.parse_expression()
+                self.parse_expression()?
             }
-            None => return None,
+            None => return Ok(None),
         };
 
         self.next_tok();
-        expr
+        Ok(expr)
     }
 
-    fn parse_bin_op_rhs(&mut self, exec_prec: i32, mut lhs: Node) -> Option<Node> {
+    fn parse_bin_op_rhs(&mut self, exec_prec: i32, mut lhs: Node) -> RsvResult<Option<Node>> {
         loop {
             let tok_prec = self.get_tok_precedence();
             if tok_prec < exec_prec {
-                return Some(lhs);
+                return Ok(Some(lhs));
             }
-            let op = match self.curr_tok {
-                Some(Token::Operator(op)) => op,
-                _ => werr_exit!("AST parse_bin_op_rhs error."),
+            let Some(Token::Operator(op)) = self.curr_tok else {
+                return Err(RsvError::ExprSyntax(
+                    "an operator is expected in expr.".to_owned(),
+                ));
             };
             if self.next_tok().is_none() {
-                return Some(lhs);
+                return Ok(Some(lhs));
             }
-            let mut rhs = self.parse_primary();
-            rhs.as_ref()?;
+            let Some(mut rhs) = self.parse_primary()? else {
+                return Ok(None);
+            };
             let next_prec = self.get_tok_precedence();
             if tok_prec < next_prec {
                 // 递归，将当前优先级+1
-                rhs = self.parse_bin_op_rhs(exec_prec + 1, rhs.unwrap());
-                rhs.as_ref()?;
+                let Some(r) = self.parse_bin_op_rhs(tok_prec + 1, rhs)? else {
+                    return Ok(None);
+                };
+                rhs = r;
             }
 
-            let rhs = rhs.unwrap();
             lhs = Node {
                 token: Token::Operator(op),
                 node_type: match (&lhs.node_type, &rhs.node_type) {
@@ -337,13 +353,13 @@ mod tests {
 
     #[test]
     fn test_expr_parser() {
-        let p = Parser::parse("").tokens;
+        let p = Parser::parse("").unwrap().tokens;
         assert_eq!(p.len(), 0);
 
-        let p = Parser::parse(" ").tokens;
+        let p = Parser::parse(" ").unwrap().tokens;
         assert_eq!(p.len(), 0);
 
-        let p = Parser::parse("  @1  + ( 1.05 + @2 ^ 2 )").tokens;
+        let p = Parser::parse("  @1  + ( 1.05 + @2 ^ 2 )").unwrap().tokens;
         println!("{:?}", p);
         assert_eq!(p[0], Token::Col(1));
         assert_eq!(p[1], Token::Operator('+'));
@@ -358,22 +374,31 @@ mod tests {
 
     #[test]
     fn test_ast() {
-        let p = AST::parse("4+2");
+        let p = AST::parse("4+2").unwrap();
         // dbg!(&p);
         assert_eq!(p.evaluate(None), 6.0);
 
-        let p = AST::parse("  @0  + (1.05 + @1 ^ 2 * 2)");
+        let p = AST::parse("  @0  + (1.05 + @1 ^ 2 * 2)").unwrap();
         // dbg!(&p);
         assert_eq!(p.evaluate(Some(&[1.0, 1.0])), 4.05);
         assert_eq!(p.evaluate(Some(&[2.0, 2.0])), 11.05);
         assert_eq!(p.evaluate(Some(&[1.0, 3.0])), 20.05);
         assert_eq!(p.evaluate(Some(&[2.0, 4.0])), 35.05);
 
-        let p = AST::parse("  @0 + (1.05 + (@1 ^ 2) * 2)");
+        let p = AST::parse("  @0 + (1.05 + (@1 ^ 2) * 2)").unwrap();
         // dbg!(&p);
         assert_eq!(p.evaluate(Some(&[1.0, 1.0])), 4.05);
         assert_eq!(p.evaluate(Some(&[2.0, 2.0])), 11.05);
         assert_eq!(p.evaluate(Some(&[1.0, 3.0])), 20.05);
         assert_eq!(p.evaluate(Some(&[2.0, 4.0])), 35.05);
+
+        let p = AST::parse("2-3*4+1").unwrap();
+        assert_eq!(p.evaluate(None), -9.0);
+
+        let p = AST::parse("10-@0*2^2+7%4").unwrap();
+        assert_eq!(p.evaluate(Some(&[2.0])), 5.0);
+
+        assert!(matches!(AST::parse("@0 + #"), Err(RsvError::ExprSyntax(_))));
+        assert!(matches!(AST::parse("("), Err(RsvError::ExprSyntax(_))));
     }
 }
@@ -0,0 +1,272 @@
+use super::column::{ColumnResolver, split_column_spec};
+use crate::utils::error::{RsvError, RsvResult};
+use ahash::{HashMap, HashSet};
+
+fn resolve_columns(spec: &str, resolver: &mut ColumnResolver) -> RsvResult<Vec<usize>> {
+    let mut cols = vec![];
+    for i in split_column_spec(spec) {
+        cols.extend(resolver.resolve(i)?);
+    }
+    Ok(cols)
+}
+
+/// Reshape a wide row into long rows, one for each value column, made of the
+/// id fields, the name of the value column and its value.
+pub struct Melter {
+    ids: Vec<usize>,
+    values: Vec<usize>,
+    names: Vec<String>,
+    max_col: usize,
+}
+
+impl Melter {
+    // Id and value columns follow the column syntax, e.g., 0,1 and 2-40. Value
+    // columns default to all columns that are not ids.
+    pub fn new(ids: &str, values: &str, names: Vec<String>) -> RsvResult<Self> {
+        let mut resolver = ColumnResolver::from_names(names.clone());
+        let ids = resolve_columns(ids, &mut resolver)?;
+        let values = match values.trim().is_empty() {
+            true => (0..names.len()).filter(|i| !ids.contains(i)).collect(),
+            false => resolve_columns(values, &mut resolver)?,
+        };
+        if values.is_empty() {
+            return Err(RsvError::InvalidArgument(
+                "no value column to melt.".to_owned(),
+            ));
+        }
+
+        let max_col = ids.iter().chain(&values).max().copied().unwrap_or_default();
+
+        Ok(Melter {
+            ids,
+            values,
+            names,
+            max_col,
+        })
+    }
+
+    pub fn header(&self, var_name: &str, value_name: &str) -> Vec<String> {
+        self.ids
+            .iter()
+            .map(|&i| self.names[i].clone())
+            .chain([var_name.to_owned(), value_name.to_owned()])
+            .collect()
+    }
+
+    /// Long rows of a wide row, or None for a bad row with fewer columns than
+    /// the ids and values.
+    pub fn melt<T: AsRef<str>>(&self, v: &[T]) -> Option<Vec<Vec<String>>> {
+        if self.max_col >= v.len() {
+            return None;
+        }
+
+        let ids = self
+            .ids
+            .iter()
+            .map(|&i| v[i].as_ref().to_owned())
+            .collect::<Vec<_>>();
+
+        let rows = self
+            .values
+            .iter()
+            .map(|&i| {
+                let mut r = ids.clone();
+                r.push(self.names[i].clone());
+                r.push(v[i].as_ref().to_owned());
+                r
+            })
+            .collect();
+
+        Some(rows)
+    }
+}
+
+/// Reshape long rows into wide rows, with one column for each distinct
+/// variable, in order of first appearance. Rows of an id are expected to be
+/// adjacent, e.g., rows output by melt or sorted by ids, and a variable that
+/// is repeated within an id keeps the last value.
+pub struct Unmelter {
+    ids: Vec<usize>,
+    key: usize,
+    value: usize,
+    max_col: usize,
+    variables: HashMap<String, usize>,
+    order: Vec<String>,
+    current: Option<(Vec<String>, Vec<String>)>,
+    bad_rows: usize,
+}
+
+impl Unmelter {
+    // The key column holds variable names and the value column their values.
+    // Id columns default to all columns other than the key and the value.
+    pub fn new(
+        ids: &str,
+        key: &str,
+        value: &str,
+        resolver: &mut ColumnResolver,
+    ) -> RsvResult<Self> {
+        let key = resolver.resolve_one(key)?;
+        let value = resolver.resolve_one(value)?;
+        if key == value {
+            return Err(RsvError::InvalidArgument(
+                "the key and value columns should be different.".to_owned(),
+            ));
+        }
+
+        let ids = match ids.trim().is_empty() {
+            true => (0..resolver.total()?)
+                .filter(|&i| i != key && i != value)
+                .collect(),
+            false => resolve_columns(ids, resolver)?,
+        };
+        if ids.contains(&key) || ids.contains(&value) {
+            return Err(RsvError::InvalidArgument(
+                "id columns should not include the key or value column.".to_owned(),
+            ));
+        }
+
+        let max_col = ids
+            .iter()
+            .chain([key, value].iter())
+            .max()
+            .copied()
+            .unwrap_or_default();
+
+        Ok(Unmelter {
+            ids,
+            key,
+            value,
+            max_col,
+            variables: HashMap::default(),
+            order: vec![],
+            current: None,
+            bad_rows: 0,
+        })
+    }
+
+    /// Variables seen in a row, for the first pass that collects all variables.
+    pub fn add_variable_by_fields<T: AsRef<str>>(&mut self, v: &[T]) {
+        if let Some(f) = v.get(self.key) {
+            self.add_variable(f.as_ref())
+        }
+    }
+
+    /// Add distinct variables, e.g., collected from a chunk, in order.
+    pub fn add_variable(&mut self, f: &str) {
+        if !self.variables.contains_key(f) {
+            self.variables.insert(f.to_owned(), self.order.len());
+            self.order.push(f.to_owned());
+        }
+    }
+
+    /// Distinct variables of rows in order of first appearance.
+    pub fn distinct_variables<T: AsRef<str>>(&self, lines: &[Vec<T>]) -> Vec<String> {
+        let mut seen = HashSet::default();
+        lines
+            .iter()
+            .filter_map(|v| v.get(self.key).map(|f| f.as_ref()))
+            .filter(|&f| seen.insert(f))
+            .map(|f| f.to_owned())
+            .collect()
+    }
+
+    pub fn header(&self, names: &[String]) -> Vec<String> {
+        let name = |i: usize| names.get(i).cloned().unwrap_or_else(|| format!("col{i}"));
+
+        self.ids
+            .iter()
+            .map(|&i| name(i))
+            .chain(self.order.iter().cloned())
+            .collect()
+    }
+
+    /// Add a long row, and return the previous wide row when the id changes.
+    pub fn push<T: AsRef<str>>(&mut self, v: &[T]) -> Option<Vec<String>> {
+        if self.max_col >= v.len() {
+            self.bad_rows += 1;
+            return None;
+        }
+
+        let same_id = self
+            .current
+            .as_ref()
+            .is_some_and(|(id, _)| self.ids.iter().zip(id).all(|(&i, f)| v[i].as_ref() == f));
+        let done = match same_id {
+            true => None,
+            false => {
+                let id = self.ids.iter().map(|&i| v[i].as_ref().to_owned()).collect();
+                let values = vec![String::new(); self.order.len()];
+                self.current.replace((id, values)).map(wide_row)
+            }
+        };
+
+        if let (Some((_, values)), Some(&i)) = (
+            self.current.as_mut(),
+            self.variables.get(v[self.key].as_ref()),
+        ) {
+            values[i] = v[self.value].as_ref().to_owned();
+        }
+
+        done
+    }
+
+    /// The last wide row.
+    pub fn finish(&mut self) -> Option<Vec<String>> {
+        self.current.take().map(wide_row)
+    }
+
+    /// Number of rows ignored for having fewer columns than the ids, the key
+    /// and the value.
+    pub fn bad_rows(&self) -> usize {
+        self.bad_rows
+    }
+}
+
+fn wide_row((mut id, values): (Vec<String>, Vec<String>)) -> Vec<String> {
+    id.extend(values);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_melt_and_unmelt() {
+        let names = ["id", "q1", "q2"].map(String::from).to_vec();
+        let melter = Melter::new("0", "", names.clone()).unwrap();
+        assert_eq!(
+            melter.header("variable", "value"),
+            ["id", "variable", "value"]
+        );
+
+        let long = [["1", "a", "b"], ["2", "c", ""]]
+            .iter()
+            .flat_map(|r| melter.melt(r).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            long,
+            [
+                ["1", "q1", "a"],
+                ["1", "q2", "b"],
+                ["2", "q1", "c"],
+                ["2", "q2", ""]
+            ]
+        );
+
+        let mut resolver = ColumnResolver::from_total(3);
+        let mut unmelter = Unmelter::new("", "-2", "-1", &mut resolver).unwrap();
+        unmelter
+            .distinct_variables(&long)
+            .iter()
+            .for_each(|i| unmelter.add_variable(i));
+        assert_eq!(unmelter.header(&names), names);
+
+        let mut wide = long
+            .iter()
+            .filter_map(|r| unmelter.push(r))
+            .collect::<Vec<_>>();
+        wide.extend(unmelter.finish());
+        assert_eq!(wide, [["1", "a", "b"], ["2", "c", ""]]);
+    }
+}
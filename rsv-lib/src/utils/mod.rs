@@ -1,23 +1,42 @@
+pub mod cat;
 pub mod chunk;
 pub mod cli_result;
 pub mod column;
 pub mod column_stats;
 pub mod column_type;
+pub mod column_type_infer;
+pub mod compress;
 pub mod constants;
 pub mod date_format_infer;
+pub mod derive;
+pub mod encoding;
+pub mod error;
 pub mod excel;
+pub mod external_sort;
 pub mod file;
 pub mod filename;
 pub mod filter;
+pub mod groupby;
+pub mod join;
+pub mod json;
 pub mod math_expr_parser;
+pub mod melt;
+pub mod parquet;
+pub mod pivot;
 pub mod priority_queue;
 pub mod progress;
 pub mod reader;
 pub mod regex;
 pub mod return_result;
 pub mod row_split;
+pub mod schema;
+pub mod sketch;
 pub mod sort;
+pub mod source;
 pub mod table;
 pub mod to;
+pub mod transpose;
+pub mod unique;
 pub mod util;
+pub mod validate;
 pub mod writer;
@@ -0,0 +1,315 @@
+use super::{
+    cli_result::CliResult,
+    column_type::{ColumnType, ColumnTypes},
+    date_format_infer::DateSmartParser,
+    excel::rows_to_range,
+    to::date_format_of,
+    util::{fmt_datetime, is_null},
+};
+use crate::utils::error::{RsvError, RsvResult};
+use calamine::{Data, Range};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeDelta};
+use parquet::{
+    basic::{Compression, LogicalType, Repetition, Type as PhysicalType},
+    data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type},
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        reader::{FileReader, SerializedFileReader},
+        writer::SerializedFileWriter,
+    },
+    format::{MilliSeconds, TimeUnit},
+    record::{Field, reader::RowIter},
+    schema::types::Type,
+};
+use rayon::prelude::*;
+use std::{fs::File, path::Path, sync::Arc};
+
+const ROW_GROUP_SIZE: usize = 100_000;
+
+/// Read a Parquet file into a range of cells, with column names as the first
+/// row, so that it can be processed the same way as an EXCEL sheet. Row groups
+/// are decoded in parallel. It is for commands that need all rows at once, while
+/// others read rows lazily with ParquetRows.
+pub fn read_range(path: &Path) -> RsvResult<Range<Data>> {
+    let rdr = SerializedFileReader::new(File::open(path)?)?;
+    let names = column_names(&rdr);
+    let groups = (0..rdr.num_row_groups())
+        .into_par_iter()
+        .map(|i| read_row_group(path, i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let n = groups.iter().map(|g| g.len()).sum::<usize>();
+
+    Ok(rows_to_range(names, groups.into_iter().flatten(), n))
+}
+
+fn column_names(rdr: &SerializedFileReader<File>) -> Vec<String> {
+    rdr.metadata()
+        .file_metadata()
+        .schema()
+        .get_fields()
+        .iter()
+        .map(|f| f.name().to_owned())
+        .collect()
+}
+
+/// Rows of cells of a Parquet file, with column names as the first row. Row
+/// groups are read one at a time as rows are consumed, so that a large file is
+/// not loaded into memory.
+pub struct ParquetRows {
+    names: Option<Vec<String>>,
+    rows: RowIter<'static>,
+}
+
+impl ParquetRows {
+    pub fn open(path: &Path) -> RsvResult<Self> {
+        let rdr = SerializedFileReader::new(File::open(path)?)?;
+        let names = column_names(&rdr);
+
+        Ok(ParquetRows {
+            names: Some(names),
+            rows: rdr.into_iter(),
+        })
+    }
+}
+
+impl Iterator for ParquetRows {
+    type Item = Result<Vec<Data>, ParquetError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(names) = self.names.take() {
+            return Some(Ok(names.into_iter().map(Data::String).collect()));
+        }
+
+        let r = self.rows.next()?;
+        Some(r.map(|r| r.get_column_iter().map(|(_, f)| field_to_data(f)).collect()))
+    }
+}
+
+fn read_row_group(path: &Path, i: usize) -> Result<Vec<Vec<Data>>, ParquetError> {
+    let rdr = SerializedFileReader::new(File::open(path)?)?;
+    let group = rdr.get_row_group(i)?;
+
+    group
+        .get_row_iter(None)?
+        .map(|r| r.map(|r| r.get_column_iter().map(|(_, f)| field_to_data(f)).collect()))
+        .collect()
+}
+
+fn field_to_data(f: &Field) -> Data {
+    match f {
+        Field::Null => Data::Empty,
+        Field::Bool(v) => Data::Bool(*v),
+        Field::Byte(v) => Data::Int(*v as i64),
+        Field::Short(v) => Data::Int(*v as i64),
+        Field::Int(v) => Data::Int(*v as i64),
+        Field::Long(v) => Data::Int(*v),
+        Field::UByte(v) => Data::Int(*v as i64),
+        Field::UShort(v) => Data::Int(*v as i64),
+        Field::UInt(v) => Data::Int(*v as i64),
+        Field::ULong(v) => i64::try_from(*v).map_or(Data::Float(*v as f64), Data::Int),
+        Field::Float16(v) => Data::Float(v.to_f64()),
+        // via its shortest representation, e.g., 41.2 rather than 41.20000076293945
+        Field::Float(v) => Data::Float(v.to_string().parse().unwrap_or(*v as f64)),
+        Field::Double(v) => Data::Float(*v),
+        Field::Str(v) => Data::String(v.to_owned()),
+        Field::Bytes(v) => match v.as_utf8() {
+            Ok(s) => Data::String(s.to_owned()),
+            Err(_) => Data::String(f.to_string()),
+        },
+        Field::Date(v) => datetime_to_data(
+            DateTime::UNIX_EPOCH
+                .date_naive()
+                .checked_add_signed(TimeDelta::days(*v as i64))
+                .map(|d| d.and_time(NaiveTime::MIN)),
+        ),
+        Field::TimestampMillis(v) => {
+            datetime_to_data(DateTime::from_timestamp_millis(*v).map(|d| d.naive_utc()))
+        }
+        Field::TimestampMicros(v) => {
+            datetime_to_data(DateTime::from_timestamp_micros(*v).map(|d| d.naive_utc()))
+        }
+        _ => Data::String(f.to_string()),
+    }
+}
+
+fn datetime_to_data(d: Option<NaiveDateTime>) -> Data {
+    match d {
+        Some(d) => Data::DateTimeIso(fmt_datetime(&d)),
+        None => Data::Empty,
+    }
+}
+
+/// A Parquet writer of text records. Physical types are picked from column types,
+/// i.e., INT64 for int, DOUBLE for float, TIMESTAMP(MILLIS) for date, and UTF8
+/// string for others. Null values of typed columns are written as nulls.
+pub struct ParquetWriter {
+    wtr: SerializedFileWriter<File>,
+    columns: Vec<ColumnBuffer>,
+    parser: DateSmartParser,
+    rows: usize,
+}
+
+struct ColumnBuffer {
+    name: String,
+    col_type: ColumnType,
+    date_format: Option<String>,
+    values: Values,
+    levels: Vec<i16>,
+}
+
+enum Values {
+    Int(Vec<i64>),
+    Float(Vec<f64>),
+    Text(Vec<ByteArray>),
+}
+
+impl ParquetWriter {
+    pub fn new(
+        path: &Path,
+        names: &[String],
+        ctypes: &ColumnTypes,
+        date_columns: &[usize],
+        date_formats: &[String],
+    ) -> RsvResult<Self> {
+        let mut fields = vec![];
+        let mut columns = vec![];
+        for (c, t) in ctypes.iter().enumerate() {
+            let name = names.get(c).cloned().unwrap_or_else(|| format!("col{c}"));
+            let (physical, logical, values) = match t.col_type {
+                ColumnType::Int => (PhysicalType::INT64, None, Values::Int(vec![])),
+                ColumnType::Float => (PhysicalType::DOUBLE, None, Values::Float(vec![])),
+                ColumnType::Date => (
+                    PhysicalType::INT64,
+                    Some(LogicalType::Timestamp {
+                        is_adjusted_to_u_t_c: false,
+                        unit: TimeUnit::MILLIS(MilliSeconds {}),
+                    }),
+                    Values::Int(vec![]),
+                ),
+                _ => (
+                    PhysicalType::BYTE_ARRAY,
+                    Some(LogicalType::String),
+                    Values::Text(vec![]),
+                ),
+            };
+
+            let field = Type::primitive_type_builder(&name, physical)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_logical_type(logical)
+                .build()?;
+            fields.push(Arc::new(field));
+            columns.push(ColumnBuffer {
+                name,
+                col_type: t.col_type.clone(),
+                date_format: date_format_of(c, date_columns, date_formats).cloned(),
+                values,
+                levels: vec![],
+            });
+        }
+
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let wtr =
+            SerializedFileWriter::new(File::create(path)?, Arc::new(schema), Arc::new(props))?;
+
+        Ok(ParquetWriter {
+            wtr,
+            columns,
+            parser: DateSmartParser::new(),
+            rows: 0,
+        })
+    }
+
+    /// Missing fields of a short row are taken as empty.
+    pub fn write_row<T: AsRef<str>>(&mut self, row: &[T]) -> CliResult {
+        for (c, col) in self.columns.iter_mut().enumerate() {
+            let f = row.get(c).map_or("", |f| f.as_ref());
+            col.push(f, &self.parser)?;
+        }
+
+        self.rows += 1;
+        if self.rows == ROW_GROUP_SIZE {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> CliResult {
+        if self.rows == 0 {
+            return Ok(());
+        }
+
+        let mut group = self.wtr.next_row_group()?;
+        for col in &mut self.columns {
+            let Some(mut w) = group.next_column()? else {
+                break;
+            };
+            let levels = Some(col.levels.as_slice());
+            match &mut col.values {
+                Values::Int(v) => {
+                    w.typed::<Int64Type>().write_batch(v, levels, None)?;
+                    v.clear();
+                }
+                Values::Float(v) => {
+                    w.typed::<DoubleType>().write_batch(v, levels, None)?;
+                    v.clear();
+                }
+                Values::Text(v) => {
+                    w.typed::<ByteArrayType>().write_batch(v, levels, None)?;
+                    v.clear();
+                }
+            }
+            col.levels.clear();
+            w.close()?;
+        }
+        group.close()?;
+        self.rows = 0;
+
+        Ok(())
+    }
+
+    pub fn close(mut self) -> CliResult {
+        self.flush()?;
+        self.wtr.close()?;
+
+        Ok(())
+    }
+}
+
+impl ColumnBuffer {
+    // text is kept as it is, while null values of typed columns are written as nulls
+    fn push(&mut self, f: &str, parser: &DateSmartParser) -> CliResult {
+        if is_null(f) && !matches!(self.values, Values::Text(_)) {
+            self.levels.push(0);
+            return Ok(());
+        }
+
+        let ok = match (&mut self.values, &self.col_type) {
+            (Values::Int(v), ColumnType::Date) => parser
+                .smart_parse(f, self.date_format.as_ref())
+                .map(|d| v.push(d.and_utc().timestamp_millis()))
+                .is_some(),
+            (Values::Int(v), _) => f.trim().parse().map(|i| v.push(i)).is_ok(),
+            (Values::Float(v), _) => f.trim().parse().map(|i| v.push(i)).is_ok(),
+            (Values::Text(v), _) => {
+                v.push(f.into());
+                true
+            }
+        };
+        if !ok {
+            return Err(RsvError::BadNumber(format!(
+                "<{f}> in column <{}> is not a valid {}, use --text-columns to write the column as text.",
+                self.name, self.col_type
+            )));
+        }
+        self.levels.push(1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use super::{
+    column::{ColumnResolver, split_column_spec},
+    groupby::{Accumulator, AggFunc, Aggregation},
+    util::fmt_f64,
+};
+use crate::utils::error::{RsvError, RsvResult};
+use ahash::HashMap;
+use std::{fmt::Display, str::FromStr};
+
+/// The total that cells are divided by in the percentage mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PercentOf {
+    Total,
+    Row,
+    Col,
+}
+
+impl FromStr for PercentOf {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let p = match s.trim().to_lowercase().as_str() {
+            "total" => PercentOf::Total,
+            "row" => PercentOf::Row,
+            "col" | "column" => PercentOf::Col,
+            _ => {
+                return Err(RsvError::InvalidArgument(format!(
+                    "unknown percentage mode <{s}>, can be one of total, row and col."
+                )));
+            }
+        };
+
+        Ok(p)
+    }
+}
+
+impl Display for PercentOf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PercentOf::Total => "total",
+            PercentOf::Row => "row",
+            PercentOf::Col => "col",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A crosstab of a value column, with one row per distinct row key and one
+/// column per distinct value of the column key. Margins are aggregated from
+/// the data rather than from cells, so that they are right for mean, min and
+/// max as well.
+pub struct Pivot {
+    rows: Vec<usize>,
+    col: usize,
+    agg: Aggregation,
+    max_col: usize,
+    cells: HashMap<(Vec<String>, String), Accumulator>,
+    row_totals: HashMap<Vec<String>, Accumulator>,
+    col_totals: HashMap<String, Accumulator>,
+    total: Accumulator,
+    bad_rows: usize,
+}
+
+impl Pivot {
+    // Row keys follow the column syntax, e.g., 0,1 or region-date, while the
+    // column key and the value are single columns. count without a value
+    // column counts rows.
+    pub fn new(
+        rows: &str,
+        col: &str,
+        value: Option<&str>,
+        func: AggFunc,
+        resolver: &mut ColumnResolver,
+    ) -> RsvResult<Self> {
+        let mut row_cols = vec![];
+        for i in split_column_spec(rows) {
+            row_cols.extend(resolver.resolve(i)?);
+        }
+        if row_cols.is_empty() {
+            return Err(RsvError::InvalidArgument(
+                "no row key column is specified.".to_owned(),
+            ));
+        }
+
+        let col = resolver.resolve_one(col)?;
+        let value = value.map(|v| resolver.resolve_one(v)).transpose()?;
+        if !matches!(
+            func,
+            AggFunc::Count | AggFunc::Sum | AggFunc::Mean | AggFunc::Min | AggFunc::Max
+        ) {
+            return Err(RsvError::InvalidArgument(format!(
+                "unsupported aggregation <{func}>, can be one of count, sum, mean, min and max."
+            )));
+        }
+        if value.is_none() && func != AggFunc::Count {
+            return Err(RsvError::InvalidArgument(format!(
+                "a value column is needed for aggregation <{func}>."
+            )));
+        }
+
+        let agg = Aggregation::new(func, value);
+        let max_col = row_cols
+            .iter()
+            .chain([col].iter())
+            .chain(value.iter())
+            .max()
+            .copied()
+            .unwrap_or_default();
+
+        Ok(Pivot {
+            rows: row_cols,
+            col,
+            agg,
+            max_col,
+            cells: HashMap::default(),
+            row_totals: HashMap::default(),
+            col_totals: HashMap::default(),
+            total: agg.accumulator(),
+            bad_rows: 0,
+        })
+    }
+
+    /// A Pivot with the same keys and aggregation but no data, to hold partial
+    /// aggregates of a chunk.
+    pub fn empty(&self) -> Self {
+        Pivot {
+            rows: self.rows.clone(),
+            col: self.col,
+            agg: self.agg,
+            max_col: self.max_col,
+            cells: HashMap::default(),
+            row_totals: HashMap::default(),
+            col_totals: HashMap::default(),
+            total: self.agg.accumulator(),
+            bad_rows: 0,
+        }
+    }
+
+    pub fn parse_line_by_fields<T: AsRef<str>>(&mut self, v: &[T]) {
+        if self.max_col >= v.len() {
+            self.bad_rows += 1;
+            return;
+        }
+
+        let row = self
+            .rows
+            .iter()
+            .map(|&i| v[i].as_ref().to_owned())
+            .collect::<Vec<_>>();
+        let col = v[self.col].as_ref().to_owned();
+        let agg = &self.agg;
+        let update = |acc: &mut Accumulator| match agg.col() {
+            Some(c) => acc.update(v[c].as_ref()),
+            None => acc.update_row(),
+        };
+
+        update(
+            self.row_totals
+                .entry(row.clone())
+                .or_insert_with(|| agg.accumulator()),
+        );
+        update(
+            self.col_totals
+                .entry(col.clone())
+                .or_insert_with(|| agg.accumulator()),
+        );
+        update(
+            self.cells
+                .entry((row, col))
+                .or_insert_with(|| agg.accumulator()),
+        );
+        update(&mut self.total);
+    }
+
+    pub fn merge(&mut self, other: Pivot) {
+        merge_map(&mut self.cells, other.cells);
+        merge_map(&mut self.row_totals, other.row_totals);
+        merge_map(&mut self.col_totals, other.col_totals);
+        self.total.merge(other.total);
+        self.bad_rows += other.bad_rows;
+    }
+
+    /// Number of rows ignored for having fewer columns than the keys and the
+    /// value need.
+    pub fn bad_rows(&self) -> usize {
+        self.bad_rows
+    }
+
+    /// Output header and rows ordered by row keys, with columns ordered by
+    /// values of the column key. Totals append a total column and a total row,
+    /// and cells are percentages of the given total in the percentage mode.
+    pub fn into_table(
+        self,
+        names: &[String],
+        totals: bool,
+        percent: Option<PercentOf>,
+    ) -> (Vec<String>, Vec<Vec<String>>) {
+        let func = self.agg.func();
+        let value = |acc: Accumulator| acc.value(func);
+
+        let mut row_totals = self
+            .row_totals
+            .into_iter()
+            .map(|(k, acc)| (k, value(acc)))
+            .collect::<Vec<_>>();
+        row_totals.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut col_totals = self
+            .col_totals
+            .into_iter()
+            .map(|(k, acc)| (k, value(acc)))
+            .collect::<Vec<_>>();
+        col_totals.sort_by(|a, b| a.0.cmp(&b.0));
+        let total = value(self.total);
+
+        let mut cells = self
+            .cells
+            .into_iter()
+            .map(|(k, acc)| (k, value(acc)))
+            .collect::<HashMap<_, _>>();
+
+        // a cell as is, or as a percentage of its total
+        let fmt = |v: &str, row: &str, col: &str| {
+            let base = match percent {
+                None => return v.to_owned(),
+                Some(PercentOf::Total) => &total,
+                Some(PercentOf::Row) => row,
+                Some(PercentOf::Col) => col,
+            };
+            match (v.parse::<f64>(), base.parse::<f64>()) {
+                (Ok(v), Ok(b)) if b != 0.0 => fmt_f64(v / b * 100.0),
+                _ => String::new(),
+            }
+        };
+
+        // header
+        let name = |i: usize| names.get(i).cloned().unwrap_or_else(|| format!("col{i}"));
+        let mut header = self.rows.iter().map(|&i| name(i)).collect::<Vec<_>>();
+        header.extend(col_totals.iter().map(|(c, _)| c.to_owned()));
+        if totals {
+            header.push("total".to_owned());
+        }
+
+        // rows, where a cell without data is empty
+        let mut out = vec![];
+        for (row, row_total) in row_totals {
+            let mut r = row.clone();
+            let mut key = (row, String::new());
+            for (col, col_total) in &col_totals {
+                key.1.clone_from(col);
+                let v = cells.remove(&key).unwrap_or_default();
+                r.push(fmt(&v, &row_total, col_total));
+            }
+            if totals {
+                r.push(fmt(&row_total, &row_total, &total));
+            }
+            out.push(r);
+        }
+
+        if totals {
+            let mut r = vec![String::new(); self.rows.len()];
+            r[0] = "total".to_owned();
+            r.extend(col_totals.iter().map(|(_, c)| fmt(c, &total, c)));
+            r.push(fmt(&total, &total, &total));
+            out.push(r);
+        }
+
+        (header, out)
+    }
+}
+
+fn merge_map<K: std::hash::Hash + Eq>(a: &mut HashMap<K, Accumulator>, b: HashMap<K, Accumulator>) {
+    for (k, o) in b {
+        match a.get_mut(&k) {
+            Some(acc) => acc.merge(o),
+            None => {
+                a.insert(k, o);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pivot(func: AggFunc, value: Option<&str>) -> Pivot {
+        let names = ["region", "product", "amount"].map(String::from).to_vec();
+        let mut resolver = ColumnResolver::from_names(names);
+        let mut p = Pivot::new("region", "product", value, func, &mut resolver).unwrap();
+        let mut chunk = p.empty();
+        [["east", "a", "1"], ["east", "b", "2"], ["west", "a", "3"]]
+            .iter()
+            .for_each(|r| p.parse_line_by_fields(r));
+        chunk.parse_line_by_fields(&["east", "a", "4"]);
+        p.merge(chunk);
+        p
+    }
+
+    #[test]
+    fn test_pivot() {
+        let names = ["region", "product", "amount"].map(String::from);
+        let (header, rows) = pivot(AggFunc::Sum, Some("amount")).into_table(&names, true, None);
+        assert_eq!(header, ["region", "a", "b", "total"]);
+        assert_eq!(
+            rows,
+            [
+                ["east", "5", "2", "7"],
+                ["west", "3", "", "3"],
+                ["total", "8", "2", "10"]
+            ]
+        );
+
+        let (_, rows) = pivot(AggFunc::Count, None).into_table(&names, false, Some(PercentOf::Row));
+        assert_eq!(
+            rows,
+            [["east", "66.666667", "33.333333"], ["west", "100", ""]]
+        );
+    }
+}
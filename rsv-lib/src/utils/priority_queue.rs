@@ -1,3 +1,5 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
 use std::collections::BinaryHeap;
 use std::hash::Hash;
 
@@ -81,3 +83,46 @@ where
         v
     }
 }
+
+/// Random sample of n items, each given a random priority, where sampled items
+/// keep their order. A seed makes the sample reproducible.
+pub struct Sampler<T: Hash + Eq> {
+    rng: StdRng,
+    queue: PriorityQueue<T>,
+    pushed: usize,
+}
+
+impl<T> Sampler<T>
+where
+    T: Hash + Eq + Clone,
+{
+    pub fn new(n: usize, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(s) => StdRng::seed_from_u64(s),
+            None => StdRng::from_rng(&mut rng()),
+        };
+
+        Sampler {
+            rng,
+            queue: PriorityQueue::with_capacity(n),
+            pushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let priority = self.rng.random::<f64>();
+        if self.queue.can_insert(priority) {
+            self.queue.push(self.pushed, priority, item);
+        }
+        self.pushed += 1;
+    }
+
+    /// Number of items pushed so far.
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+
+    pub fn into_sorted_items(self) -> Vec<Item<T>> {
+        self.queue.into_sorted_items()
+    }
+}
@@ -1,15 +1,113 @@
-use crate::utils::util::werr_exit;
+use crate::utils::encoding::{self, TextEncoding};
+use crate::utils::error::{RsvError, RsvResult};
+use crate::utils::excel::datatype_vec_to_string_vec;
+use crate::utils::file::{is_json, is_parquet};
+use crate::utils::{compress, json, parquet};
 use calamine::{Data, Range, Reader, Rows, open_workbook_auto};
 use crossbeam_channel::Sender;
-use std::error::Error;
-use std::io::stdin;
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader, Lines},
-};
 
-pub struct ChunkReader(Lines<BufReader<File>>);
+// A record reader that follows RFC 4180: a quoted field is allowed to contain
+// line breaks, in which case several physical lines make up one record.
+// The line terminator of a record (\n or \r\n) is removed, while line breaks
+// inside a quoted field are kept as they are.
+pub struct RecordReader<R> {
+    rdr: R,
+    sep: char,
+    quote: char,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum QuoteState {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuotedEscape,
+    QuoteInQuoted,
+}
+
+impl QuoteState {
+    pub(crate) fn in_quoted_field(&self) -> bool {
+        matches!(self, QuoteState::Quoted | QuoteState::QuotedEscape)
+    }
+
+    // state at the end of a line, which is the start state of the next line
+    pub(crate) fn after(mut self, s: &str, sep: char, quote: char) -> QuoteState {
+        for c in s.chars() {
+            self = match self {
+                QuoteState::FieldStart | QuoteState::Unquoted if c == sep => QuoteState::FieldStart,
+                QuoteState::FieldStart if c == quote => QuoteState::Quoted,
+                QuoteState::FieldStart | QuoteState::Unquoted => QuoteState::Unquoted,
+                QuoteState::Quoted if c == quote => QuoteState::QuoteInQuoted,
+                QuoteState::Quoted if c == '\\' => QuoteState::QuotedEscape,
+                QuoteState::Quoted | QuoteState::QuotedEscape => QuoteState::Quoted,
+                // "" is an escaped quote within a quoted field
+                QuoteState::QuoteInQuoted if c == quote => QuoteState::Quoted,
+                QuoteState::QuoteInQuoted if c == sep => QuoteState::FieldStart,
+                QuoteState::QuoteInQuoted => QuoteState::Unquoted,
+            }
+        }
+
+        self
+    }
+}
+
+impl<R: BufRead> RecordReader<R> {
+    pub fn new(rdr: R, sep: char, quote: char) -> Self {
+        RecordReader { rdr, sep, quote }
+    }
+
+    pub fn next_record(&mut self) -> Option<Result<String, io::Error>> {
+        let mut record = String::new();
+        let mut state = QuoteState::FieldStart;
+
+        loop {
+            let start = record.len();
+            match self.rdr.read_line(&mut record) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+
+            // fast path: a line without any quote char cannot open a quoted field
+            let line = &record[start..];
+            if state == QuoteState::FieldStart && !line.contains(self.quote) {
+                break;
+            }
+
+            state = state.after(line, self.sep, self.quote);
+            if !state.in_quoted_field() {
+                break;
+            }
+        }
+
+        if record.is_empty() {
+            return None;
+        }
+
+        if record.ends_with('\n') {
+            record.pop();
+            if record.ends_with('\r') {
+                record.pop();
+            }
+        }
+
+        Some(Ok(record))
+    }
+}
+
+impl<R: BufRead> Iterator for RecordReader<R> {
+    type Item = Result<String, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record()
+    }
+}
+
+/// A reader of records in chunks, from a file by default, or from any buffered
+/// reader, e.g., stdin, so that piped data is processed the same way as files.
+pub struct ChunkReader<R = BufReader<Box<dyn Read + Send>>>(RecordReader<R>);
 
 pub struct Task {
     pub lines: Vec<String>,
@@ -18,34 +116,52 @@ pub struct Task {
 }
 
 impl ChunkReader {
-    pub fn new(path: &Path) -> Result<Self, std::io::Error> {
-        let rdr = BufReader::new(File::open(path)?).lines();
+    pub fn new(
+        path: &Path,
+        sep: char,
+        quote: char,
+        enc: TextEncoding,
+    ) -> Result<Self, std::io::Error> {
+        // compressed files are decompressed on the fly
+        let rdr = RecordReader::new(BufReader::new(compress::open(path, enc)?), sep, quote);
         Ok(ChunkReader(rdr))
     }
+}
 
+impl<R: BufRead> ChunkReader<R> {
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<Result<String, std::io::Error>> {
         self.0.next()
     }
 
-    pub fn send_to_channel_by_chunks(&mut self, tx: Sender<Task>, line_buffer_n: usize) {
+    /// Send lines in chunks to a channel. A read error stops the reading and is
+    /// returned, and the reading stops when the receiver is dropped.
+    pub fn send_to_channel_by_chunks(
+        &mut self,
+        tx: Sender<Task>,
+        line_buffer_n: usize,
+    ) -> io::Result<()> {
         let mut lines = Vec::with_capacity(line_buffer_n);
         let mut n = 0;
         let mut bytes = 0;
         let mut chunk = 1;
 
         for l in self.0.by_ref() {
-            let l = l.unwrap();
+            let l = l?;
             n += 1;
             bytes += l.len();
             lines.push(l);
             if n >= line_buffer_n {
-                tx.send(Task {
-                    lines,
-                    bytes,
-                    chunk,
-                })
-                .unwrap();
+                if tx
+                    .send(Task {
+                        lines,
+                        bytes,
+                        chunk,
+                    })
+                    .is_err()
+                {
+                    return Ok(());
+                }
                 n = 0;
                 bytes = 0;
                 lines = Vec::with_capacity(line_buffer_n);
@@ -54,13 +170,14 @@ impl ChunkReader {
         }
 
         if !lines.is_empty() {
-            tx.send(Task {
+            let _ = tx.send(Task {
                 lines,
                 bytes,
                 chunk,
-            })
-            .unwrap();
+            });
         }
+
+        Ok(())
     }
 }
 
@@ -69,19 +186,26 @@ pub struct ExcelReader {
     pub next_called: usize,
 }
 
-pub struct ExcelChunkTask {
-    pub lines: Vec<Vec<Data>>,
-    pub n: usize,
-    pub chunk: usize,
-}
-
 impl<'a> ExcelReader {
-    pub fn new(path: &Path, sheet: usize) -> Result<Self, Box<dyn Error>> {
+    pub fn new(path: &Path, sheet: usize) -> RsvResult<Self> {
+        // Parquet and JSON files are read as a sheet, and so go through the EXCEL route
+        let range = match path {
+            p if is_parquet(p) => Some(parquet::read_range(p)?),
+            p if is_json(p) => Some(json::read_range(p)?),
+            _ => None,
+        };
+        if let Some(range) = range {
+            return Ok(ExcelReader {
+                range,
+                next_called: 0,
+            });
+        }
+
         let mut workbook = open_workbook_auto(path)?;
 
-        let range = workbook.worksheet_range_at(sheet).unwrap_or_else(|| {
-            werr_exit!("{}-th sheet does not exist.", sheet);
-        })?;
+        let range = workbook
+            .worksheet_range_at(sheet)
+            .ok_or_else(|| RsvError::Excel(format!("{sheet}-th sheet does not exist.")))??;
 
         Ok(ExcelReader {
             range,
@@ -93,6 +217,10 @@ impl<'a> ExcelReader {
         self.range.get_size().0
     }
 
+    pub fn into_range(self) -> Range<Data> {
+        self.range
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -101,6 +229,14 @@ impl<'a> ExcelReader {
         self.range.get_size().1
     }
 
+    pub fn header_names(&self) -> Vec<String> {
+        self.range
+            .rows()
+            .next()
+            .map(datatype_vec_to_string_vec)
+            .unwrap_or_default()
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<&[Data]> {
         self.next_called += 1;
@@ -110,35 +246,14 @@ impl<'a> ExcelReader {
     pub fn iter(&'a self) -> Rows<'a, Data> {
         self.range.rows()
     }
-
-    pub fn send_to_channel_by_chunk(self, tx: Sender<ExcelChunkTask>, size: Option<usize>) {
-        let line_buffer_n = size.unwrap_or(1000);
-        let mut lines = Vec::with_capacity(line_buffer_n);
-        let mut n = 0;
-        let mut chunk = 1;
-        for l in self.iter().skip(self.next_called) {
-            let l = l.to_owned();
-            n += 1;
-            lines.push(l);
-            if n >= line_buffer_n {
-                tx.send(ExcelChunkTask { lines, n, chunk }).unwrap();
-                n = 0;
-                lines = Vec::with_capacity(line_buffer_n);
-                chunk += 1;
-            }
-        }
-
-        if !lines.is_empty() {
-            tx.send(ExcelChunkTask { lines, n, chunk }).unwrap();
-        }
-
-        drop(tx)
-    }
 }
 
 pub struct IoReader {
     no_header: bool,
     top_n: Option<usize>,
+    sep: char,
+    quote: char,
+    enc: TextEncoding,
 }
 
 impl Default for IoReader {
@@ -152,9 +267,27 @@ impl IoReader {
         IoReader {
             no_header: false,
             top_n: None,
+            sep: ',',
+            quote: '"',
+            enc: TextEncoding::Auto,
         }
     }
 
+    pub fn sep(&mut self, sep: char) -> &mut Self {
+        self.sep = sep;
+        self
+    }
+
+    pub fn quote(&mut self, quote: char) -> &mut Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn encoding(&mut self, enc: TextEncoding) -> &mut Self {
+        self.enc = enc;
+        self
+    }
+
     pub fn no_header(&mut self, no_header: bool) -> &mut Self {
         self.no_header = no_header;
         self
@@ -165,16 +298,56 @@ impl IoReader {
         self
     }
 
-    pub fn lines(&self) -> Vec<String> {
+    pub fn lines(&self) -> io::Result<Vec<String>> {
         // open file and header
-        let lines = stdin().lock().lines();
+        let lines = RecordReader::new(encoding::stdin_reader(self.enc)?, self.sep, self.quote);
 
-        match self.top_n {
+        let lines = match self.top_n {
             Some(n) => lines
                 .take(n + 1 - self.no_header as usize)
                 .map_while(Result::ok)
                 .collect(),
             None => lines.map_while(Result::ok).collect(),
-        }
+        };
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(s: &str) -> Vec<String> {
+        RecordReader::new(s.as_bytes(), ',', '"')
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    #[test]
+    fn test_record_reader() {
+        assert_eq!(records(""), Vec::<String>::new());
+        assert_eq!(records("a,b\n1,2\n"), vec!["a,b", "1,2"]);
+        assert_eq!(records("a,b\r\n1,2"), vec!["a,b", "1,2"]);
+
+        // line break in a quoted field
+        let r = records("id,addr\n1,\"1 Short St\nSmallville\"\n2,x\n");
+        assert_eq!(r, vec!["id,addr", "1,\"1 Short St\nSmallville\"", "2,x"]);
+
+        // crlf inside a quoted field is kept
+        let r = records("1,\"a\r\nb\",c\r\n2,d\r\n");
+        assert_eq!(r, vec!["1,\"a\r\nb\",c", "2,d"]);
+
+        // escaped quotes do not close a quoted field
+        let r = records("1,\"say \"\"hi\"\"\nthere\"\n2\n");
+        assert_eq!(r, vec!["1,\"say \"\"hi\"\"\nthere\"", "2"]);
+        let r = records("1,\"say \\\"hi\nthere\"\n2\n");
+        assert_eq!(r, vec!["1,\"say \\\"hi\nthere\"", "2"]);
+
+        // a quote inside an unquoted field is a literal char
+        assert_eq!(records("5\" screen,x\n2,y\n"), vec!["5\" screen,x", "2,y"]);
+
+        // unbalanced quote runs to the end of file
+        assert_eq!(records("1,\"a\nb"), vec!["1,\"a\nb"]);
     }
 }
@@ -1,10 +1,10 @@
+use crate::utils::{column::Columns, error::RsvResult};
 use regex::{Regex, RegexBuilder};
-use std::error::Error;
 
 pub struct Re(Regex);
 
 impl Re {
-    pub fn new(pattern: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn new(pattern: &str) -> RsvResult<Self> {
         let re = RegexBuilder::new(pattern).case_insensitive(true).build()?;
 
         Ok(Re(re))
@@ -13,4 +13,15 @@ impl Re {
     pub fn is_match(&self, v: &str) -> bool {
         self.0.is_match(v)
     }
+
+    /// Whether any field of the columns matches, or any field of the row when
+    /// all columns are selected.
+    pub fn is_match_in<T: AsRef<str>>(&self, row: &[T], cols: &Columns) -> bool {
+        match cols.select_all {
+            true => row.iter().any(|f| self.is_match(f.as_ref())),
+            false => cols
+                .iter()
+                .any(|&i| row.get(i).is_some_and(|f| self.is_match(f.as_ref()))),
+        }
+    }
 }
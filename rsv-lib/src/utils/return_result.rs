@@ -1,4 +1,6 @@
-pub type CliResultData = Result<Option<ResultData>, Box<dyn std::error::Error>>;
+use super::error::RsvResult;
+
+pub type CliResultData = RsvResult<Option<ResultData>>;
 
 #[derive(Debug)]
 pub struct ResultData {
@@ -4,6 +4,9 @@
 // 3. double-quotes in a field escaped by a backslash \
 // 4. double-quotes in a field escaped by a preceding double-quotes as discussed in
 // https://stackoverflow.com/questions/17808511/how-to-properly-escape-a-double-quote-in-csv
+//
+// A quoted field is yielded unescaped, e.g., "a ""b""" as a "b", and an unquoted
+// field is yielded as it is.
 
 // worked for examples:
 // v1,v2,v3
@@ -12,7 +15,7 @@
 // "Charles \"Pretty Boy\" Floyd","1 Short St, Smallville"
 // "Charles ""Pretty Boy"" Floyd","1 Short St, Smallville"
 
-use std::{iter::Peekable, str::CharIndices};
+use std::{borrow::Cow, iter::Peekable, str::CharIndices};
 
 #[derive(Debug)]
 pub struct CsvRowSplitter<'a> {
@@ -20,6 +23,7 @@ pub struct CsvRowSplitter<'a> {
     iter: Peekable<CharIndices<'a>>,
     sep: char,
     quote: char,
+    started: bool,
 }
 
 impl<'a> CsvRowSplitter<'a> {
@@ -29,6 +33,7 @@ impl<'a> CsvRowSplitter<'a> {
             iter: row.char_indices().peekable(),
             sep,
             quote,
+            started: false,
         }
     }
 
@@ -46,31 +51,55 @@ impl<'a> CsvRowSplitter<'a> {
     pub fn collect_owned(self) -> Vec<String> {
         self.map(String::from).collect()
     }
+
+    // e.g., a ""b"" or a \"b\" in a quoted field is a "b"
+    fn unescape(&self, f: &'a str) -> Cow<'a, str> {
+        if !f.contains(self.quote) {
+            return Cow::Borrowed(f);
+        }
+
+        let mut s = String::with_capacity(f.len());
+        let mut chars = f.chars().peekable();
+        while let Some(c) = chars.next() {
+            if (c == self.quote || c == '\\') && chars.peek() == Some(&self.quote) {
+                chars.next();
+                s.push(self.quote);
+            } else {
+                s.push(c);
+            }
+        }
+
+        Cow::Owned(s)
+    }
 }
 
 impl<'a> Iterator for CsvRowSplitter<'a> {
-    type Item = &'a str;
+    type Item = Cow<'a, str>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // First char of field
         // 1. for the first field, first char is not separator
         // 2. for other fields, first char is separator
-        let (mut start_index, mut first_char) = self.iter.next()?;
-
-        // For first-field-empty string like ",1,2,3"
-        if start_index == 0 && first_char == self.sep {
-            return Some("");
+        // For first-field-empty string like ",1,2,3", the separator is left to start
+        // the second field
+        if !self.started {
+            self.started = true;
+            if self.row.starts_with(self.sep) {
+                return Some(Cow::Borrowed(""));
+            }
         }
 
+        let (mut start_index, mut first_char) = self.iter.next()?;
+
         // Field may start with a separator that should be escaped
         // Parsing chain: "1,2,,3" => "1" -> ",2" -> "," -> ",3"
         if first_char == self.sep {
             match self.iter.peek() {
-                None => return Some(""),
+                None => return Some(Cow::Borrowed("")),
                 Some(&(index, c)) => {
                     // empty field
                     if c == self.sep {
-                        return Some("");
+                        return Some(Cow::Borrowed(""));
                     }
 
                     // true field start
@@ -86,7 +115,7 @@ impl<'a> Iterator for CsvRowSplitter<'a> {
             while let Some((i, c)) = self.iter.next() {
                 if c == self.quote {
                     if self.next_char_is_none_or_sep() {
-                        return Some(self.get_field(start_index + 1, i));
+                        return Some(self.unescape(self.get_field(start_index + 1, i)));
                     }
                 } else if c == '\\' {
                     self.iter.next();
@@ -97,12 +126,12 @@ impl<'a> Iterator for CsvRowSplitter<'a> {
         // Case 2: The field is not quoted
         while let Some(&(i, c)) = self.iter.peek() {
             if c == self.sep {
-                return Some(self.get_field(start_index, i));
+                return Some(Cow::Borrowed(self.get_field(start_index, i)));
             }
             self.iter.next();
         }
 
-        Some(self.get_field(start_index, self.row.len()))
+        Some(Cow::Borrowed(self.get_field(start_index, self.row.len())))
     }
 }
 
@@ -137,6 +166,10 @@ mod tests {
         let o = CsvRowSplitter::new(r, ',', '"').collect::<Vec<_>>();
         assert_eq!(o, vec!["", "1", "2", "3", "", "4"]);
 
+        let r = ",,3,";
+        let o = CsvRowSplitter::new(r, ',', '"').collect::<Vec<_>>();
+        assert_eq!(o, vec!["", "", "3", ""]);
+
         let r = r#"1,2,3,"","4""#;
         let o = CsvRowSplitter::new(r, ',', '"').collect::<Vec<_>>();
         assert_eq!(o, vec!["1", "2", "3", "", "4"]);
@@ -168,7 +201,12 @@ mod tests {
         let o = CsvRowSplitter::new(r, ',', '"').collect::<Vec<_>>();
         assert_eq!(
             o,
-            vec![r#"Charles ""Pretty Boy"" Floyd"#, "1 Short St, Smallville"]
+            vec![r#"Charles "Pretty Boy" Floyd"#, "1 Short St, Smallville"]
         );
+
+        // a quoted field is unescaped, and an unquoted field is not
+        let r = r#""Charles \"Pretty Boy\" Floyd",a""b"#;
+        let o = CsvRowSplitter::new(r, ',', '"').collect::<Vec<_>>();
+        assert_eq!(o, vec![r#"Charles "Pretty Boy" Floyd"#, r#"a""b"#]);
     }
 }
@@ -0,0 +1,470 @@
+use super::{
+    column_type::ColumnType,
+    date_format_infer::DateSmartParser,
+    error::{RsvError, RsvResult},
+    source::RecordSource,
+    util::{fmt_f64, is_null},
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use std::{fs, path::Path};
+
+// string columns with at most so many distinct values are inferred as enums
+const MAX_INFERRED_VALUES: usize = 10;
+
+/// Declared names, types and constraints of columns, loaded from a TOML or
+/// JSON file, e.g.,
+///
+/// ```toml
+/// [[columns]]
+/// name = "amount"
+/// type = "int"
+/// nullable = false
+/// min = 0
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub col_type: ColumnType,
+    pub nullable: bool,
+    /// Allowed values, any value is allowed when it is empty.
+    pub values: Vec<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// A regex that the whole value should match.
+    pub pattern: Option<String>,
+    pub date_format: Option<String>,
+    re: Option<Regex>,
+}
+
+/// A value that violates the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    Null,
+    Type,
+    Value,
+    Range,
+    Pattern,
+}
+
+impl Schema {
+    /// Load a schema from a .toml or .json file.
+    pub fn load(path: &Path) -> RsvResult<Self> {
+        let s = fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+
+        let v = match is_json {
+            true => serde_json::from_str::<Value>(&s).map_err(|e| e.to_string()),
+            false => s
+                .parse::<toml::Table>()
+                .map_err(|e| e.to_string())
+                .and_then(|t| serde_json::to_value(t).map_err(|e| e.to_string())),
+        };
+        let v = v.map_err(|e| {
+            RsvError::InvalidArgument(format!("bad schema file {}: {e}", path.display()))
+        })?;
+
+        Schema::from_value(&v)
+    }
+
+    pub fn from_value(v: &Value) -> RsvResult<Self> {
+        let Some(cols) = v.get("columns").and_then(|i| i.as_array()) else {
+            return Err(schema_err("a <columns> list is expected"));
+        };
+
+        let fields = cols
+            .iter()
+            .map(Field::from_value)
+            .collect::<Result<_, _>>()?;
+        Ok(Schema { fields })
+    }
+
+    pub fn to_value(&self) -> Value {
+        let cols = self.fields.iter().map(|f| f.to_value()).collect::<Vec<_>>();
+        json!({ "columns": cols })
+    }
+
+    /// TOML for a .toml file, and JSON otherwise.
+    pub fn to_string_by_extension(&self, path: Option<&Path>) -> RsvResult<String> {
+        let v = self.to_value();
+        match path.and_then(|p| p.extension()) {
+            Some(e) if e.eq_ignore_ascii_case("json") => Ok(serde_json::to_string_pretty(&v)?),
+            _ => toml::to_string(&v).map_err(|e| RsvError::Other(e.to_string())),
+        }
+    }
+
+    /// Column index of each field, matched by name in the header, or by position
+    /// when there is no header, in which case names are fields of the first row.
+    pub fn resolve<T: AsRef<str>>(&self, names: &[T], no_header: bool) -> Vec<Option<usize>> {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match no_header {
+                true => (i < names.len()).then_some(i),
+                false => names.iter().position(|n| n.as_ref().trim() == f.name),
+            })
+            .collect()
+    }
+
+    /// Like resolve, but a column that is not found is an error.
+    pub fn resolve_all<T: AsRef<str>>(
+        &self,
+        names: &[T],
+        no_header: bool,
+    ) -> RsvResult<Vec<(usize, &Field)>> {
+        self.resolve(names, no_header)
+            .into_iter()
+            .zip(&self.fields)
+            .map(|(c, f)| match c {
+                Some(c) => Ok((c, f)),
+                None => Err(RsvError::ColumnNotFound(format!(
+                    "column <{}> of the schema is not found in the file.",
+                    f.name
+                ))),
+            })
+            .collect()
+    }
+
+    /// Date columns and their formats, which are empty when not declared.
+    pub fn date_columns<T: AsRef<str>>(
+        &self,
+        names: &[T],
+        no_header: bool,
+    ) -> RsvResult<(Vec<usize>, Vec<String>)> {
+        Ok(self
+            .resolve_all(names, no_header)?
+            .into_iter()
+            .filter(|(_, f)| f.col_type.is_date())
+            .map(|(c, f)| (c, f.date_format.clone().unwrap_or_default()))
+            .unzip())
+    }
+
+    /// Draft a schema from all rows of a source, rather than from the first rows
+    /// as the type guess of other commands.
+    pub fn infer(src: &mut dyn RecordSource, no_header: bool) -> RsvResult<Self> {
+        let Some(first) = src.next_row() else {
+            return Ok(Schema::default());
+        };
+        let first = first?;
+        let names = match no_header {
+            true => (0..first.len()).map(|i| format!("col{i}")).collect(),
+            false => first.clone(),
+        };
+
+        let parser = DateSmartParser::new();
+        let mut fields = names
+            .into_iter()
+            .map(|n| FieldGuess::new(n, &parser))
+            .collect::<Vec<_>>();
+
+        let mut update = |row: &[String]| {
+            fields
+                .iter_mut()
+                .zip(row)
+                .for_each(|(f, v)| f.update(v, &parser))
+        };
+        if no_header {
+            update(&first);
+        }
+        while let Some(r) = src.next_row() {
+            update(&r?);
+        }
+
+        let fields = fields.into_iter().map(|f| f.into_field()).collect();
+        Ok(Schema { fields })
+    }
+}
+
+impl Field {
+    fn from_value(v: &Value) -> RsvResult<Self> {
+        let Some(name) = v.get("name").and_then(|i| i.as_str()) else {
+            return Err(schema_err("every column should have a <name>"));
+        };
+        let err = |key: &str| schema_err(&format!("<{key}> of column <{name}> is wrong"));
+
+        let col_type = match v.get("type").map(|i| i.as_str()) {
+            None => ColumnType::String,
+            Some(Some("int" | "integer")) => ColumnType::Int,
+            Some(Some("float" | "number")) => ColumnType::Float,
+            Some(Some("string" | "text")) => ColumnType::String,
+            Some(Some("date" | "datetime")) => ColumnType::Date,
+            _ => return Err(err("type")),
+        };
+        let nullable = match v.get("nullable") {
+            None => true,
+            Some(i) => i.as_bool().ok_or_else(|| err("nullable"))?,
+        };
+        let values = match v.get("values") {
+            None => vec![],
+            Some(i) => i
+                .as_array()
+                .ok_or_else(|| err("values"))?
+                .iter()
+                .map(|i| match i {
+                    Value::String(s) => s.to_owned(),
+                    i => i.to_string(),
+                })
+                .collect(),
+        };
+        let number = |key: &str| match v.get(key) {
+            None => Ok(None),
+            Some(i) => i.as_f64().map(Some).ok_or_else(|| err(key)),
+        };
+        let string = |key: &str| match v.get(key) {
+            None => Ok(None),
+            Some(i) => i
+                .as_str()
+                .map(|s| Some(s.to_owned()))
+                .ok_or_else(|| err(key)),
+        };
+        let pattern = string("pattern")?;
+        let re = match &pattern {
+            Some(p) => Some(Regex::new(&format!("^(?:{p})$"))?),
+            None => None,
+        };
+
+        Ok(Field {
+            name: name.to_owned(),
+            col_type,
+            nullable,
+            values,
+            min: number("min")?,
+            max: number("max")?,
+            pattern,
+            date_format: string("date_format")?,
+            re,
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        let mut m = Map::new();
+        m.insert("name".to_owned(), json!(self.name));
+        m.insert("type".to_owned(), json!(self.col_type.to_string()));
+        m.insert("nullable".to_owned(), json!(self.nullable));
+        if !self.values.is_empty() {
+            m.insert("values".to_owned(), json!(self.values));
+        }
+        let number = |v: f64| match self.col_type {
+            ColumnType::Int => json!(v as i64),
+            _ => json!(v),
+        };
+        if let Some(v) = self.min {
+            m.insert("min".to_owned(), number(v));
+        }
+        if let Some(v) = self.max {
+            m.insert("max".to_owned(), number(v));
+        }
+        if let Some(p) = &self.pattern {
+            m.insert("pattern".to_owned(), json!(p));
+        }
+        if let Some(f) = &self.date_format {
+            m.insert("date_format".to_owned(), json!(f));
+        }
+
+        Value::Object(m)
+    }
+
+    /// Check a value, and return the violation with a detail when it is invalid.
+    pub fn check(&self, v: &str, parser: &DateSmartParser) -> Option<(Violation, String)> {
+        let name = &self.name;
+        if is_null(v) {
+            return match self.nullable {
+                true => None,
+                false => Some((Violation::Null, format!("<{name}> should not be null"))),
+            };
+        }
+
+        let number = match self.col_type {
+            ColumnType::Int => v.parse::<i64>().ok().map(|i| i as f64),
+            ColumnType::Float => v.parse::<f64>().ok(),
+            _ => None,
+        };
+        let is_type = match self.col_type {
+            ColumnType::Int | ColumnType::Float => number.is_some(),
+            ColumnType::Date => match &self.date_format {
+                Some(f) => parse_date_with(v, f),
+                None => parser.smart_parse(v, None).is_some(),
+            },
+            _ => true,
+        };
+        if !is_type {
+            let t = match &self.date_format {
+                Some(f) if self.col_type.is_date() => format!("date of format {f}"),
+                _ => self.col_type.to_string(),
+            };
+            return Some((Violation::Type, format!("<{name}> should be {t}: {v}")));
+        }
+
+        if !self.values.is_empty() && !self.values.iter().any(|i| i == v) {
+            let detail = format!("<{name}> should be one of {}: {v}", self.values.join("|"));
+            return Some((Violation::Value, detail));
+        }
+
+        if let Some(n) = number {
+            let out_of_range = self.min.is_some_and(|m| n < m) || self.max.is_some_and(|m| n > m);
+            if out_of_range {
+                let bound = |b: Option<f64>| b.map(fmt_f64).unwrap_or_default();
+                let detail = format!(
+                    "<{name}> should be in [{}, {}]: {v}",
+                    bound(self.min),
+                    bound(self.max)
+                );
+                return Some((Violation::Range, detail));
+            }
+        }
+
+        if let Some(re) = &self.re
+            && !re.is_match(v)
+        {
+            let p = self.pattern.as_deref().unwrap_or_default();
+            let detail = format!("<{name}> should match {p}: {v}");
+            return Some((Violation::Pattern, detail));
+        }
+
+        None
+    }
+}
+
+// type, nullability, range and distinct values of a column seen so far
+struct FieldGuess {
+    name: String,
+    col_type: ColumnType,
+    nullable: bool,
+    count: usize,
+    values: Option<Vec<String>>,
+    min: f64,
+    max: f64,
+    is_date: bool,
+    // formats that all values seen so far match
+    date_formats: Vec<String>,
+}
+
+impl FieldGuess {
+    fn new(name: String, parser: &DateSmartParser) -> Self {
+        FieldGuess {
+            name,
+            col_type: ColumnType::Null,
+            nullable: false,
+            count: 0,
+            values: Some(vec![]),
+            min: f64::MAX,
+            max: f64::MIN,
+            is_date: true,
+            date_formats: parser.formats().cloned().collect(),
+        }
+    }
+
+    fn update(&mut self, v: &str, parser: &DateSmartParser) {
+        if is_null(v) {
+            self.nullable = true;
+            return;
+        }
+
+        self.count += 1;
+        self.col_type.update(v);
+        if let Ok(n) = v.parse::<f64>() {
+            self.min = self.min.min(n);
+            self.max = self.max.max(n);
+        }
+
+        if let Some(values) = &mut self.values
+            && !values.iter().any(|i| i == v)
+        {
+            match values.len() < MAX_INFERRED_VALUES {
+                true => values.push(v.to_owned()),
+                false => self.values = None,
+            }
+        }
+
+        if self.is_date && self.col_type.is_string() {
+            self.is_date = parser.smart_parse(v, None).is_some();
+            self.date_formats.retain(|f| parse_date_with(v, f));
+        }
+    }
+
+    fn into_field(self) -> Field {
+        let (col_type, date_format) = match self.col_type {
+            ColumnType::String if self.is_date => (ColumnType::Date, self.date_formats.first()),
+            // a column of nulls only
+            ColumnType::Null => (ColumnType::String, None),
+            t => (t, None),
+        };
+        let (min, max) = match col_type.is_number() {
+            true => (Some(self.min), Some(self.max)),
+            false => (None, None),
+        };
+        // values are listed when they repeat, e.g., a category
+        let values = match (&col_type, self.values) {
+            (ColumnType::String, Some(v)) if v.len() * 2 <= self.count => v,
+            _ => vec![],
+        };
+
+        Field {
+            name: self.name,
+            col_type,
+            nullable: self.nullable,
+            values,
+            min,
+            max,
+            pattern: None,
+            date_format: date_format.cloned(),
+            re: None,
+        }
+    }
+}
+
+fn parse_date_with(v: &str, fmt: &str) -> bool {
+    let v = v.trim();
+    NaiveDate::parse_from_str(v, fmt).is_ok() || NaiveDateTime::parse_from_str(v, fmt).is_ok()
+}
+
+fn schema_err(msg: &str) -> RsvError {
+    RsvError::InvalidArgument(format!("bad schema: {msg}."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let s = r#"
+            [[columns]]
+            name = "amount"
+            type = "int"
+            nullable = false
+            min = 0
+            max = 100
+
+            [[columns]]
+            name = "code"
+            pattern = "[A-Z]{2}"
+            values = ["AB", "CD", "ab"]
+        "#;
+        let v = serde_json::to_value(s.parse::<toml::Table>().unwrap()).unwrap();
+        let schema = Schema::from_value(&v).unwrap();
+        let parser = DateSmartParser::new();
+        let check = |i: usize, v: &str| schema.fields[i].check(v, &parser).map(|i| i.0);
+
+        assert_eq!(check(0, "50"), None);
+        assert_eq!(check(0, ""), Some(Violation::Null));
+        assert_eq!(check(0, "1.5"), Some(Violation::Type));
+        assert_eq!(check(0, "101"), Some(Violation::Range));
+        assert_eq!(check(1, ""), None);
+        assert_eq!(check(1, "CD"), None);
+        assert_eq!(check(1, "EF"), Some(Violation::Value));
+        assert_eq!(check(1, "ab"), Some(Violation::Pattern));
+        assert_eq!(
+            schema.resolve(&["code", "id", "amount"], false),
+            [Some(2), Some(0)]
+        );
+    }
+}
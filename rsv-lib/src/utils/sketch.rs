@@ -0,0 +1,232 @@
+// default accuracy of the quantile sketch, with a rank error of about 1% or less
+const KLL_K: usize = 256;
+
+/// Count, mean and sum of squared deviations of values, updated by Welford's
+/// algorithm and merged by Chan's formula, so that chunks can be processed in
+/// parallel.
+#[derive(Debug, Clone, Default)]
+pub struct Moments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    pub fn update(&mut self, v: f64) {
+        self.n += 1;
+        let d = v - self.mean;
+        self.mean += d / self.n as f64;
+        self.m2 += d * (v - self.mean);
+    }
+
+    pub fn merge(&mut self, o: &Moments) {
+        if o.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = o.clone();
+            return;
+        }
+
+        let n = self.n + o.n;
+        let d = o.mean - self.mean;
+        self.mean += d * o.n as f64 / n as f64;
+        self.m2 += o.m2 + d * d * (self.n * o.n) as f64 / n as f64;
+        self.n = n;
+    }
+
+    /// Sample variance, or 0 with fewer than two values.
+    pub fn variance(&self) -> f64 {
+        match self.n {
+            0 | 1 => 0.0,
+            n => self.m2 / (n - 1) as f64,
+        }
+    }
+
+    pub fn stddev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// A KLL sketch for approximate quantiles of a stream of values. Values are
+/// kept in levels, where a value at level h stands for 2^h values, and a full
+/// level is compacted by keeping every other sorted value in the next level.
+/// Quantiles are exact until the first compaction, and sketches of chunks
+/// can be merged.
+#[derive(Debug, Clone)]
+pub struct KllSketch {
+    k: usize,
+    levels: Vec<Vec<f64>>,
+    // alternate the kept half of compactions, deterministic unlike a coin flip
+    odd: bool,
+}
+
+impl Default for KllSketch {
+    fn default() -> Self {
+        KllSketch::new(KLL_K)
+    }
+}
+
+impl KllSketch {
+    pub fn new(k: usize) -> Self {
+        KllSketch {
+            k: k.max(8),
+            levels: vec![vec![]],
+            odd: false,
+        }
+    }
+
+    pub fn update(&mut self, v: f64) {
+        self.levels[0].push(v);
+        self.compact();
+    }
+
+    pub fn merge(&mut self, o: &KllSketch) {
+        if self.levels.len() < o.levels.len() {
+            self.levels.resize(o.levels.len(), vec![]);
+        }
+        self.levels
+            .iter_mut()
+            .zip(&o.levels)
+            .for_each(|(a, b)| a.extend(b));
+        self.compact();
+    }
+
+    /// Whether quantiles are exact, i.e., no value has been compacted yet, which
+    /// holds for up to about k values.
+    pub fn is_exact(&self) -> bool {
+        self.levels.len() == 1
+    }
+
+    /// Number of values seen.
+    pub fn count(&self) -> usize {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(h, l)| l.len() << h)
+            .sum()
+    }
+
+    // capacity decays by 2/3 from the top level downwards
+    fn capacity(&self, h: usize) -> usize {
+        let depth = (self.levels.len() - 1 - h) as i32;
+        ((self.k as f64 * (2.0f64 / 3.0).powi(depth)).ceil() as usize).max(2)
+    }
+
+    fn compact(&mut self) {
+        let mut h = 0;
+        while h < self.levels.len() {
+            if self.levels[h].len() > self.capacity(h) {
+                if h + 1 == self.levels.len() {
+                    self.levels.push(vec![]);
+                }
+
+                let mut l = std::mem::take(&mut self.levels[h]);
+                l.sort_unstable_by(f64::total_cmp);
+                // an odd value out stays at the level, so the total weight is kept
+                if l.len() % 2 == 1 {
+                    self.levels[h].push(l.pop().unwrap());
+                }
+                let start = self.odd as usize;
+                self.odd = !self.odd;
+                let kept = l.into_iter().skip(start).step_by(2);
+                self.levels[h + 1].extend(kept);
+            }
+            h += 1;
+        }
+    }
+
+    // sorted values with their weights
+    fn weighted(&self) -> Vec<(f64, usize)> {
+        let mut v = self
+            .levels
+            .iter()
+            .enumerate()
+            .flat_map(|(h, l)| l.iter().map(move |&i| (i, 1usize << h)))
+            .collect::<Vec<_>>();
+        v.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        v
+    }
+
+    /// The value at the quantile q in [0, 1] by the nearest rank, or None when
+    /// no value is seen.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.quantiles(&[q]).pop().flatten()
+    }
+
+    pub fn quantiles(&self, qs: &[f64]) -> Vec<Option<f64>> {
+        let v = self.weighted();
+        let n = self.count();
+
+        qs.iter()
+            .map(|q| {
+                let rank = ((q * n as f64).ceil() as usize).max(1);
+                let mut seen = 0;
+                v.iter().find_map(|&(i, w)| {
+                    seen += w;
+                    (seen >= rank).then_some(i)
+                })
+            })
+            .collect()
+    }
+
+    /// Counts of values in equal-width bins from min to max, where the last
+    /// bin includes max.
+    pub fn histogram(&self, min: f64, max: f64, bins: usize) -> Vec<usize> {
+        let mut counts = vec![0; bins];
+        if bins == 0 {
+            return counts;
+        }
+
+        let width = (max - min) / bins as f64;
+        for (v, w) in self.weighted() {
+            let i = match width > 0.0 {
+                true => ((v - min) / width) as usize,
+                false => 0,
+            };
+            counts[i.min(bins - 1)] += w;
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moments() {
+        let mut a = Moments::default();
+        let mut b = Moments::default();
+        [2.0, 4.0, 4.0, 4.0].iter().for_each(|&v| a.update(v));
+        [5.0, 5.0, 7.0, 9.0].iter().for_each(|&v| b.update(v));
+        a.merge(&b);
+        assert!((a.variance() - 32.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_kll_sketch() {
+        let mut a = KllSketch::default();
+        (1..=100).for_each(|v| a.update(v as f64));
+        assert_eq!(a.quantile(0.5), Some(50.0));
+        assert_eq!(
+            a.quantiles(&[0.0, 0.25, 1.0]),
+            [Some(1.0), Some(25.0), Some(100.0)]
+        );
+        assert_eq!(a.histogram(1.0, 100.0, 4), [25, 25, 25, 25]);
+        assert!(a.is_exact());
+
+        // approximate after compactions, and merged from chunks
+        let mut b = KllSketch::default();
+        for chunk in (0..100_000).collect::<Vec<_>>().chunks(1000) {
+            let mut s = KllSketch::default();
+            chunk.iter().for_each(|&v| s.update(v as f64));
+            b.merge(&s);
+        }
+        assert_eq!(b.count(), 100_000);
+        assert!(!b.is_exact());
+        let median = b.quantile(0.5).unwrap();
+        assert!((median - 50_000.0).abs() < 1_000.0);
+    }
+}
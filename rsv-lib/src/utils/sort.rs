@@ -1,389 +1,302 @@
+use crate::utils::error::{RsvError, RsvResult};
 use crate::utils::row_split::CsvRowSplitter;
 
-use super::{cli_result::CliResult, writer::Writer};
+use super::{
+    cli_result::CliResult,
+    column::{ColumnResolver, split_column_spec},
+    date_format_infer::DateSmartParser,
+    writer::Writer,
+};
+use chrono::NaiveDateTime;
 use rayon::prelude::*;
-use std::{borrow::Cow, error::Error};
+use std::{borrow::Cow, cmp::Ordering, iter::Peekable, str::Chars};
 
-pub struct SortColumns(Vec<SortColumn>);
+const FLAGS: [char; 12] = ['d', 'D', 'n', 'N', 'v', 'V', 't', 'T', 'f', 'F', 'l', 'L'];
+
+pub struct SortColumns {
+    cols: Vec<SortColumn>,
+    date_parser: DateSmartParser,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKind {
+    Str,
+    Numeric,
+    Natural,
+    Date,
+}
 
 pub struct SortColumn {
     col: usize,
     ascending: bool,
-    pub numeric: bool,
+    pub kind: SortKind,
+    nulls_first: bool,
 }
 
-impl SortColumns {
-    pub fn from(cols: &str) -> Result<Self, Box<dyn Error>> {
-        let mut r = SortColumns(vec![]);
+// Empty fields, and fields that cannot be parsed as a number or a date, are
+// regarded as nulls in numeric and date sorts. String and natural sorts compare
+// fields as they are, so that an empty field comes first in ascending order.
+#[derive(Debug)]
+pub enum SortKey<'a> {
+    Null,
+    Str(Cow<'a, str>),
+    Numeric(f64),
+    Natural(Cow<'a, str>),
+    Date(NaiveDateTime),
+}
 
-        for i in cols.split(',') {
-            let mut j = i.replace(' ', "");
+impl SortColumns {
+    // Column is followed by optional flags:
+    // 1. D for descending, default to ascending;
+    // 2. N for numeric, V for natural (e.g., file2 < file10), T for date,
+    //    default to string;
+    // 3. F for nulls first, L for nulls last, default to nulls last, for
+    //    numeric and date sorts only.
+    // e.g., 0, 0N, 0DN, amountD, dateTF. A column name ending with these
+    // letters, e.g., id, takes precedence over the flags.
+    pub fn from(cols: &str, resolver: &mut ColumnResolver) -> RsvResult<Self> {
+        let mut r = vec![];
+
+        for i in split_column_spec(cols) {
+            let syntax_error = || {
+                format!(
+                    "column syntax error for <-c {i}>, a column can be followed by flags D (descending), \
+                    N (numeric), V (natural), T (date), and F or L (nulls first or last). \
+                    Run <rsv sort -h> for help."
+                )
+            };
 
-            (0..2).for_each(|_| {
-                if j.ends_with(['n', 'N', 'd', 'D']) {
-                    j.pop();
+            let mut spec = i;
+            let col = loop {
+                if let Some(col) = resolver.try_resolve_one(spec)? {
+                    break col;
                 }
-            });
-
-            if j.is_empty() {
-                continue;
+                match spec.strip_suffix(FLAGS) {
+                    Some(v) if i.len() - v.len() <= 3 => spec = v,
+                    _ => return Err(RsvError::InvalidArgument(syntax_error())),
+                }
+            };
+
+            let flags = i[spec.len()..].to_uppercase();
+            let kinds = [
+                ('N', SortKind::Numeric),
+                ('V', SortKind::Natural),
+                ('T', SortKind::Date),
+            ]
+            .into_iter()
+            .filter(|(c, _)| flags.contains(*c))
+            .map(|(_, k)| k)
+            .collect::<Vec<_>>();
+            if kinds.len() > 1 || (flags.contains('F') && flags.contains('L')) {
+                return Err(RsvError::InvalidArgument(syntax_error()));
             }
-
-            if let Ok(col) = j.parse::<usize>() {
-                r.0.push(SortColumn {
-                    col,
-                    ascending: !i.contains(['d', 'D']),
-                    numeric: i.contains(['n', 'N']),
-                });
-            } else {
-                let e = format!("column syntax error for <-c {i}>. Run <rsv sort -h> for help.");
-                return Err(e.into());
+            let kind = kinds.first().copied().unwrap_or(SortKind::Str);
+            if (flags.contains('F') || flags.contains('L'))
+                && !matches!(kind, SortKind::Numeric | SortKind::Date)
+            {
+                return Err(RsvError::InvalidArgument(format!(
+                    "nulls flags in <-c {i}> only apply to numeric (N) or date (T) sorts."
+                )));
             }
-        }
 
-        if r.0.is_empty() {
-            return Err("no column is specified.".into());
+            r.push(SortColumn {
+                col,
+                ascending: !flags.contains('D'),
+                kind,
+                nulls_first: flags.contains('F'),
+            });
         }
 
-        if r.0.len() > 2 {
-            return Err("sort by more than two columns is not supported.".into());
+        if r.is_empty() {
+            return Err(RsvError::InvalidArgument(
+                "no column is specified.".to_owned(),
+            ));
         }
 
-        Ok(r)
+        Ok(SortColumns {
+            cols: r,
+            date_parser: DateSmartParser::new(),
+        })
     }
 
-    fn col_at(&self, n: usize) -> usize {
-        self.0[n].col
+    /// Sort keys of a record, to be compared with [`SortColumns::compare`].
+    pub fn keys<'a, T: AsRef<str>>(&self, fields: &'a [T]) -> Vec<SortKey<'a>> {
+        self.cols
+            .iter()
+            .map(|c| c.key(fields.get(c.col).map(|f| f.as_ref()), &self.date_parser))
+            .collect()
     }
 
-    fn ascending_at(&self, n: usize) -> bool {
-        self.0[n].ascending
-    }
-
-    fn numeric_at(&self, n: usize) -> bool {
-        self.0[n].numeric
+    /// Compare the sort keys of two records column by column.
+    pub fn compare(&self, a: &[SortKey], b: &[SortKey]) -> Ordering {
+        self.cols
+            .iter()
+            .zip(a.iter().zip(b))
+            .map(|(c, (a, b))| c.compare(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
     }
 
     pub fn sort_and_write(
         &self,
-        lines: &Vec<String>,
+        lines: &[String],
         sep: char,
         quote: char,
         wtr: &mut Writer,
     ) -> CliResult {
-        match self.0.len() {
-            1 => match self.numeric_at(0) {
-                true => self.sort_numeric_column(lines, sep, quote, wtr),
-                false => self.sort_str_column(lines, sep, quote, wtr),
-            },
-            2 => match (self.numeric_at(0), self.numeric_at(1)) {
-                (true, true) => self.sort_numeric_numeric_columns(lines, sep, quote, wtr),
-                (true, false) => self.sort_numeric_str_columns(lines, sep, quote, wtr),
-                (false, true) => self.sort_str_numeric_columns(lines, sep, quote, wtr),
-                (false, false) => self.sort_str_str_columns(lines, sep, quote, wtr),
-            },
-            _ => {}
-        }
-
-        Ok(())
-    }
-
-    fn sort_str_column(&self, lines: &Vec<String>, sep: char, quote: char, wtr: &mut Writer) {
-        let c = self.col_at(0);
-        let mut r = lines
+        let fields = lines
             .par_iter()
-            .map(|i| {
-                (
-                    i,
-                    CsvRowSplitter::new(i, sep, quote)
-                        .nth(c)
-                        .unwrap_or_default(),
-                )
-            })
+            .map(|l| CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>())
             .collect::<Vec<_>>();
-        match self.ascending_at(0) {
-            true => r.sort_by(|&a, &b| a.1.cmp(b.1)),
-            false => r.sort_by(|&a, &b| b.1.cmp(a.1)),
-        }
-
-        r.iter().for_each(|(l, _)| wtr.write_str_unchecked(l));
-    }
-
-    fn sort_numeric_column(&self, lines: &Vec<String>, sep: char, quote: char, wtr: &mut Writer) {
-        let c = self.col_at(0);
         let mut r = lines
             .par_iter()
-            .map(|i| {
-                let f = CsvRowSplitter::new(i, sep, quote)
-                    .nth(c)
-                    .unwrap_or_default();
-                (i, f.parse::<f64>().unwrap_or_default())
-            })
+            .zip(&fields)
+            .map(|(l, f)| (l, self.keys(f)))
             .collect::<Vec<_>>();
-        match self.ascending_at(0) {
-            true => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap()),
-            false => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap()),
-        };
+        r.par_sort_by(|a, b| self.compare(&a.1, &b.1));
 
         r.iter().for_each(|(l, _)| wtr.write_str_unchecked(l));
-    }
 
-    fn sort_str_str_columns(&self, lines: &Vec<String>, sep: char, quote: char, wtr: &mut Writer) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| {
-                let f = CsvRowSplitter::new(i, sep, quote).collect::<Vec<_>>();
-                (i, f[c1], f[c2])
-            })
-            .collect::<Vec<_>>();
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(a.2.cmp(b.2))),
-            (true, false) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(b.2.cmp(a.2))),
-            (false, true) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(a.2.cmp(b.2))),
-            (false, false) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(b.2.cmp(a.2))),
-        }
-
-        r.iter().for_each(|(l, _, _)| wtr.write_str_unchecked(l));
+        Ok(())
     }
 
-    fn sort_str_numeric_columns(
-        &self,
-        lines: &Vec<String>,
-        sep: char,
-        quote: char,
-        wtr: &mut Writer,
-    ) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| {
-                let f = CsvRowSplitter::new(i, sep, quote).collect::<Vec<_>>();
-                (i, f[c1], f[c2].parse::<f64>().unwrap_or_default())
-            })
-            .collect::<Vec<_>>();
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(a.2.partial_cmp(&b.2).unwrap())),
-            (true, false) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(b.2.partial_cmp(&a.2).unwrap())),
-            (false, true) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(a.2.partial_cmp(&b.2).unwrap())),
-            (false, false) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(b.2.partial_cmp(&a.2).unwrap())),
-        }
+    /// Rows in a stable order of their sort keys.
+    pub fn sort_rows<T: AsRef<str> + Send + Sync>(&self, rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+        let order = {
+            let keys = rows.par_iter().map(|r| self.keys(r)).collect::<Vec<_>>();
+            let mut order = (0..rows.len()).collect::<Vec<_>>();
+            order.par_sort_by(|&a, &b| self.compare(&keys[a], &keys[b]));
+            order
+        };
 
-        r.iter().for_each(|(l, _, _)| wtr.write_str_unchecked(l));
+        let mut rows = rows.into_iter().map(Some).collect::<Vec<_>>();
+        order.into_iter().filter_map(|i| rows[i].take()).collect()
     }
+}
 
-    fn sort_numeric_str_columns(
-        &self,
-        lines: &Vec<String>,
-        sep: char,
-        quote: char,
-        wtr: &mut Writer,
-    ) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| {
-                let f = CsvRowSplitter::new(i, sep, quote).collect::<Vec<_>>();
-                (i, f[c1].parse::<f64>().unwrap_or_default(), f[c2])
-            })
-            .collect::<Vec<_>>();
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap().then(a.2.cmp(b.2))),
-            (true, false) => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap().then(b.2.cmp(a.2))),
-            (false, true) => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap().then(a.2.cmp(b.2))),
-            (false, false) => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap().then(b.2.cmp(a.2))),
+impl SortColumn {
+    fn key<'a>(&self, field: Option<&'a str>, date_parser: &DateSmartParser) -> SortKey<'a> {
+        let f = field.unwrap_or_default();
+
+        match self.kind {
+            SortKind::Str => SortKey::Str(Cow::Borrowed(f)),
+            SortKind::Natural => SortKey::Natural(Cow::Borrowed(f)),
+            _ if f.trim().is_empty() => SortKey::Null,
+            SortKind::Numeric => f
+                .trim()
+                .parse::<f64>()
+                .map_or(SortKey::Null, SortKey::Numeric),
+            SortKind::Date => date_parser
+                .smart_parse(f, None)
+                .map_or(SortKey::Null, SortKey::Date),
         }
-
-        r.iter().for_each(|(l, _, _)| wtr.write_str_unchecked(l));
     }
 
-    fn sort_numeric_numeric_columns(
-        &self,
-        lines: &Vec<String>,
-        sep: char,
-        quote: char,
-        wtr: &mut Writer,
-    ) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| {
-                let f = CsvRowSplitter::new(i, sep, quote).collect::<Vec<_>>();
-                (
-                    i,
-                    f[c1].parse::<f64>().unwrap_or_default(),
-                    f[c2].parse::<f64>().unwrap_or_default(),
-                )
-            })
-            .collect::<Vec<_>>();
-
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| {
-                a.1.partial_cmp(&b.1)
-                    .unwrap()
-                    .then(a.2.partial_cmp(&b.2).unwrap())
-            }),
-            (true, false) => r.sort_by(|&a, &b| {
-                a.1.partial_cmp(&b.1)
-                    .unwrap()
-                    .then(b.2.partial_cmp(&a.2).unwrap())
-            }),
-            (false, true) => r.sort_by(|&a, &b| {
-                b.1.partial_cmp(&a.1)
-                    .unwrap()
-                    .then(a.2.partial_cmp(&b.2).unwrap())
-            }),
-            (false, false) => r.sort_by(|&a, &b| {
-                b.1.partial_cmp(&a.1)
-                    .unwrap()
-                    .then(b.2.partial_cmp(&a.2).unwrap())
-            }),
-        }
-
-        r.iter().for_each(|(l, _, _)| wtr.write_str_unchecked(l));
-    }
+    fn compare(&self, a: &SortKey, b: &SortKey) -> Ordering {
+        // null placement is independent of the sort direction
+        let null = match self.nulls_first {
+            true => Ordering::Less,
+            false => Ordering::Greater,
+        };
 
-    pub fn sort_excel_and_write(
-        &self,
-        lines: &mut Vec<Vec<Cow<str>>>,
-        wtr: &mut Writer,
-    ) -> CliResult {
-        match self.0.len() {
-            1 => match self.numeric_at(0) {
-                true => self.sort_excel_numeric_column(lines, wtr),
-                false => self.sort_excel_str_column(lines, wtr),
+        match (a, b) {
+            (SortKey::Null, SortKey::Null) => Ordering::Equal,
+            (SortKey::Null, _) => null,
+            (_, SortKey::Null) => null.reverse(),
+            _ => match self.ascending {
+                true => a.cmp_value(b),
+                false => b.cmp_value(a),
             },
-            2 => match (self.numeric_at(0), self.numeric_at(1)) {
-                (true, true) => self.sort_excel_numeric_numeric_columns(lines, wtr),
-                (true, false) => self.sort_excel_numeric_str_columns(lines, wtr),
-                (false, true) => self.sort_excel_str_numeric_columns(lines, wtr),
-                (false, false) => self.sort_excel_str_str_columns(lines, wtr),
-            },
-            _ => {}
         }
-
-        Ok(())
     }
+}
 
-    fn sort_excel_str_column(&self, lines: &mut [Vec<Cow<str>>], wtr: &mut Writer) {
-        let c = self.col_at(0);
-        match self.ascending_at(0) {
-            true => lines.sort_by(|a, b| a[c].cmp(&b[c])),
-            false => lines.sort_by(|a, b| b[c].cmp(&a[c])),
+impl SortKey<'_> {
+    pub fn into_owned(self) -> SortKey<'static> {
+        match self {
+            SortKey::Null => SortKey::Null,
+            SortKey::Str(v) => SortKey::Str(Cow::Owned(v.into_owned())),
+            SortKey::Numeric(v) => SortKey::Numeric(v),
+            SortKey::Natural(v) => SortKey::Natural(Cow::Owned(v.into_owned())),
+            SortKey::Date(v) => SortKey::Date(v),
         }
-
-        lines.iter().for_each(|l| wtr.write_fields_unchecked(l));
-    }
-
-    fn sort_excel_numeric_column(&self, lines: &mut Vec<Vec<Cow<str>>>, wtr: &mut Writer) {
-        let c = self.col_at(0);
-        let mut r = lines
-            .par_iter()
-            .map(|i| (i, i[c].parse::<f64>().unwrap_or_default()))
-            .collect::<Vec<_>>();
-        match self.ascending_at(0) {
-            true => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap()),
-            false => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap()),
-        };
-
-        r.iter().for_each(|(l, _)| wtr.write_fields_unchecked(l));
     }
 
-    fn sort_excel_str_str_columns(&self, lines: &mut [Vec<Cow<str>>], wtr: &mut Writer) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => lines.sort_by(|a, b| a[c1].cmp(&b[c1]).then(a[c2].cmp(&b[c2]))),
-            (true, false) => lines.sort_by(|a, b| a[c1].cmp(&b[c1]).then(b[c2].cmp(&a[c2]))),
-            (false, true) => lines.sort_by(|a, b| b[c1].cmp(&a[c1]).then(a[c2].cmp(&b[c2]))),
-            (false, false) => lines.sort_by(|a, b| b[c1].cmp(&a[c1]).then(b[c2].cmp(&a[c2]))),
+    fn cmp_value(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Str(a), SortKey::Str(b)) => a.cmp(b),
+            (SortKey::Numeric(a), SortKey::Numeric(b)) => a.total_cmp(b),
+            (SortKey::Natural(a), SortKey::Natural(b)) => natural_cmp(a, b),
+            (SortKey::Date(a), SortKey::Date(b)) => a.cmp(b),
+            _ => Ordering::Equal,
         }
-
-        lines.iter().for_each(|l| wtr.write_fields_unchecked(l));
     }
+}
 
-    fn sort_excel_str_numeric_columns(&self, lines: &mut Vec<Vec<Cow<str>>>, wtr: &mut Writer) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| (i, &i[c1], i[c2].parse::<f64>().unwrap_or_default()))
-            .collect::<Vec<_>>();
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(a.2.partial_cmp(&b.2).unwrap())),
-            (true, false) => r.sort_by(|&a, &b| a.1.cmp(b.1).then(b.2.partial_cmp(&a.2).unwrap())),
-            (false, true) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(a.2.partial_cmp(&b.2).unwrap())),
-            (false, false) => r.sort_by(|&a, &b| b.1.cmp(a.1).then(b.2.partial_cmp(&a.2).unwrap())),
+// Natural order compares digit runs by their numeric values, e.g., file2 < file10.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, _) => return Ordering::Less,
+            (_, None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a);
+                let y = take_digits(&mut b);
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                let o = x.len().cmp(&y.len()).then_with(|| x.cmp(y));
+                if o.is_ne() {
+                    return o;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
         }
-
-        r.iter().for_each(|(l, _, _)| wtr.write_fields_unchecked(l));
     }
+}
 
-    fn sort_excel_numeric_str_columns(&self, lines: &mut [Vec<Cow<str>>], wtr: &mut Writer) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| (i, i[c1].parse::<f64>().unwrap_or_default(), &(i[c2])))
-            .collect::<Vec<_>>();
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap().then(a.2.cmp(b.2))),
-            (true, false) => r.sort_by(|&a, &b| a.1.partial_cmp(&b.1).unwrap().then(b.2.cmp(a.2))),
-            (false, true) => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap().then(a.2.cmp(b.2))),
-            (false, false) => r.sort_by(|&a, &b| b.1.partial_cmp(&a.1).unwrap().then(b.2.cmp(a.2))),
-        }
-
-        r.iter().for_each(|(l, _, _)| wtr.write_fields_unchecked(l));
+fn take_digits(s: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = s.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
     }
+    digits
+}
 
-    fn sort_excel_numeric_numeric_columns(&self, lines: &mut [Vec<Cow<str>>], wtr: &mut Writer) {
-        let c1 = self.col_at(0);
-        let c2 = self.col_at(1);
-
-        let mut r = lines
-            .par_iter()
-            .map(|i| {
-                (
-                    i,
-                    i[c1].parse::<f64>().unwrap_or_default(),
-                    i[c2].parse::<f64>().unwrap_or_default(),
-                )
-            })
-            .collect::<Vec<_>>();
-
-        match (self.ascending_at(0), self.ascending_at(1)) {
-            (true, true) => r.sort_by(|&a, &b| {
-                a.1.partial_cmp(&b.1)
-                    .unwrap()
-                    .then(a.2.partial_cmp(&b.2).unwrap())
-            }),
-            (true, false) => r.sort_by(|&a, &b| {
-                a.1.partial_cmp(&b.1)
-                    .unwrap()
-                    .then(b.2.partial_cmp(&a.2).unwrap())
-            }),
-            (false, true) => r.sort_by(|&a, &b| {
-                b.1.partial_cmp(&a.1)
-                    .unwrap()
-                    .then(a.2.partial_cmp(&b.2).unwrap())
-            }),
-            (false, false) => r.sort_by(|&a, &b| {
-                b.1.partial_cmp(&a.1)
-                    .unwrap()
-                    .then(b.2.partial_cmp(&a.2).unwrap())
-            }),
+#[cfg(test)]
+mod tests {
+    use super::{SortColumns, natural_cmp};
+    use crate::utils::{column::ColumnResolver, error::RsvError};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_sort_columns_syntax() {
+        let names = ["id", "amount"].map(String::from).to_vec();
+        let mut resolver = ColumnResolver::from_names(names);
+        assert!(SortColumns::from("0DN,amountNL", &mut resolver).is_ok());
+        for spec in ["0N>", "0X", "N", "0NV", "1FL"] {
+            match SortColumns::from(spec, &mut resolver) {
+                Err(RsvError::InvalidArgument(e)) => assert!(e.contains("D (descending)"), "{e}"),
+                r => panic!("<{spec}> should be a syntax error, got {:?}", r.err()),
+            }
         }
+    }
 
-        r.iter().for_each(|(l, _, _)| wtr.write_fields_unchecked(l));
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+        assert_eq!(natural_cmp("file02", "file2"), Ordering::Equal);
+        assert_eq!(natural_cmp("a1b2", "a1b10"), Ordering::Less);
+        assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+        assert_eq!(natural_cmp("v1", "v1.1"), Ordering::Less);
     }
 }
@@ -0,0 +1,349 @@
+use super::{
+    excel::{cell_to_str, write_csv_field},
+    file::{is_parquet, is_sheet},
+    filename::{new_file, new_path},
+    parquet::ParquetRows,
+    reader::{ExcelReader, RecordReader, Task},
+    row_split::CsvRowSplitter,
+    util::datetime_str,
+};
+use crate::utils::compress;
+use crate::utils::encoding::{self, TextEncoding};
+use crate::utils::error::RsvResult;
+use calamine::{Data, Range};
+use crossbeam_channel::Sender;
+use encoding_rs::UTF_8;
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// A source of records, i.e., a CSV or TXT file, stdin, a Parquet file, or an EXCEL
+/// sheet (JSON files included), so that a command is written once for all inputs.
+/// Records are yielded as lines, whose fields are split by the sep and quote of the
+/// source. Cells of a sheet are formatted as comma-separated CSV fields, quoted as
+/// in RFC 4180, and are yielded as they are by next_row.
+pub trait RecordSource: Send {
+    fn sep(&self) -> char;
+
+    fn quote(&self) -> char;
+
+    fn next_line(&mut self) -> Option<io::Result<String>>;
+
+    /// Keep records read from now on, so that the source can be rewound. Stdin is
+    /// spilled to a temporary file rather than kept in memory, while files are
+    /// opened again.
+    fn keep_for_rewind(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Read the source again from the start, e.g., for a second pass.
+    fn rewind(&mut self) -> io::Result<()>;
+
+    fn split<'a>(&self, line: &'a str) -> CsvRowSplitter<'a> {
+        CsvRowSplitter::new(line, self.sep(), self.quote())
+    }
+
+    fn next_row(&mut self) -> Option<io::Result<Vec<String>>> {
+        let l = self.next_line()?;
+        Some(l.map(|l| self.split(&l).collect_owned()))
+    }
+
+    /// Up to n rows, and an empty chunk at the end of the source.
+    fn next_chunk(&mut self, n: usize) -> io::Result<Vec<Vec<String>>> {
+        let mut rows = Vec::with_capacity(n);
+        while rows.len() < n {
+            match self.next_row() {
+                Some(r) => rows.push(r?),
+                None => break,
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Send lines in chunks of n to a channel, so that chunks are processed in
+    /// parallel while the source is read in a thread. A read error stops the
+    /// reading and is returned, and the reading stops when the receiver is dropped.
+    fn send_to_channel_by_chunks(&mut self, tx: Sender<Task>, n: usize) -> io::Result<()> {
+        let mut lines = Vec::with_capacity(n);
+        let mut bytes = 0;
+        let mut chunk = 1;
+
+        while let Some(l) = self.next_line() {
+            let l = l?;
+            bytes += l.len();
+            lines.push(l);
+            if lines.len() >= n {
+                let lines = std::mem::replace(&mut lines, Vec::with_capacity(n));
+                if tx
+                    .send(Task {
+                        lines,
+                        bytes,
+                        chunk,
+                    })
+                    .is_err()
+                {
+                    return Ok(());
+                }
+                bytes = 0;
+                chunk += 1;
+            }
+        }
+
+        if !lines.is_empty() {
+            let _ = tx.send(Task {
+                lines,
+                bytes,
+                chunk,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// where records of a CSV source come from, so that they can be read again
+enum Origin {
+    File(PathBuf, TextEncoding),
+    Stdin,
+    // stdin copied to a temporary file as it is read
+    Spilled(PathBuf, Option<BufWriter<File>>),
+}
+
+pub struct CsvSource {
+    rdr: RecordReader<BufReader<Box<dyn io::Read + Send>>>,
+    sep: char,
+    quote: char,
+    origin: Origin,
+}
+
+impl CsvSource {
+    pub fn open(path: &Path, sep: char, quote: char, enc: TextEncoding) -> io::Result<Self> {
+        let rdr = BufReader::new(compress::open(path, enc)?);
+        Ok(CsvSource {
+            rdr: RecordReader::new(rdr, sep, quote),
+            sep,
+            quote,
+            origin: Origin::File(path.to_owned(), enc),
+        })
+    }
+
+    pub fn stdin(sep: char, quote: char, enc: TextEncoding) -> io::Result<Self> {
+        Ok(CsvSource {
+            rdr: RecordReader::new(encoding::stdin_reader(enc)?, sep, quote),
+            sep,
+            quote,
+            origin: Origin::Stdin,
+        })
+    }
+}
+
+impl RecordSource for CsvSource {
+    fn sep(&self) -> char {
+        self.sep
+    }
+
+    fn quote(&self) -> char {
+        self.quote
+    }
+
+    fn next_line(&mut self) -> Option<io::Result<String>> {
+        let l = self.rdr.next_record();
+        if let (Some(Ok(l)), Origin::Spilled(_, Some(wtr))) = (&l, &mut self.origin)
+            && let Err(e) = writeln!(wtr, "{l}")
+        {
+            return Some(Err(e));
+        }
+
+        l
+    }
+
+    fn keep_for_rewind(&mut self) -> io::Result<()> {
+        if let Origin::Stdin = self.origin {
+            let name = format!("rsv-stdin-{}-{}.csv", datetime_str(), process::id());
+            let path = env::temp_dir().join(name);
+            let wtr = BufWriter::new(File::create(&path)?);
+            self.origin = Origin::Spilled(path, Some(wtr));
+        }
+
+        Ok(())
+    }
+
+    fn rewind(&mut self) -> io::Result<()> {
+        // the rest of stdin is spilled before it is read again
+        if let Origin::Spilled(_, Some(_)) = self.origin {
+            while let Some(l) = self.next_line() {
+                l?;
+            }
+        }
+
+        let (path, enc) = match &mut self.origin {
+            Origin::File(p, enc) => (p.clone(), *enc),
+            Origin::Spilled(p, wtr) => {
+                if let Some(mut wtr) = wtr.take() {
+                    wtr.flush()?;
+                }
+                (p.clone(), TextEncoding::Fixed(UTF_8))
+            }
+            Origin::Stdin => return Err(io::Error::other("stdin is not kept for a second pass")),
+        };
+        let rdr = BufReader::new(compress::open(&path, enc)?);
+        self.rdr = RecordReader::new(rdr, self.sep, self.quote);
+
+        Ok(())
+    }
+}
+
+impl Drop for CsvSource {
+    fn drop(&mut self) {
+        if let Origin::Spilled(p, _) = &self.origin {
+            let _ = fs::remove_file(p);
+        }
+    }
+}
+
+pub struct SheetSource {
+    range: Range<Data>,
+    row: usize,
+}
+
+impl SheetSource {
+    pub fn open(path: &Path, sheet: usize) -> RsvResult<Self> {
+        let range = ExcelReader::new(path, sheet)?.into_range();
+        Ok(SheetSource { range, row: 0 })
+    }
+}
+
+impl RecordSource for SheetSource {
+    fn sep(&self) -> char {
+        ','
+    }
+
+    fn quote(&self) -> char {
+        '"'
+    }
+
+    fn next_line(&mut self) -> Option<io::Result<String>> {
+        // cells are got by position, as iterating rows from the start for each
+        // row takes quadratic time
+        let (height, width) = self.range.get_size();
+        if self.row >= height {
+            return None;
+        }
+
+        let row = self.row;
+        self.row += 1;
+
+        let cells = (0..width).map(|c| self.range.get((row, c)).unwrap_or(&Data::Empty));
+        Some(cells_to_line(cells))
+    }
+
+    // cells are plain text, so rows are not formatted as CSV and split again
+    fn next_row(&mut self) -> Option<io::Result<Vec<String>>> {
+        let (height, width) = self.range.get_size();
+        if self.row >= height {
+            return None;
+        }
+
+        let row = self.row;
+        self.row += 1;
+
+        let cells = (0..width).map(|c| self.range.get((row, c)).unwrap_or(&Data::Empty));
+        Some(Ok(cells.map(|d| cell_to_str(d).into_owned()).collect()))
+    }
+
+    fn rewind(&mut self) -> io::Result<()> {
+        self.row = 0;
+        Ok(())
+    }
+}
+
+/// A Parquet file read row group by row group, see ParquetRows.
+pub struct ParquetSource {
+    path: PathBuf,
+    rows: ParquetRows,
+}
+
+impl ParquetSource {
+    pub fn open(path: &Path) -> RsvResult<Self> {
+        let rows = ParquetRows::open(path)?;
+        Ok(ParquetSource {
+            path: path.to_owned(),
+            rows,
+        })
+    }
+}
+
+impl RecordSource for ParquetSource {
+    fn sep(&self) -> char {
+        ','
+    }
+
+    fn quote(&self) -> char {
+        '"'
+    }
+
+    fn next_line(&mut self) -> Option<io::Result<String>> {
+        match self.rows.next()? {
+            Ok(r) => Some(cells_to_line(r.iter())),
+            Err(e) => Some(Err(io::Error::other(e))),
+        }
+    }
+
+    fn next_row(&mut self) -> Option<io::Result<Vec<String>>> {
+        match self.rows.next()? {
+            Ok(r) => Some(Ok(r.iter().map(|d| cell_to_str(d).into_owned()).collect())),
+            Err(e) => Some(Err(io::Error::other(e))),
+        }
+    }
+
+    fn rewind(&mut self) -> io::Result<()> {
+        self.rows = ParquetRows::open(&self.path).map_err(io::Error::other)?;
+        Ok(())
+    }
+}
+
+fn cells_to_line<'a>(cells: impl Iterator<Item = &'a Data>) -> io::Result<String> {
+    let mut l = vec![];
+    for (c, f) in cells.enumerate() {
+        if c > 0 {
+            l.push(b',');
+        }
+        write_csv_field(&mut l, f)?;
+    }
+
+    String::from_utf8(l).map_err(io::Error::other)
+}
+
+/// Open a file as a record source, or stdin when no file is given. CSV and TXT
+/// files and stdin are transcoded to UTF-8 by the encoding.
+pub fn open(
+    path: Option<&Path>,
+    sep: char,
+    quote: char,
+    sheet: usize,
+    enc: TextEncoding,
+) -> RsvResult<Box<dyn RecordSource>> {
+    let src = match path {
+        Some(p) if is_parquet(p) => Box::new(ParquetSource::open(p)?) as Box<dyn RecordSource>,
+        Some(p) if is_sheet(p) => Box::new(SheetSource::open(p, sheet)?) as Box<dyn RecordSource>,
+        Some(p) => Box::new(CsvSource::open(p, sep, quote, enc)?),
+        None => Box::new(CsvSource::stdin(sep, quote, enc)?),
+    };
+
+    Ok(src)
+}
+
+/// Path of an exported file, e.g., data-head.csv for data.xlsx with the name head,
+/// or head.csv in the current directory for stdin.
+pub fn export_path(path: Option<&Path>, name: &str) -> PathBuf {
+    match path {
+        Some(p) if is_sheet(p) => new_path(p, &format!("-{name}")).with_extension("csv"),
+        Some(p) => new_path(p, &format!("-{name}")),
+        None => new_file(&format!("{name}.csv")),
+    }
+}
@@ -1,49 +1,109 @@
 use super::date_format_infer::DateSmartParser;
-use super::{cli_result::CliResult, filename::new_file};
+use super::{
+    cli_result::CliResult, compress::Codec, filename::new_file, json::JsonWriter,
+    parquet::ParquetWriter,
+};
+use crate::utils::column::Columns;
 use crate::utils::column_type::{ColumnType, ColumnTypes};
+use crate::utils::error::RsvResult;
 use rust_xlsxwriter::*;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader, BufWriter, Write, stdin},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 pub fn is_file_suffix(f: &str) -> bool {
-    f == "csv" || f == "txt" || f == "tsv" || f == "xlsx" || f == "xls"
+    matches!(
+        strip_compression_suffix(f),
+        "csv" | "txt" | "tsv" | "xlsx" | "xls" | "parquet" | "json" | "ndjson" | "jsonl"
+    )
 }
 
+// plain text outputs can be compressed, e.g., out.csv.gz or csv.zst
 pub fn is_valid_plain_text(f: &str) -> bool {
+    let f = strip_compression_suffix(f);
     f.ends_with("csv") || f.ends_with("txt") || f.ends_with("tsv")
 }
 
+/// File name without the compression extension, e.g., out.csv for out.csv.gz.
+pub fn strip_compression_suffix(f: &str) -> &str {
+    match f.rsplit_once('.') {
+        Some((s, e)) if e.parse::<Codec>().is_ok() => s,
+        _ => f,
+    }
+}
+
 pub fn is_valid_excel(f: &str) -> bool {
     f.ends_with("xlsx") || f.ends_with("xls")
 }
 
-pub fn csv_or_io_to_csv(path: Option<&Path>, out: &str) -> CliResult {
-    // out path
-    let out = out_filename(out);
+pub fn is_valid_parquet(f: &str) -> bool {
+    f.ends_with("parquet")
+}
 
-    // rdr and wtr
-    let mut rdr = match path {
-        Some(f) => Box::new(BufReader::new(File::open(f)?)) as Box<dyn BufRead>,
-        None => Box::new(BufReader::new(stdin())) as Box<dyn BufRead>,
-    };
-    let mut wtr = BufWriter::new(File::create(&out)?);
+pub fn is_valid_json(f: &str) -> bool {
+    f.ends_with("json") || f.ends_with("jsonl")
+}
 
-    // copy
-    let mut buf = vec![];
-    while let Ok(bytes) = rdr.read_until(b'\n', &mut buf) {
-        if bytes == 0 {
-            break;
+/// A writer of formats with typed values, i.e., Parquet and JSON, the types of
+/// which are picked from inferred column types.
+pub enum TypedWriter {
+    Parquet(Box<ParquetWriter>),
+    Json(JsonWriter),
+}
+
+impl TypedWriter {
+    pub fn new(
+        out: &Path,
+        names: &[String],
+        ctypes: &ColumnTypes,
+        date_columns: &[usize],
+        date_formats: &[String],
+    ) -> RsvResult<Self> {
+        let f = out.to_string_lossy().to_lowercase();
+        let wtr = match is_valid_parquet(&f) {
+            true => {
+                let w = ParquetWriter::new(out, names, ctypes, date_columns, date_formats)?;
+                TypedWriter::Parquet(Box::new(w))
+            }
+            // one object per line for ndjson and jsonl
+            false => TypedWriter::Json(JsonWriter::new(
+                out,
+                names,
+                ctypes,
+                f.ends_with("ndjson") || f.ends_with("jsonl"),
+            )?),
+        };
+
+        Ok(wtr)
+    }
+
+    pub fn write_row<T: AsRef<str>>(&mut self, row: &[T]) -> CliResult {
+        match self {
+            TypedWriter::Parquet(w) => w.write_row(row),
+            TypedWriter::Json(w) => w.write_row(row),
         }
-        wtr.write_all(&buf[..bytes])?;
-        buf.clear();
     }
 
-    println!("Saved to file: {}", out.display());
+    pub fn close(self) -> CliResult {
+        match self {
+            TypedWriter::Parquet(w) => w.close(),
+            TypedWriter::Json(w) => w.close(),
+        }
+    }
+}
 
-    Ok(())
+// a single date format applies to all date columns
+pub fn date_format_of<'a>(
+    c: usize,
+    date_columns: &[usize],
+    date_formats: &'a [String],
+) -> Option<&'a String> {
+    match date_formats {
+        [] => None,
+        [fmt] => Some(fmt),
+        _ => date_columns
+            .iter()
+            .position(|&r| r == c)
+            .and_then(|i| date_formats.get(i)),
+    }
 }
 
 pub fn out_filename(out: &str) -> PathBuf {
@@ -57,10 +117,10 @@ pub fn out_filename(out: &str) -> PathBuf {
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn write_excel_line(
+pub fn write_excel_line<T: AsRef<str>>(
     sheet: &mut Worksheet,
     row: usize,
-    line: &[&str],
+    line: &[T],
     ctypes: Option<&ColumnTypes>,
     date_columns: &[usize],
     date_formats: &[String],
@@ -71,25 +131,15 @@ pub fn write_excel_line(
 ) -> CliResult {
     let row = row as u32;
     if let Some(ctypes) = ctypes {
-        for ((c, &v), t) in line.iter().enumerate().zip(ctypes.iter()) {
-            let col = c as u16;
+        for ((c, v), t) in line.iter().enumerate().zip(ctypes.iter()) {
+            let (col, v) = (c as u16, v.as_ref());
             match t.col_type {
                 ColumnType::Float | ColumnType::Int => match v.parse::<f64>() {
                     Ok(v) => sheet.write(row, col, v)?,
                     Err(_) => sheet.write(row, col, v)?,
                 },
                 ColumnType::Date => {
-                    let assigned_fmt = match date_formats {
-                        [] => None,
-                        [fmt] => Some(fmt),
-                        _ => {
-                            if let Some(i) = date_columns.iter().position(|&r| r == c) {
-                                date_formats.get(i)
-                            } else {
-                                None
-                            }
-                        }
-                    };
+                    let assigned_fmt = date_format_of(c, date_columns, date_formats);
                     if let Some(dt) = parser.smart_parse(v, assigned_fmt) {
                         if serial_dates {
                             sheet.write_datetime(row, col, dt)?
@@ -113,10 +163,49 @@ pub fn write_excel_line(
             };
         }
     } else {
-        for (col, &v) in line.iter().enumerate() {
-            sheet.write(row, col as u16, v)?;
+        for (col, v) in line.iter().enumerate() {
+            sheet.write(row, col as u16, v.as_ref())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a header and rows to an excel file, where numeric columns are typed
+/// as numbers.
+pub fn write_excel_rows(out: &Path, header: &[String], rows: &[Vec<String>]) -> CliResult {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.write_row(0, 0, header)?;
+
+    let lines = rows
+        .iter()
+        .map(|r| r.iter().map(|i| i.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if !lines.is_empty() {
+        let cols = Columns::new("").total_col(header.len()).parse()?;
+        let ctypes = ColumnTypes::guess_from_lines(&lines, &cols, &[], &[]);
+        ctypes.update_excel_column_width(sheet)?;
+
+        let parser = DateSmartParser::new();
+        let fmt = Format::new();
+        for (n, l) in lines.iter().enumerate() {
+            write_excel_line(
+                sheet,
+                n + 1,
+                l,
+                Some(&ctypes),
+                &[],
+                &[],
+                false,
+                &parser,
+                &fmt,
+                &fmt,
+            )?;
         }
     }
 
+    workbook.save(out)?;
+
     Ok(())
 }
@@ -0,0 +1,107 @@
+use super::constants::MB_USIZE;
+
+// heap and String overhead of a field kept in memory
+const FIELD_OVERHEAD: f64 = 32.0;
+
+/// Output rows of a transposed file for the input columns from start to end,
+/// or to the widest row when end is not given. A short row is padded with
+/// empty fields, and the parts of chunks can be merged in order.
+pub struct Transposer {
+    start: usize,
+    end: Option<usize>,
+    n: usize,
+    rows: Vec<Vec<String>>,
+}
+
+impl Transposer {
+    pub fn new(start: usize, end: Option<usize>) -> Self {
+        let rows = match end {
+            Some(e) => vec![vec![]; e.saturating_sub(start)],
+            None => vec![],
+        };
+
+        Transposer {
+            start,
+            end,
+            n: 0,
+            rows,
+        }
+    }
+
+    /// A Transposer of the same columns but no data, to hold a part of a chunk.
+    pub fn empty(&self) -> Self {
+        Transposer::new(self.start, self.end)
+    }
+
+    fn pad_to(&mut self, len: usize) {
+        while self.rows.len() < len {
+            self.rows.push(vec![String::new(); self.n]);
+        }
+    }
+
+    pub fn add_fields<T: AsRef<str>>(&mut self, v: &[T]) {
+        if self.end.is_none() {
+            self.pad_to(v.len().saturating_sub(self.start));
+        }
+
+        for (c, r) in (self.start..).zip(self.rows.iter_mut()) {
+            r.push(v.get(c).map(|f| f.as_ref().to_owned()).unwrap_or_default());
+        }
+        self.n += 1;
+    }
+
+    /// Append the rows of a later part of the file.
+    pub fn merge(&mut self, o: Transposer) {
+        self.pad_to(o.rows.len());
+
+        let mut rows = o.rows.into_iter();
+        for r in self.rows.iter_mut() {
+            match rows.next() {
+                Some(o) => r.extend(o),
+                None => r.extend(vec![String::new(); o.n]),
+            }
+        }
+        self.n += o.n;
+    }
+
+    pub fn into_rows(self) -> Vec<Vec<String>> {
+        self.rows
+    }
+}
+
+/// Number of input columns to transpose in a pass so that the memory stays
+/// within max_mb, estimated from the file size and the row size.
+pub fn columns_per_pass(file_bytes: u64, row_bytes: f64, width: usize, max_mb: usize) -> usize {
+    let rows = file_bytes as f64 / row_bytes.max(1.0);
+    let column_bytes = (file_bytes as f64 + rows * width as f64 * FIELD_OVERHEAD) / width as f64;
+    let n = (max_mb * MB_USIZE) as f64 / column_bytes.max(1.0);
+
+    (n as usize).clamp(1, width.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transposer() {
+        let mut t = Transposer::new(0, None);
+        let mut part = t.empty();
+        t.add_fields(&["a", "b"]);
+        part.add_fields(&["1", "2", "3"]);
+        t.merge(part);
+        assert_eq!(t.into_rows(), [["a", "1"], ["b", "2"], ["", "3"]]);
+
+        // the second column only
+        let mut t = Transposer::new(1, Some(2));
+        t.add_fields(&["a", "b"]);
+        t.add_fields(&["1"]);
+        assert_eq!(t.into_rows(), [["b", ""]]);
+    }
+
+    #[test]
+    fn test_columns_per_pass() {
+        assert_eq!(columns_per_pass(1_000, 10.0, 5, 100), 5);
+        assert_eq!(columns_per_pass(1_000_000_000, 1_000.0, 100, 100), 2);
+    }
+}
@@ -0,0 +1,65 @@
+use ahash::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Unique rows by keys, where the first or the last row of duplicates is kept.
+/// A first row is returned as soon as it is pushed, while last rows are held
+/// with their positions, so that the input is read only once.
+pub struct UniqueRows<K, T> {
+    keep_last: bool,
+    seen: HashSet<K>,
+    last: HashMap<K, (usize, T)>,
+    n: usize,
+}
+
+impl<K: Hash + Eq, T> UniqueRows<K, T> {
+    pub fn new(keep_last: bool) -> Self {
+        UniqueRows {
+            keep_last,
+            seen: HashSet::default(),
+            last: HashMap::default(),
+            n: 0,
+        }
+    }
+
+    /// Add a row of a key, and return it when it is the first row of the key
+    /// and first rows are kept.
+    pub fn push(&mut self, key: K, row: T) -> Option<T> {
+        self.n += 1;
+        if self.keep_last {
+            self.last.insert(key, (self.n, row));
+            return None;
+        }
+
+        self.seen.insert(key).then_some(row)
+    }
+
+    /// Last rows of keys in the order of their positions, or none when first
+    /// rows are kept.
+    pub fn finish(self) -> Vec<T> {
+        let mut rows = self.last.into_values().collect::<Vec<_>>();
+        rows.sort_unstable_by_key(|(i, _)| *i);
+        rows.into_iter().map(|(_, r)| r).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_rows() {
+        let rows = [("a", 1), ("b", 2), ("a", 3)];
+
+        let mut u = UniqueRows::new(false);
+        let first = rows
+            .iter()
+            .filter_map(|&(k, r)| u.push(k, r))
+            .collect::<Vec<_>>();
+        assert_eq!(first, [1, 2]);
+
+        let mut u = UniqueRows::new(true);
+        rows.iter()
+            .for_each(|&(k, r)| assert_eq!(u.push(k, r), None));
+        assert_eq!(u.finish(), [2, 3]);
+    }
+}
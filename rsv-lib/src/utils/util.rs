@@ -1,4 +1,6 @@
-use std::io::{BufWriter, Error, Write, stdout};
+use super::error::{RsvError, RsvResult};
+use chrono::{NaiveDateTime, NaiveTime};
+use std::cmp::Reverse;
 
 pub fn datetime_str() -> String {
     let t = chrono::offset::Local::now();
@@ -10,7 +12,45 @@ pub fn is_null(s: &str) -> bool {
     s.is_empty() || s == "NA" || s == "Na" || s == "na" || s == "NULL" || s == "Null" || s == "null"
 }
 
-pub fn get_valid_sep(sep: &str) -> Result<char, Error> {
+// e.g., 300 rather than 300.0, and 0.3 rather than 0.30000000000000004
+pub fn fmt_f64(v: f64) -> String {
+    let s = format!("{v:.6}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" => "0".to_owned(),
+        _ => s.to_owned(),
+    }
+}
+
+// e.g., 2024-01-02, or 2024-01-02 08:30:00 when the time is not midnight
+pub fn fmt_datetime(d: &NaiveDateTime) -> String {
+    match d.time() == NaiveTime::MIN {
+        true => d.format("%Y-%m-%d").to_string(),
+        false => d.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+/// A ratio in (0, 1], e.g., the confidence of type inference.
+pub fn get_valid_ratio(s: &str) -> Result<f64, RsvError> {
+    match s.parse::<f64>() {
+        Ok(v) if v > 0.0 && v <= 1.0 => Ok(v),
+        _ => Err(RsvError::InvalidArgument(format!(
+            "<{s}> should be a ratio greater than 0 and no more than 1."
+        ))),
+    }
+}
+
+/// A percentile in [0, 100], e.g., 25 for the first quartile.
+pub fn get_valid_percentile(s: &str) -> Result<f64, RsvError> {
+    match s.parse::<f64>() {
+        Ok(v) if (0.0..=100.0).contains(&v) => Ok(v),
+        _ => Err(RsvError::InvalidArgument(format!(
+            "<{s}> should be a percentile from 0 to 100."
+        ))),
+    }
+}
+
+pub fn get_valid_sep(sep: &str) -> Result<char, RsvError> {
     let cleaned_sep = sep.replace("\"", "").replace("'", "");
 
     if cleaned_sep == "\\t" || cleaned_sep == "t" {
@@ -20,33 +60,44 @@ pub fn get_valid_sep(sep: &str) -> Result<char, Error> {
     } else if cleaned_sep.len() == 1 {
         Ok(cleaned_sep.chars().next().unwrap())
     } else {
-        werr_exit!("cannot parse separator <{}>.", sep);
+        Err(RsvError::InvalidArgument(format!(
+            "cannot parse separator <{sep}>."
+        )))
     }
 }
 
-/// early return when pipeline closed
-pub fn print_frequency_table(names: &[String], freq: Vec<(String, usize)>) {
-    let mut wtr = BufWriter::new(stdout());
-
-    if writeln!(wtr, "{}", names.join(",")).is_err() {
-        return;
-    };
+/// [start, end) of a slice by an index, or by a start with an end or a length.
+pub fn slice_range(
+    start: usize,
+    end: Option<usize>,
+    length: Option<usize>,
+    index: Option<usize>,
+) -> RsvResult<(usize, usize)> {
+    if let Some(i) = index {
+        return Ok((i, i + 1));
+    }
 
-    for (k, n) in &freq {
-        if writeln!(wtr, "{k},{n}").is_err() {
-            return;
-        }
+    let end = end
+        .or_else(|| length.map(|l| start + l))
+        .unwrap_or(usize::MAX);
+    if start > end {
+        return Err(RsvError::InvalidArgument(
+            "end index should be equal to or bigger than start index.".to_owned(),
+        ));
     }
-}
 
-#[macro_export]
-macro_rules! werr_exit {
-    ($($arg:tt)*) => ({
-        use std::io::Write;
-        use std::process;
-        (writeln!(&mut ::std::io::stderr(), $($arg)*)).unwrap();
-        process::exit(1);
-    });
+    Ok((start, end))
 }
 
-pub use werr_exit;
+/// Sort a frequency table by counts, where ties are ordered by values so that
+/// the order is deterministic, and keep the first n values when n is given.
+pub fn sort_frequency<K: Ord>(freq: &mut Vec<(K, usize)>, ascending: bool, n: Option<usize>) {
+    match ascending {
+        true => freq.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0))),
+        false => freq.sort_by(|a, b| Reverse(a.1).cmp(&Reverse(b.1)).then_with(|| a.0.cmp(&b.0))),
+    }
+
+    if let Some(n) = n {
+        freq.truncate(n)
+    }
+}
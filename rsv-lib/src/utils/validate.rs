@@ -0,0 +1,457 @@
+use super::{
+    date_format_infer::DateSmartParser,
+    error::RsvResult,
+    reader::QuoteState,
+    row_split::CsvRowSplitter,
+    schema::{Schema, Violation},
+};
+use ahash::HashSet;
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
+
+/// Problems of a CSV or TXT file that are found by validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IssueKind {
+    Bom,
+    InvalidUtf8,
+    EmptyHeader,
+    DuplicateHeader,
+    FieldCount,
+    UnbalancedQuote,
+    MixedLineEndings,
+    TrailingWhitespace,
+    // violations of a schema
+    MissingColumn,
+    Null,
+    Type,
+    Value,
+    Range,
+    Pattern,
+}
+
+impl IssueKind {
+    // a row with the problem cannot be loaded as it is, so it is rejected
+    fn is_bad_row(&self) -> bool {
+        matches!(
+            self,
+            IssueKind::InvalidUtf8
+                | IssueKind::FieldCount
+                | IssueKind::UnbalancedQuote
+                | IssueKind::Null
+                | IssueKind::Type
+                | IssueKind::Value
+                | IssueKind::Range
+                | IssueKind::Pattern
+        )
+    }
+}
+
+impl Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            IssueKind::Bom => "bom",
+            IssueKind::InvalidUtf8 => "invalid-utf8",
+            IssueKind::EmptyHeader => "empty-header",
+            IssueKind::DuplicateHeader => "duplicate-header",
+            IssueKind::FieldCount => "field-count",
+            IssueKind::UnbalancedQuote => "unbalanced-quote",
+            IssueKind::MixedLineEndings => "mixed-line-endings",
+            IssueKind::TrailingWhitespace => "trailing-whitespace",
+            IssueKind::MissingColumn => "missing-column",
+            IssueKind::Null => "null",
+            IssueKind::Type => "type",
+            IssueKind::Value => "value",
+            IssueKind::Range => "range",
+            IssueKind::Pattern => "pattern",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug)]
+pub struct Issue {
+    /// Line number in the file, starting from 1.
+    pub line: usize,
+    pub kind: IssueKind,
+    pub detail: String,
+}
+
+impl From<Violation> for IssueKind {
+    fn from(v: Violation) -> Self {
+        match v {
+            Violation::Null => IssueKind::Null,
+            Violation::Type => IssueKind::Type,
+            Violation::Value => IssueKind::Value,
+            Violation::Range => IssueKind::Range,
+            Violation::Pattern => IssueKind::Pattern,
+        }
+    }
+}
+
+impl Issue {
+    fn new(line: usize, kind: IssueKind, detail: String) -> Self {
+        Issue { line, kind, detail }
+    }
+}
+
+/// Numbers of rows and problems of a file.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub rows: usize,
+    pub counts: BTreeMap<IssueKind, usize>,
+}
+
+impl Report {
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts = self
+            .counts
+            .iter()
+            .map(|(k, n)| format!("{n} {k}"))
+            .collect::<Vec<_>>();
+        match self.total() {
+            0 => write!(f, "{} rows, no problem found.", self.rows),
+            n => write!(
+                f,
+                "{} rows, {n} problems found: {}.",
+                self.rows,
+                counts.join(", ")
+            ),
+        }
+    }
+}
+
+/// Check the raw bytes of a CSV or TXT file record by record, where a quoted
+/// field may span several lines. Values can be checked against a schema, and
+/// bad rows can be written to a reject file, along with the header.
+pub struct Validator {
+    sep: char,
+    quote: char,
+    no_header: bool,
+    reject: Option<Box<dyn Write>>,
+    schema: Option<Schema>,
+}
+
+// a physical line, i.e., the bytes up to and including \n
+struct Line<'a> {
+    content: &'a [u8],
+    ending: Option<&'static str>,
+}
+
+impl<'a> Line<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        match bytes {
+            [c @ .., b'\r', b'\n'] => Line {
+                content: c,
+                ending: Some("\\r\\n"),
+            },
+            [c @ .., b'\n'] => Line {
+                content: c,
+                ending: Some("\\n"),
+            },
+            c => Line {
+                content: c,
+                ending: None,
+            },
+        }
+    }
+}
+
+impl Validator {
+    pub fn new(sep: char, quote: char, no_header: bool) -> Self {
+        Validator {
+            sep,
+            quote,
+            no_header,
+            reject: None,
+            schema: None,
+        }
+    }
+
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn reject(mut self, wtr: Box<dyn Write>) -> Self {
+        self.reject = Some(wtr);
+        self
+    }
+
+    /// Validate records of a reader, and hand over problems one by one in order
+    /// of lines.
+    pub fn run<R: BufRead>(
+        &mut self,
+        mut rdr: R,
+        mut f: impl FnMut(&Issue) -> io::Result<()>,
+    ) -> RsvResult<Report> {
+        let mut report = Report::default();
+        let mut line_n = 0;
+        let mut first_ending = None;
+        let mut fields = None;
+        let mut buf = vec![];
+        // column index of each field of the schema
+        let mut schema_cols = vec![];
+        let parser = DateSmartParser::new();
+
+        loop {
+            // a record of one or more lines
+            let start = line_n + 1;
+            let mut record = vec![];
+            let mut issues = vec![];
+            let mut state = QuoteState::FieldStart;
+            loop {
+                buf.clear();
+                if rdr.read_until(b'\n', &mut buf)? == 0 {
+                    break;
+                }
+                line_n += 1;
+
+                let mut bytes = &buf[..];
+                if line_n == 1 {
+                    bytes = self.check_bom(bytes, &mut issues);
+                }
+
+                let line = Line::new(bytes);
+                match (first_ending, line.ending) {
+                    (None, e) => first_ending = e,
+                    (Some(a), Some(b)) if a != b => issues.push(Issue::new(
+                        line_n,
+                        IssueKind::MixedLineEndings,
+                        format!("line ends with {b}, while line 1 ends with {a}"),
+                    )),
+                    _ => {}
+                }
+
+                let text = match std::str::from_utf8(line.content) {
+                    Ok(s) => Cow::Borrowed(s),
+                    Err(e) => {
+                        issues.push(Issue::new(
+                            line_n,
+                            IssueKind::InvalidUtf8,
+                            format!("invalid byte at position {}", e.valid_up_to()),
+                        ));
+                        String::from_utf8_lossy(line.content)
+                    }
+                };
+                record.extend_from_slice(bytes);
+                state = state.after(&text, self.sep, self.quote);
+                if !state.in_quoted_field() {
+                    break;
+                }
+            }
+
+            if record.is_empty() {
+                break;
+            }
+            if state.in_quoted_field() {
+                issues.push(Issue::new(
+                    start,
+                    IssueKind::UnbalancedQuote,
+                    "quoted field is not closed until the end of file".to_owned(),
+                ));
+            }
+
+            let text = String::from_utf8_lossy(Line::new(&record).content);
+            let row = CsvRowSplitter::new(&text, self.sep, self.quote).collect::<Vec<_>>();
+            check_trailing_whitespace(&row, start, &mut issues);
+            let is_header = fields.is_none() && !self.no_header;
+            if fields.is_none() {
+                schema_cols = self.check_schema_columns(&row, &mut issues);
+            }
+            match fields {
+                None => fields = Some(row.len()),
+                Some(n) if n != row.len() => issues.push(Issue::new(
+                    start,
+                    IssueKind::FieldCount,
+                    format!("{} fields, while the first row has {n}", row.len()),
+                )),
+                _ => {}
+            }
+
+            if is_header {
+                check_header(&row, &mut issues);
+                self.write_reject(&record)?;
+            } else {
+                self.check_values(&row, &schema_cols, &parser, start, &mut issues);
+                report.rows += 1;
+                if issues.iter().any(|i| i.kind.is_bad_row()) {
+                    self.write_reject(&record)?;
+                }
+            }
+
+            issues.sort_by_key(|i| i.line);
+            for i in &issues {
+                *report.counts.entry(i.kind).or_default() += 1;
+                f(i)?;
+            }
+        }
+
+        if let Some(wtr) = &mut self.reject {
+            wtr.flush()?;
+        }
+
+        Ok(report)
+    }
+
+    fn check_bom<'a>(&self, bytes: &'a [u8], issues: &mut Vec<Issue>) -> &'a [u8] {
+        let (bom, name): (&[u8], _) = match bytes {
+            [0xef, 0xbb, 0xbf, ..] => (&[0xef, 0xbb, 0xbf], "UTF-8"),
+            [0xff, 0xfe, ..] => (&[0xff, 0xfe], "UTF-16LE"),
+            [0xfe, 0xff, ..] => (&[0xfe, 0xff], "UTF-16BE"),
+            _ => return bytes,
+        };
+
+        issues.push(Issue::new(
+            1,
+            IssueKind::Bom,
+            format!("file starts with a {name} BOM"),
+        ));
+        &bytes[bom.len()..]
+    }
+
+    fn check_schema_columns<T: AsRef<str>>(
+        &self,
+        row: &[T],
+        issues: &mut Vec<Issue>,
+    ) -> Vec<Option<usize>> {
+        let Some(schema) = &self.schema else {
+            return vec![];
+        };
+
+        let cols = schema.resolve(row, self.no_header);
+        for (c, f) in cols.iter().zip(&schema.fields) {
+            if c.is_none() {
+                issues.push(Issue::new(
+                    1,
+                    IssueKind::MissingColumn,
+                    format!("column <{}> of the schema is not found", f.name),
+                ));
+            }
+        }
+
+        cols
+    }
+
+    fn check_values<T: AsRef<str>>(
+        &self,
+        row: &[T],
+        cols: &[Option<usize>],
+        parser: &DateSmartParser,
+        line: usize,
+        issues: &mut Vec<Issue>,
+    ) {
+        let Some(schema) = &self.schema else {
+            return;
+        };
+
+        for (c, f) in cols.iter().zip(&schema.fields) {
+            let Some(v) = c.and_then(|c| row.get(c)) else {
+                continue;
+            };
+            if let Some((v, detail)) = f.check(v.as_ref(), parser) {
+                issues.push(Issue::new(line, v.into(), detail));
+            }
+        }
+    }
+
+    fn write_reject(&mut self, record: &[u8]) -> io::Result<()> {
+        let Some(wtr) = &mut self.reject else {
+            return Ok(());
+        };
+
+        wtr.write_all(record)?;
+        if !record.ends_with(b"\n") {
+            wtr.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_trailing_whitespace<T: AsRef<str>>(row: &[T], line: usize, issues: &mut Vec<Issue>) {
+    for (i, v) in row.iter().enumerate() {
+        if v.as_ref().ends_with([' ', '\t']) {
+            issues.push(Issue::new(
+                line,
+                IssueKind::TrailingWhitespace,
+                format!("field {i} ends with whitespace"),
+            ));
+        }
+    }
+}
+
+fn check_header<T: AsRef<str>>(names: &[T], issues: &mut Vec<Issue>) {
+    let mut seen = HashSet::default();
+    for (i, name) in names.iter().enumerate() {
+        let name = name.as_ref().trim();
+        if name.is_empty() {
+            issues.push(Issue::new(
+                1,
+                IssueKind::EmptyHeader,
+                format!("column {i} has an empty name"),
+            ));
+        } else if !seen.insert(name) {
+            issues.push(Issue::new(
+                1,
+                IssueKind::DuplicateHeader,
+                format!("column {i} has a duplicate name <{name}>"),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(data: &str, no_header: bool) -> Vec<(usize, IssueKind)> {
+        let mut r = vec![];
+        Validator::new(',', '"', no_header)
+            .run(data.as_bytes(), |i| {
+                r.push((i.line, i.kind));
+                Ok(())
+            })
+            .unwrap();
+        r
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(kinds("a,b\n1,2\n\"3\n4\",5\n", false).is_empty());
+        assert_eq!(
+            kinds("a,,a\n1,2\n", false),
+            [
+                (1, IssueKind::EmptyHeader),
+                (1, IssueKind::DuplicateHeader),
+                (2, IssueKind::FieldCount)
+            ]
+        );
+        assert_eq!(
+            kinds("a,b\r\n1,2 \n\"3,4\n", true),
+            [
+                (2, IssueKind::MixedLineEndings),
+                (2, IssueKind::TrailingWhitespace),
+                (3, IssueKind::MixedLineEndings),
+                (3, IssueKind::UnbalancedQuote),
+                (3, IssueKind::FieldCount)
+            ]
+        );
+        assert_eq!(
+            kinds("a,b\n1 ,2\n\"3\t\",4\n", false),
+            [
+                (2, IssueKind::TrailingWhitespace),
+                (3, IssueKind::TrailingWhitespace)
+            ]
+        );
+    }
+}
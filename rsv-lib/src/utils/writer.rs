@@ -1,43 +1,227 @@
-use super::{cli_result::CliResult, constants::TERMINATOR};
+use super::{
+    cli_result::CliResult,
+    compress,
+    encoding::{self, TextEncoding},
+    error::RsvError,
+    excel::cell_to_str,
+    row_split::CsvRowSplitter,
+};
 use calamine::Data;
-use chrono::Timelike;
 use std::{
-    fs::{File, OpenOptions},
+    borrow::Cow,
+    fmt::Display,
     io::{BufWriter, Error, Write, stdout},
     path::Path,
     process,
+    str::FromStr,
 };
 
-pub struct Writer(pub Box<dyn Write>);
+/// When output fields are quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// Only fields with a separator, quote char or line break
+    #[default]
+    Minimal,
+    All,
+    /// Fields that do not parse as a number
+    NonNumeric,
+    Never,
+}
+
+impl FromStr for QuoteStyle {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "minimal" => Ok(QuoteStyle::Minimal),
+            "all" => Ok(QuoteStyle::All),
+            "non-numeric" | "nonnumeric" => Ok(QuoteStyle::NonNumeric),
+            "never" => Ok(QuoteStyle::Never),
+            _ => Err(RsvError::InvalidArgument(format!(
+                "unknown quote style <{s}>, can be minimal, all, non-numeric or never."
+            ))),
+        }
+    }
+}
+
+impl Display for QuoteStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            QuoteStyle::Minimal => "minimal",
+            QuoteStyle::All => "all",
+            QuoteStyle::NonNumeric => "non-numeric",
+            QuoteStyle::Never => "never",
+        };
+        f.write_str(s)
+    }
+}
+
+/// How a quote char in a quoted field is escaped, e.g., "a ""b""" or "a \"b\"".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscapeStyle {
+    #[default]
+    Double,
+    Backslash,
+}
+
+impl FromStr for EscapeStyle {
+    type Err = RsvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "double" => Ok(EscapeStyle::Double),
+            "backslash" => Ok(EscapeStyle::Backslash),
+            _ => Err(RsvError::InvalidArgument(format!(
+                "unknown escape style <{s}>, can be double or backslash."
+            ))),
+        }
+    }
+}
+
+impl Display for EscapeStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscapeStyle::Double => f.write_str("double"),
+            EscapeStyle::Backslash => f.write_str("backslash"),
+        }
+    }
+}
 
+/// Output dialect of CSV rows. Raw rows are given as read with the input
+/// separator and quote char, and are written as they are unless the dialect
+/// differs. Fields are plain text, as unescaped by the row splitter, and are
+/// only quoted and escaped by the dialect.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dialect {
+    pub sep: char,
+    pub quote: char,
+    pub quote_style: QuoteStyle,
+    pub escape_style: EscapeStyle,
+    pub crlf: bool,
+    pub in_sep: char,
+    pub in_quote: char,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Dialect {
+            sep: ',',
+            quote: '"',
+            quote_style: QuoteStyle::Minimal,
+            escape_style: EscapeStyle::Double,
+            crlf: false,
+            in_sep: ',',
+            in_quote: '"',
+        }
+    }
+}
+
+impl Dialect {
+    /// The default dialect for rows read with the separator and quote char.
+    pub fn input(sep: char, quote: char) -> Self {
+        Dialect {
+            sep,
+            quote,
+            in_sep: sep,
+            in_quote: quote,
+            ..Default::default()
+        }
+    }
+
+    fn terminator(&self) -> &'static [u8] {
+        match self.crlf {
+            true => b"\r\n",
+            false => b"\n",
+        }
+    }
+
+    // a raw row is kept as it is when it is read in the same dialect
+    fn keeps_rows(&self) -> bool {
+        self.sep == self.in_sep
+            && self.quote == self.in_quote
+            && self.quote_style == QuoteStyle::Minimal
+            && self.escape_style == EscapeStyle::Double
+    }
+
+    fn needs_quote(&self, f: &str, numeric: bool) -> bool {
+        match self.quote_style {
+            QuoteStyle::Minimal => f.contains([self.sep, self.quote, '\n', '\r']),
+            QuoteStyle::All => true,
+            QuoteStyle::NonNumeric => !numeric,
+            QuoteStyle::Never => false,
+        }
+    }
+
+    /// A field of plain text in the dialect, quoted and escaped if needed.
+    pub fn quote_field<'a>(&self, f: &'a str, numeric: bool) -> Cow<'a, str> {
+        if !self.needs_quote(f, numeric) {
+            return Cow::Borrowed(f);
+        }
+
+        let q = self.quote.to_string();
+        let escaped = match self.escape_style {
+            EscapeStyle::Double => f.replace(&q, &q.repeat(2)),
+            EscapeStyle::Backslash => f.replace(&q, &format!("\\{q}")),
+        };
+        Cow::Owned(format!("{q}{escaped}{q}"))
+    }
+}
+
+pub struct Writer(pub Box<dyn Write>, Dialect);
+
+// a file with a compression extension, e.g., .gz, .zst or .bz2, is written compressed
 impl Writer {
     pub fn new(path: &Path) -> Result<Self, Error> {
-        let wtr = Box::new(BufWriter::new(File::create(path)?));
+        let wtr = Box::new(BufWriter::new(compress::create(path)?));
 
-        Ok(Writer(wtr))
+        Ok(Writer(wtr, Dialect::default()))
     }
 
     pub fn file_or_stdout(export: bool, path: &Path) -> Result<Self, Error> {
         let wtr = match export {
-            true => Box::new(BufWriter::new(File::create(path)?)) as Box<dyn Write>,
+            true => Box::new(BufWriter::new(compress::create(path)?)) as Box<dyn Write>,
             false => Box::new(stdout()) as Box<dyn Write>,
         };
 
-        Ok(Writer(wtr))
+        Ok(Writer(wtr, Dialect::default()))
     }
 
     pub fn stdout() -> Result<Self, Error> {
         let wtr = Box::new(stdout()) as Box<dyn Write>;
-        Ok(Writer(wtr))
+        Ok(Writer(wtr, Dialect::default()))
     }
 
     pub fn append_to(out: &Path) -> Result<Self, Error> {
         // open file
-        let f = OpenOptions::new().append(true).create(true).open(out)?;
+        let f = compress::append(out)?;
 
         let wtr = Box::new(BufWriter::new(f));
 
-        Ok(Writer(wtr))
+        Ok(Writer(wtr, Dialect::default()))
+    }
+
+    /// Transcode the output from UTF-8, e.g., to GBK.
+    pub fn encoding(self, enc: TextEncoding) -> Self {
+        Writer(encoding::encode(self.0, enc), self.1)
+    }
+
+    /// Write rows in the dialect, e.g., with another separator or quoting.
+    pub fn dialect(self, dialect: Dialect) -> Self {
+        Writer(self.0, dialect)
+    }
+
+    fn write_field(&mut self, f: &str) -> CliResult {
+        let numeric = f.parse::<f64>().is_ok();
+        self.0
+            .write_all(self.1.quote_field(f, numeric).as_bytes())?;
+        Ok(())
+    }
+
+    fn write_sep(&mut self) -> CliResult {
+        let mut b = [0; 4];
+        self.0
+            .write_all(self.1.sep.encode_utf8(&mut b).as_bytes())?;
+        Ok(())
     }
 
     pub fn write_bytes(&mut self, bytes: &[u8]) -> CliResult {
@@ -64,9 +248,17 @@ impl Writer {
     //     }
     // }
 
+    /// Write a raw row, which is split and written by fields when the
+    /// dialect differs from the input.
     pub fn write_str<T: AsRef<str>>(&mut self, row: T) -> CliResult {
+        if !self.1.keeps_rows() {
+            let r = row.as_ref();
+            let fields = CsvRowSplitter::new(r, self.1.in_sep, self.1.in_quote).collect::<Vec<_>>();
+            return self.write_fields(&fields);
+        }
+
         self.0.write_all(row.as_ref().as_bytes())?;
-        self.0.write_all(TERMINATOR)?;
+        self.0.write_all(self.1.terminator())?;
         Ok(())
     }
 
@@ -92,10 +284,12 @@ impl Writer {
     pub fn write_fields<T: AsRef<str>>(&mut self, line: &[T]) -> CliResult {
         let mut l = line.iter().peekable();
         while let Some(f) = l.next() {
-            self.0.write_all(f.as_ref().as_bytes())?;
-            let sep = if l.peek().is_none() { TERMINATOR } else { b"," };
-            self.0.write_all(sep)?;
+            self.write_field(f.as_ref())?;
+            if l.peek().is_some() {
+                self.write_sep()?;
+            }
         }
+        self.0.write_all(self.1.terminator())?;
 
         Ok(())
     }
@@ -110,29 +304,21 @@ impl Writer {
         &mut self,
         line: &[T],
         cols: &[usize],
-        sep: Option<&[u8]>,
     ) -> CliResult {
         let mut l = cols.iter().peekable();
         while let Some(&i) = l.next() {
-            self.0.write_all(line[i].as_ref().as_bytes())?;
-            let sep = if l.peek().is_none() {
-                TERMINATOR
-            } else {
-                sep.unwrap_or(b",")
-            };
-            self.0.write_all(sep)?;
+            self.write_field(line[i].as_ref())?;
+            if l.peek().is_some() {
+                self.write_sep()?;
+            }
         }
+        self.0.write_all(self.1.terminator())?;
 
         Ok(())
     }
 
-    pub fn write_selected_fields_unchecked<T: AsRef<str>>(
-        &mut self,
-        line: &[T],
-        cols: &[usize],
-        sep: Option<&[u8]>,
-    ) {
-        if self.write_selected_fields(line, cols, sep).is_err() {
+    pub fn write_selected_fields_unchecked<T: AsRef<str>>(&mut self, line: &[T], cols: &[usize]) {
+        if self.write_selected_fields(line, cols).is_err() {
             process::exit(0)
         }
     }
@@ -145,108 +331,40 @@ impl Writer {
         }
     }
 
+    /// Write a cell, where a string is plain text rather than an escaped field.
     pub fn write_excel_field(&mut self, data: &Data) -> CliResult {
-        match data {
-            Data::DateTime(v) => {
-                if let Some(a) = v.as_datetime() {
-                    if a.hour() == 0 && a.minute() == 0 && a.second() == 0 {
-                        write!(&mut self.0, "{}", a.format("%Y-%m-%d"))?
-                    } else {
-                        write!(&mut self.0, "{}", a.format("%Y-%m-%d %H:%M:%S"))?
-                    }
-                }
-            }
-
-            Data::String(v) => {
-                // escape double-quote in Excel field by a \ char, we do not escape
-                // double-quote by appending a preceding double quote as in
-                // https://stackoverflow.com/questions/17808511/how-to-properly-escape-a-double-quote-in-csv
-                // this is to avoid conflict with the start and end double-quotes
-                // for a double-quoted comma-shown field.
-                let double_quote_escape_field = if v.contains("\\\"") {
-                    v
-                } else if v.contains("\"") {
-                    &v.replace("\"", "\\\"")
-                } else {
-                    v
-                };
-
-                if v.contains(',') {
-                    write!(&mut self.0, "\"{}\"", double_quote_escape_field)?
-                } else {
-                    write!(&mut self.0, "{}", double_quote_escape_field)?
-                }
-            }
-
-            _ => write!(&mut self.0, "{}", data)?,
-        }
+        let f = cell_to_str(data);
+        let numeric = matches!(data, Data::Int(_) | Data::Float(_));
+        self.0
+            .write_all(self.1.quote_field(&f, numeric).as_bytes())?;
 
         Ok(())
     }
 
-    pub fn write_excel_line(&mut self, line: &[Data], sep: &[u8]) -> CliResult {
+    pub fn write_excel_line(&mut self, line: &[Data]) -> CliResult {
         let mut l = line.iter().peekable();
         while let Some(f) = l.next() {
             self.write_excel_field(f)?;
-
             if l.peek().is_some() {
-                self.0.write_all(sep)?;
-            } else {
-                self.0.write_all(TERMINATOR)?;
+                self.write_sep()?;
             }
         }
+        self.0.write_all(self.1.terminator())?;
 
         Ok(())
     }
 
-    pub fn write_excel_line_unchecked(&mut self, line: &[Data], sep: &[u8]) {
-        if self.write_excel_line(line, sep).is_err() {
-            process::exit(0)
-        }
-    }
-
-    pub fn write_excel_selected_fields(
-        &mut self,
-        line: &[Data],
-        cols: &[usize],
-        sep: &[u8],
-    ) -> CliResult {
-        let mut l = cols.iter().peekable();
-        while let Some(&i) = l.next() {
-            self.write_excel_field(&line[i])?;
-
-            if l.peek().is_some() {
-                self.0.write_all(sep)?;
-            } else {
-                self.0.write_all(TERMINATOR)?;
-            }
-        }
-
-        Ok(())
-    }
-
-    pub fn write_excel_selected_fields_unchecked(
-        &mut self,
-        line: &[Data],
-        cols: &[usize],
-        sep: &[u8],
-    ) {
-        if self.write_excel_selected_fields(line, cols, sep).is_err() {
-            process::exit(0)
-        }
-    }
-
-    pub fn write_excel_lines(&mut self, lines: &[Vec<Data>], sep: &[u8]) -> CliResult {
+    pub fn write_excel_lines(&mut self, lines: &[Vec<Data>]) -> CliResult {
         for l in lines {
-            self.write_excel_line(l, sep)?;
+            self.write_excel_line(l)?;
         }
 
         Ok(())
     }
 
-    pub fn write_excel_lines_by_ref(&mut self, lines: &[&Vec<Data>], sep: &[u8]) -> CliResult {
+    pub fn write_excel_lines_by_ref(&mut self, lines: &[&Vec<Data>]) -> CliResult {
         for &l in lines {
-            self.write_excel_line(l, sep)?;
+            self.write_excel_line(l)?;
         }
 
         Ok(())
@@ -1,10 +1,22 @@
 use clap::Args;
-use rsv_lib::utils::{row_split::CsvRowSplitter, util::get_valid_sep};
+use rsv_lib::utils::{
+    encoding::TextEncoding,
+    groupby::AggFunc,
+    pivot::PercentOf,
+    util::{get_valid_percentile, get_valid_ratio, get_valid_sep},
+    writer::{Dialect, EscapeStyle, QuoteStyle},
+};
 
 #[derive(Debug, Args)]
 pub struct Count {
     /// File to open
     pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
@@ -23,6 +35,12 @@ pub struct Size {
 pub struct Estimate {
     /// File to open
     pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
     /// Get the nth worksheet for an Excel file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
@@ -50,6 +68,8 @@ pub struct Head {
     /// Quote char
     #[arg(short, long, default_value_t = '"')]
     pub quote: char,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -65,9 +85,17 @@ pub struct Tail {
     /// Get the nth worksheet of EXCEL file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
-    /// Export to a file named current-file-head.csv?
+    /// Export to a file named current-file-tail.csv?
     #[arg(short = 'E', long, default_value_t = false)]
     pub export: bool,
+    /// Field separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -89,6 +117,15 @@ pub struct Headers {
 pub struct Clean {
     /// File to open
     pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
     /// Output file, default to current-file-cleaned.csv
     #[arg(short, long, default_value_t = String::from(""), hide_default_value=true)]
     pub output: String,
@@ -97,6 +134,51 @@ pub struct Clean {
     pub escape: String,
 }
 
+#[derive(Debug, Args)]
+pub struct Validate {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// File to save bad rows, along with the header
+    #[arg(short, long)]
+    pub reject: Option<String>,
+    /// Schema file (TOML or JSON) to check values against
+    #[arg(long)]
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct Schema {
+    /// Action to take, only infer is supported
+    #[arg(value_parser = ["infer"])]
+    pub action: String,
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Output file, a .toml or .json file, default to print TOML
+    #[arg(short, long)]
+    pub output: Option<String>,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+}
+
 #[derive(Debug, Args)]
 pub struct Flatten {
     /// File to open
@@ -149,6 +231,8 @@ pub struct Frequency {
     /// Get the nth worksheet of EXCEL file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -164,21 +248,32 @@ pub struct Split {
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
-    /// Column to split upon
-    #[arg(short, long, default_value_t = 0)]
-    pub col: usize,
+    /// Column to split upon, an index or a header name
+    #[arg(short, long, default_value_t = String::from("0"), allow_hyphen_values = true)]
+    pub col: String,
     /// Get the nth worksheet of EXCEL file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
     /// Number of records to write in each separate file
     #[arg(long)]
     pub size: Option<usize>,
+    /// Compress output files, can be gz, zst or bz2 [default: compression of input file]
+    #[arg(short = 'z', long)]
+    pub compress: Option<String>,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
 pub struct Slice {
     /// File to open
     pub filename: Option<String>,
+    /// Separator
+    #[arg(long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
     /// Start index of CSV
     #[arg(short, long, default_value_t = 0)]
     pub start: usize,
@@ -209,16 +304,16 @@ pub struct Select {
     /// Separator
     #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
     pub sep: char,
-    /// Separator
-    #[arg(short, long, default_value_t = ',')]
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
     pub quote: char,
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
-    /// Columns to select, support syntax 0,1,3 or 0-4, including 4; Default to select all columns
+    /// Columns to select, support syntax 0,1,3 or 0-4, including 4, or header names like id,amount; Default to select all columns
     #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values=true)]
     pub cols: String,
-    /// Row filter, support syntax 0=a,b,c or 0=a,b&1=c,d; Default to None
+    /// Row filter, support syntax 0=a,b,c or (0=a|1N>10)&!contains(2,b); Default to None
     #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values=true)]
     pub filter: String,
     /// Export results to a file named current-file-selected.csv?
@@ -227,6 +322,8 @@ pub struct Select {
     /// Get the nth worksheet of EXCEL file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -242,7 +339,7 @@ pub struct Stats {
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
-    /// Columns to generate statistics, support syntax 0,1,3 or 0-4, including 4; Default to select all columns
+    /// Columns to generate statistics, support syntax 0,1,3 or 0-4, including 4, or header names like id,amount; Default to select all columns
     #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values=true)]
     pub cols: String,
     /// Export results to a file named current-file-selected.csv?
@@ -254,6 +351,22 @@ pub struct Stats {
     /// Comma-separated column indexes to format as text (e.g., "0,2")
     #[arg(short, long, value_delimiter = ',', allow_hyphen_values = true)]
     pub text_columns: Vec<usize>,
+    /// Schema file (TOML or JSON) declaring column types
+    #[arg(long)]
+    pub schema: Option<String>,
+    /// Infer column types from all rows rather than the first 5000 rows
+    #[arg(long, default_value_t = false)]
+    pub full_scan: bool,
+    /// Ratio of non-null values that fit a type inferred by --full-scan
+    #[arg(long, default_value_t = 1.0, value_parser=get_valid_ratio)]
+    pub confidence: f64,
+    /// Comma-separated percentiles of numeric columns to export, besides the median,
+    /// approximated over all rows by a KLL sketch (K=256) and labelled with ~ when inexact
+    #[arg(short, long, value_delimiter = ',', default_value = "1,25,75,99", value_parser=get_valid_percentile)]
+    pub percentiles: Vec<f64>,
+    /// Print a histogram of each numeric column with the number of bins
+    #[arg(long, num_args = 0..=1, default_missing_value = "10")]
+    pub histogram: Option<usize>,
 }
 
 #[derive(Debug, Args)]
@@ -269,6 +382,8 @@ pub struct Excel2csv {
     /// Quote Char
     #[arg(short, long, default_value_t = '"')]
     pub quote: char,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -305,7 +420,7 @@ pub struct Search {
     /// Search specific columns, e.g. -c=0,1 to search first two columns; Default to all columns
     #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values=true)]
     pub col: String,
-    /// Columns to select in output, support syntax 0,1,3 or 0-4, including 4; Default to select all columns
+    /// Columns to select in output, support syntax 0,1,3 or 0-4, including 4, or header names like id,amount; Default to select all columns
     #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values=true)]
     pub out: String,
     /// Get the nth worksheet of EXCEL file
@@ -314,6 +429,8 @@ pub struct Search {
     /// Export to a file named current-file-searched.csv?
     #[arg(short = 'E', long, default_value_t = false)]
     pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
@@ -329,22 +446,37 @@ pub struct Sort {
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
-    /// Columns to sort by, support syntax 0 (first column),
-    /// "-0" (descending), "-0N" (as numeric) or "0N,-1" (two columns)
+    /// Columns to sort by, support syntax 0 (first column), "0D" (descending),
+    /// "0N" (numeric), "0V" (natural), "0T" (date), "0NF" (nulls first),
+    /// "0N,1,2D" (multiple columns) or header names like "amountDN"
     #[arg(short, long, default_value_t = String::from("0"), allow_hyphen_values=true)]
     pub cols: String,
     /// Get the nth worksheet of EXCEL file
     #[arg(short = 'S', long, default_value_t = 0)]
     pub sheet: usize,
+    /// Memory budget in MB, sorted runs are spilled to temporary files when exceeded
+    #[arg(short, long, default_value_t = 1024)]
+    pub memory: usize,
+    /// Directory for temporary files, default to the system temp directory
+    #[arg(long)]
+    pub temp_dir: Option<String>,
     /// Export to a file named current-file-searched.csv?
     #[arg(short = 'E', long, default_value_t = false)]
     pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
 #[derive(Debug, Args)]
 pub struct Sample {
     /// File to open
     pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
     /// Whether the file has a header
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
@@ -398,6 +530,18 @@ pub struct To {
     /// Return datetimes as Excel serial values instead of formatted strings
     #[arg(long, default_value_t = false)]
     pub serial_dates: bool,
+    /// Encoding of CSV, TSV and TXT outputs, e.g., gbk
+    #[arg(long, default_value = "utf-8")]
+    pub out_encoding: TextEncoding,
+    /// Schema file (TOML or JSON) declaring column types and date formats
+    #[arg(long)]
+    pub schema: Option<String>,
+    /// Infer column types from all rows rather than the first 5000 rows
+    #[arg(long, default_value_t = false)]
+    pub full_scan: bool,
+    /// Ratio of non-null values that fit a type inferred by --full-scan
+    #[arg(long, default_value_t = 1.0, value_parser=get_valid_ratio)]
+    pub confidence: f64,
 }
 
 #[derive(Debug, Args)]
@@ -425,45 +569,308 @@ pub struct Unique {
     /// Export to a file named drop-duplicates.csv?
     #[arg(short = 'E', long, default_value_t = false)]
     pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Join {
+    /// File to join with, i.e., the right file
+    pub right: String,
+    /// File to open, i.e., the left file
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the files have a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Key columns of the left file, support syntax 0 or 0,1 or header names like id,date
+    #[arg(short, long, default_value_t = String::from("0"), allow_hyphen_values = true)]
+    pub keys: String,
+    /// Key columns of the right file, default to the same as --keys
+    #[arg(short, long, allow_hyphen_values = true)]
+    pub right_keys: Option<String>,
+    /// Join type, can be one of inner, left, right, full, semi and anti
+    #[arg(long, default_value_t = String::from("inner"))]
+    pub how: String,
+    /// Join strategy, can be one of auto, hash and merge
+    #[arg(long, default_value_t = String::from("auto"))]
+    pub strategy: String,
+    /// Memory budget in MB, the hash strategy is used when the right file fits in
+    #[arg(short, long, default_value_t = 1024)]
+    pub memory: usize,
+    /// Get the nth worksheet of the left EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Get the nth worksheet of the right EXCEL file
+    #[arg(long, default_value_t = 0)]
+    pub right_sheet: usize,
+    /// Export to a file named current-file-joined.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Groupby {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Key columns to group by, support syntax 0 or 0,2 or header names like region,date
+    #[arg(short, long, default_value_t = String::from("0"), allow_hyphen_values = true)]
+    pub keys: String,
+    /// Aggregations, e.g., sum(5),mean(6),countd(1); Default to count rows of groups
+    #[arg(short, long, default_value_t = String::from("count"))]
+    pub aggs: String,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-groupby.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
 }
 
-macro_rules! impl_row_split {
-    ($cmd:ident) => {
-        impl $cmd {
-            #[allow(dead_code)]
-            pub fn split<'a>(&self, row: &'a str) -> CsvRowSplitter<'a> {
-                CsvRowSplitter::new(row, self.sep, self.quote)
-            }
-
-            #[allow(dead_code)]
-            pub fn split_row_to_vec<'a>(&self, row: &'a str) -> Vec<&'a str> {
-                CsvRowSplitter::new(row, self.sep, self.quote).collect()
-            }
-
-            #[allow(dead_code)]
-            pub fn split_row_to_owned_vec(&self, row: &str) -> Vec<String> {
-                CsvRowSplitter::new(row, self.sep, self.quote)
-                    .map(|i| i.to_owned())
-                    .collect::<Vec<_>>()
-            }
-
-            #[allow(dead_code)]
-            pub fn row_field_count(&self, row: &str) -> usize {
-                CsvRowSplitter::new(row, self.sep, self.quote).count()
-            }
+#[derive(Debug, Args)]
+pub struct Pivot {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Row key columns, support syntax 0 or 0,2 or header names like region,date
+    #[arg(short, long, default_value_t = String::from("0"), allow_hyphen_values = true)]
+    pub rows: String,
+    /// Column key, whose distinct values become output columns
+    #[arg(short, long, default_value_t = String::from("1"), allow_hyphen_values = true)]
+    pub cols: String,
+    /// Value column to aggregate; Default to count rows
+    #[arg(short, long, allow_hyphen_values = true)]
+    pub value: Option<String>,
+    /// Aggregation, can be count, sum, mean, min or max
+    #[arg(short, long, default_value_t = AggFunc::Count)]
+    pub agg: AggFunc,
+    /// Append a total column and a total row
+    #[arg(short, long, default_value_t = false)]
+    pub totals: bool,
+    /// Output percentages of the total, row or col totals
+    #[arg(short, long)]
+    pub percent: Option<PercentOf>,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-pivot.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    /// Export to a file named current-file-pivot.xlsx rather than csv
+    #[arg(long, default_value_t = false)]
+    pub xlsx: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Melt {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Id columns kept in each output row, support syntax 0 or 0,1 or header names
+    #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values = true)]
+    pub id: String,
+    /// Value columns to melt, support syntax 2-40 or header names; Default to all non-id columns
+    #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values = true)]
+    pub value: String,
+    /// Name of the output column holding header names of value columns
+    #[arg(long, default_value_t = String::from("variable"))]
+    pub var_name: String,
+    /// Name of the output column holding values
+    #[arg(long, default_value_t = String::from("value"))]
+    pub value_name: String,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-melted.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Unmelt {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Id columns, support syntax 0 or 0,1 or header names; Default to all columns other than the key and value
+    #[arg(short, long, default_value_t = String::from(""), allow_hyphen_values = true)]
+    pub id: String,
+    /// Key column holding variable names, which become output columns
+    #[arg(short, long, default_value_t = String::from("-2"), allow_hyphen_values = true)]
+    pub key: String,
+    /// Value column holding values of variables
+    #[arg(short, long, default_value_t = String::from("-1"), allow_hyphen_values = true)]
+    pub value: String,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-unmelted.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Transpose {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Memory limit in MB, to transpose a large file in multiple passes of columns
+    #[arg(short, long)]
+    pub max_memory: Option<usize>,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-transposed.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    /// Export to a file named current-file-transposed.xlsx rather than csv
+    #[arg(long, default_value_t = false)]
+    pub xlsx: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Cat {
+    /// Action to take, rows to stack files, or cols to paste files side by side
+    #[arg(value_parser = ["rows", "cols"])]
+    pub action: String,
+    /// Files to concatenate, or - for standard input
+    #[arg(required = true)]
+    pub filenames: Vec<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether files have a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Append a column of source file names to rows, e.g., --source=file, named source_file by default
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "source_file")]
+    pub source: Option<String>,
+    /// Get the nth worksheet of EXCEL files
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named cat.csv in the current directory?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+#[derive(Debug, Args)]
+pub struct Derive {
+    /// File to open
+    pub filename: Option<String>,
+    /// Separator
+    #[arg(short, long, default_value_t = ',', value_parser=get_valid_sep)]
+    pub sep: char,
+    /// Quote char
+    #[arg(short, long, default_value_t = '"')]
+    pub quote: char,
+    /// Whether the file has a header
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+    /// Expression like total=c3*c4, can be repeated
+    #[arg(short, long = "expr", required = true)]
+    pub exprs: Vec<String>,
+    /// Get the nth worksheet of EXCEL file
+    #[arg(short = 'S', long, default_value_t = 0)]
+    pub sheet: usize,
+    /// Export to a file named current-file-derived.csv?
+    #[arg(short = 'E', long, default_value_t = false)]
+    pub export: bool,
+    #[command(flatten)]
+    pub dialect: OutDialect,
+}
+
+/// Output dialect of commands that write rows
+#[derive(Debug, Args)]
+pub struct OutDialect {
+    /// Output separator [default: the input separator]
+    #[arg(long, value_parser=get_valid_sep)]
+    pub out_sep: Option<char>,
+    /// Output quote char
+    #[arg(long, default_value_t = '"')]
+    pub out_quote: char,
+    /// Quote output fields, can be minimal, all, non-numeric or never
+    #[arg(long, default_value_t = QuoteStyle::Minimal)]
+    pub quote_style: QuoteStyle,
+    /// Escape quote chars in quoted fields, can be double or backslash
+    #[arg(long, default_value_t = EscapeStyle::Double)]
+    pub escape_style: EscapeStyle,
+    /// End output rows with CRLF rather than LF
+    #[arg(long, default_value_t = false)]
+    pub crlf: bool,
+}
+
+impl OutDialect {
+    /// The output dialect of rows read with the separator and quote char.
+    pub fn of(&self, sep: char, quote: char) -> Dialect {
+        Dialect {
+            sep: self.out_sep.unwrap_or(sep),
+            quote: self.out_quote,
+            quote_style: self.quote_style,
+            escape_style: self.escape_style,
+            crlf: self.crlf,
+            ..Dialect::input(sep, quote)
         }
-    };
-}
-
-impl_row_split!(Head);
-impl_row_split!(Headers);
-impl_row_split!(Frequency);
-impl_row_split!(Select);
-impl_row_split!(Stats);
-impl_row_split!(To);
-impl_row_split!(Flatten);
-impl_row_split!(Unique);
-impl_row_split!(Search);
-impl_row_split!(Table);
-impl_row_split!(Split);
-impl_row_split!(Excel2csv);
+    }
+}
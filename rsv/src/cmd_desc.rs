@@ -11,8 +11,9 @@ Options:
 "#;
 
 pub const COUNT_DESC: &str = r#"
-Count the number of lines in a file. When supplemented with a directory, 
-the command counts the number of files in the directory.
+Count the number of records in a file, where a quoted field is allowed to span
+lines. When supplemented with a directory, the command counts the number of 
+files in the directory.
 
 Usage: 
   rsv.exe count [OPTIONS] <FILENAME>
@@ -25,6 +26,8 @@ Arguments:
   <FILENAME>  File to open
 
 Options:
+  -s, --sep <SEP>      Separator [default: ,]
+  -q, --quote <QUOTE>  Quote char [default: "]
       --no-header      Whether the file has a header
   -S, --sheet <SHEET>  Get the nth worksheet of Excel file [default: 0]
   -h, --help           Print help information (use `--help` for more detail)
@@ -69,6 +72,13 @@ Options:
   -n, --n <N>          Number of records to show [default: 10]
   -S, --sheet <SHEET>  Get the nth worksheet of EXCEL file [default: 0]
   -h, --help           Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
 "#;
 
 pub const TAIL_DESC: &str = r#"
@@ -80,6 +90,8 @@ Usage:
   rsv tail -n 5 data.csv          # tail 5 records
   rsv tail data.xlsx              # EXCEL file, default to first sheet
   rsv tail --sheet 1 data.xlsx    # the second sheet
+  rsv tail -s '\t' data.tsv        # tab-separated file
+  cat data.csv | rsv tail         # read from stdin
 
 Arguments:
   [FILENAME]           File to open
@@ -88,8 +100,17 @@ Options:
       --no-header      Whether the file has a header
   -n, --n <N>          Number of records to show [default: 10]
   -S, --sheet <SHEET>  Get the nth worksheet of EXCEL file [default: 0]
-  -E, --export         Export to a file named current-file-head.csv?
+  -E, --export         Export to a file named current-file-tail.csv?
+  -s, --sep <SEP>      Field separator [default: ,]
+  -q, --quote <QUOTE>  Quote char [default: "]
   -h, --help           Print help
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
 "#;
 
 pub const ESTIMATE_DESC: &str = r#"
@@ -107,29 +128,119 @@ Arguments:
   <FILENAME>  File to open
 
 Options:
+  -s, --sep <SEP>      Separator [default: ,]
+  -q, --quote <QUOTE>  Quote char [default: "]
   -S, --sheet <SHEET>  Get the nth worksheet for an Excel file [default: 0]
   -h, --help           Print help information 
 "#;
 
 pub const CLEAN_DESC: &str = r#"
 Clean file with escape chars (e.g. "). Other special strings can also be cleaned. 
-EXCEL files are not supported.
+Records of EXCEL sheets are cleaned as CSV rows, and stdin is cleaned to stdout
+unless an output file is given.
 
 Usage: 
   rsv.exe clean [OPTIONS] <FILENAME>
   rsv clean data.csv                             # default to clean escape char "
   rsv clean -e "content-to-delete" data.csv      # clean str to empty
   rsv clean -o new-file.csv data.csv             # save to new-file.csv
+  rsv clean -S 1 data.xlsx                       # clean the second sheet
 
 Arguments:
   <FILENAME>  File to open
 
 Options:
+  -s, --sep <SEP>        Separator [default: ,]
+  -q, --quote <QUOTE>    Quote char [default: "]
+  -S, --sheet <SHEET>    Get the nth worksheet of EXCEL file [default: 0]
   -o, --output <F>       Output file, default to current-file-cleaned.csv
   -e, --escape <ESCAPE>  Escape char to clean [default: "]
   -h, --help             Print help information
 "#;
 
+pub const VALIDATE_DESC: &str = r#"
+Check a CSV or TXT file for problems, and report them with line numbers as
+CSV rows of line,issue,detail. A summary is printed at the end, and the exit
+code is 9 when any problem is found. Bad rows, i.e., rows with a wrong number
+of fields, an unclosed quote or invalid UTF-8, can be saved to a reject file.
+EXCEL files are not supported.
+
+Usage:
+  rsv.exe validate [OPTIONS] [FILENAME]
+  rsv validate data.csv                      # report problems
+  rsv validate -r bad.csv data.csv           # save bad rows to bad.csv
+  rsv validate -s \t data.txt                # tab separator
+  rsv validate data.csv > issues.csv         # save the report
+  rsv validate --schema schema.toml data.csv # check values against a schema
+
+Issues:
+  bom                   file starts with a byte order mark
+  invalid-utf8          line is not valid UTF-8
+  empty-header          a header name is empty
+  duplicate-header      a header name appears more than once
+  field-count           row has a different number of fields from the first row
+  unbalanced-quote      quoted field is not closed until the end of file
+  mixed-line-endings    line ends differently from the first line, e.g., \r\n and \n
+  trailing-whitespace   field ends with spaces or tabs
+
+Schema issues:
+  missing-column        a column of the schema is not in the file
+  null                  value is null, while the column is not nullable
+  type                  value is not of the column type, or not of the date format
+  value                 value is not one of the allowed values
+  range                 number is less than min or greater than max
+  pattern               value does not match the regex pattern
+
+Arguments:
+  [FILENAME]  File to open
+
+Options:
+  -s, --sep <SEP>        Separator [default: ,]
+  -q, --quote <QUOTE>    Quote char [default: "]
+      --no-header        Whether the file has a header
+  -r, --reject <F>       File to save bad rows, along with the header
+      --schema <F>       Schema file (TOML or JSON) to check values against
+  -h, --help             Print help information
+"#;
+
+pub const SCHEMA_DESC: &str = r#"
+Infer a schema of a file, which declares the name, type and nullability of
+columns, along with the range of number columns, the allowed values of category
+columns, and the format of date columns. All rows are scanned, rather than the
+first 5000 rows as the type guess of other commands. The schema is a draft to
+be edited, and is consumed by the --schema flag of stats, to and validate.
+
+Usage:
+  rsv.exe schema infer [OPTIONS] [FILENAME]
+  rsv schema infer data.csv                    # print a TOML schema
+  rsv schema infer -o schema.toml data.csv     # save to schema.toml
+  rsv schema infer -o schema.json data.xlsx    # save as JSON
+  rsv stats --schema schema.toml data.csv      # use the schema
+
+Schema:
+  [[columns]]
+  name = "amount"              # header name, or matched by position with --no-header
+  type = "int"                 # int, float, string or date [default: string]
+  nullable = false             # [default: true]
+  min = 0                      # range of numbers
+  max = 1000
+  values = ["east", "west"]    # allowed values
+  pattern = '[A-Z]{2}\d+'      # regex that the whole value matches
+  date_format = "%Y-%m-%d"     # format of date columns [default: auto parse]
+
+Arguments:
+  <ACTION>    Action to take, only infer is supported
+  [FILENAME]  File to open
+
+Options:
+  -s, --sep <SEP>        Separator [default: ,]
+  -q, --quote <QUOTE>    Quote char [default: "]
+      --no-header        Whether the file has a header
+  -o, --output <F>       Output file, a .toml or .json file, default to print TOML
+  -S, --sheet <SHEET>    Get the nth worksheet of EXCEL file [default: 0]
+  -h, --help             Print help information
+"#;
+
 pub const FLATTEN_DESC: &str = r##"
 Prints flattened records to view them one by one. Records are separated
 by "#", which could be changed with the --delimiter flag. The command is 
@@ -180,6 +291,8 @@ Arguments:
   <FILENAME>             File to open, including CSV, TXT, and EXCEL
 
 Options:
+      --sep <SEP>        Separator [default: ,]
+  -q, --quote <QUOTE>    Quote char [default: "]
   -s, --start <START>    Start index of file [default: 0]
   -e, --end <END>        End index of file
   -l, --length <LENGTH>  Slice length
@@ -215,13 +328,23 @@ Options:
   -E, --export         Export result to a frequency.csv file
   -n, --n <N>          Top N to keep in frequency table [default: -1]
   -S, --sheet <SHEET>  Work for the nth worksheet of EXCEL file [default: 0]
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
   -h, --help           Print help information
 
 Column selection syntax:
-  -c 0,1,2,5    -->    cols [0,1,2,5]
-  -c 0-2,5      -->    same as cols [0,1,2,5]
-  -c -1         -->    last column
-  -c -2--1      -->    last two columns
+  -c 0,1,2,5         -->    cols [0,1,2,5]
+  -c 0-2,5           -->    same as cols [0,1,2,5]
+  -c -1              -->    last column
+  -c -2--1           -->    last two columns
+  -c id,amount       -->    columns by header name
+  -c id-amount       -->    columns from id to amount
+  -c '"2020"'        -->    header named 2020 rather than index 2020
+  -c "amount_*"      -->    header names matching a glob pattern
+  -c '/^amount/'     -->    header names matching a regex
 "#;
 
 pub const SPLIT_DESC: &str = r#"
@@ -245,8 +368,10 @@ Usage:
   rsv split data.csv               # default to split based on first column 
   rsv split -c 1 data.csv          # second column to split
   rsv split -c 0 -s \t data.csv    # first column, \t separator
+  rsv split -c region data.csv     # column named region to split
   rsv split data.xlsx              # EXCEL file
   rsv split --size 1000 data.xlsx  # sequential split, 1000 records per file.
+  rsv split -z gz data.csv         # gzip compressed outputs
 
 Arguments:
   <FILENAME>           File to open
@@ -258,10 +383,20 @@ Column-based Split Options:
   -s, --sep <SEP>      Separator [default: ,]
   -q, --quote <QUOTE>  Quote Char [default: "]
       --no-header      Whether the file has a header
-  -c, --col <COL>      Column to split upon [default: 0]
+  -c, --col <COL>      Column to split upon, an index or a header name [default: 0]
   -S, --sheet <SHEET>  Get the nth worksheet of EXCEL file [default: 0]
       --size <SIZE>    Number of records to write in each separate file
+
+Output Options:
+  -z, --compress <COMPRESS>  Compress output files, can be gz, zst or bz2 [default: compression of input file]
   -h, --help           Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
 "#;
 
 pub const SELECT_DESC: &str = r#"
@@ -276,6 +411,9 @@ Usage:
   rsv select -f 0=a,b data.xlsx           # apply to EXCEL file
   rsv select -f "0>@1-10*(@3+2)" data.csv # math calculation based on column 2 (@1) and column 4 (@3)
                                           # left column is treated as numeric automatically
+  rsv select -f '(0=a | 3N>100) & !(2 ~ "^test")' data.csv  # OR, NOT, parentheses and regex
+  rsv select -c 0,1 --out-sep '\t' data.csv  # tab-separated output
+  rsv select -c 0,1 --quote-style all data.csv  # quote all output fields
 
 Arguments:
   <FILENAME>                File to open
@@ -290,14 +428,35 @@ Options:
   -S, --sheet <SHEET>       Get the nth worksheet of EXCEL file [default: 0]
   -h, --help                Print help information
 
-Filter syntax, support =, !=, >, >=, <, <= and &:
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Filter syntax, support =, !=, >, >=, <, <=, ~, in, between, functions, and
+& (AND), | (OR), ! (NOT) and parentheses:
   -f 0=a,b,c           -->  first column is a, b, or c
   -f 0N=1,2            -->  first column numerically equals to 1 or 2
   -f 0!=               -->  first column is not empty
   -f "0>=2022-01-21"   -->  first column equal to or bigger than 2022-01-21, lexicographically
   -f "0N>10"           -->  first column > 10 numerically
   -f "0N>10&2=pattern" -->  first column > 10 numerically, AND the third column equals to <pattern>
-  
+  -f "amountN>10"      -->  column named amount > 10 numerically
+  -f "0=a | 1N>10"     -->  first column is a, OR the second column > 10 numerically
+  -f '!(0=a | 1=b)'    -->  NOT (first column is a OR the second column is b)
+  -f '2 ~ "^test"'     -->  third column matches the regex ^test, !~ for not matching
+  -f "0 in (a, b)"     -->  first column is a or b, "not in" for the opposite
+  -f "0N between 1 and 5"   -->  first column in [1, 5] numerically
+  -f "nameI=bob"            -->  a trailing I for case-insensitive comparison, e.g., Bob or BOB
+  -f "contains(0, abc)"     -->  first column contains abc, also starts_with and ends_with
+  -f "is_null(0)"           -->  first column is empty
+  -f '(0=a | 3N>100) & !(2 ~ "^test")'  -->  combine conditions with parentheses
+
+Values run until the next &, | or ), quote them when they contain these chars,
+e.g., -f '0="a&b"' or -f '0 in ("a,b", c)'.
+
 Math express syntax (support +, -, *, /, %, ^, (, )):
 -f "0>@1 + 1"         -->   first column > second column plus one
 -f "0>=(@1+1)/(2^2)"  -->   first column >= (second column + 1) / (2 ^ 2)
@@ -307,13 +466,25 @@ Column selection syntax:
   -c 0-2,5            -->   same as cols [0,1,2,5]
   -c -1               -->   last column
   -c -2--1            -->   last two columns
+  -c id,amount        -->   columns by header name
+  -c id-amount        -->   columns from id to amount
+  -c '"2020"'         -->   header named 2020 rather than index 2020
+  -c "amount_*"       -->   header names matching a glob pattern
+  -c '/^amount/'      -->   header names matching a regex
 "#;
 
 pub const STATS_DESC: &str = r#"
-Statistics for columns, including min, max, mean, unique, null. Within the command, 
-columns are regarded as either an Int, Float or String. When the column is String, 
-min, max, mean are ignored. When the column is Float, the unique stat is ignored.
-The command processes data in batches and in parallel.
+Statistics for columns, including min, max, mean, stddev, median, unique, null. 
+Within the command, columns are regarded as either an Int, Float or String. When 
+the column is String, min, max, mean are ignored. When the column is Float, the 
+unique stat is ignored. The export (-E) also includes sum, variance, percentiles 
+(-p) and the min and max lengths of values. Medians, percentiles and histograms are 
+computed over all rows by a mergeable KLL sketch (K=256). They are exact for columns 
+with up to 256 values, and are approximated and labelled with ~ (e.g., ~median) beyond.
+The command processes data in batches and in parallel. Column types are guessed 
+from the first 5000 rows, or from all rows with --full-scan, where --confidence 
+keeps a narrower type when a few values do not fit it, and such values are skipped.
+Standard input is spilled to a temporary file for the extra pass of --full-scan.
 
 Usage: 
   rsv stats [OPTIONS] <FILENAME>
@@ -321,6 +492,12 @@ Usage:
   rsv stats -c 0,1 data.csv                # first two columns
   rsv stats -c 0,1 --export data.csv       # export statistics to data-stats.csv
   rsv stats -c 0,1 --export data.xlsx      # EXCEL file
+  rsv stats --schema schema.toml data.csv  # column types declared in a schema
+  rsv stats --full-scan data.csv           # column types inferred from all rows
+  rsv stats --full-scan --confidence 0.99 data.csv
+  rsv stats -E -p 5,50,95 data.csv         # export with the 5th, 50th and 95th percentiles
+  rsv stats --histogram data.csv           # histograms of numeric columns with 10 bins
+  rsv stats --histogram 20 data.csv        # 20 bins
 
 Arguments:
   <FILENAME>                         File to open, including CSV, TXT, and EXCEL
@@ -332,13 +509,23 @@ Options:
   -E, --export                       Export results to a file named current-file-selected.csv
   -S, --sheet <SHEET>                Get the nth worksheet of EXCEL file [default: 0]
   -t, --text-columns <TEXT_COLUMNS>  Comma-separated column indexes to format as text [default:]
+      --schema <F>                   Schema file (TOML or JSON) declaring column types
+      --full-scan                    Infer column types from all rows rather than the first 5000 rows
+      --confidence <C>               Ratio of non-null values that fit an inferred type [default: 1]
+  -p, --percentiles <P>              Comma-separated percentiles to export [default: 1,25,75,99]
+      --histogram [<BINS>]           Print a histogram of each numeric column [default: 10]
   -h, --help                         Print help information
 
 Column selection syntax:
-  -c 0,1,2,5       -->   cols [0,1,2,5]
-  -c 0-2,5         -->   same as cols [0,1,2,5]
-  -c -1            -->   last column
-  -c -2--1         -->   last two columns
+  -c 0,1,2,5         -->   cols [0,1,2,5]
+  -c 0-2,5           -->   same as cols [0,1,2,5]
+  -c -1              -->   last column
+  -c -2--1           -->   last two columns
+  -c id,amount       -->   columns by header name
+  -c id-amount       -->   columns from id to amount
+  -c '"2020"'        -->   header named 2020 rather than index 2020
+  -c "amount_*"      -->   header names matching a glob pattern
+  -c '/^amount/'     -->   header names matching a regex
 "#;
 
 pub const EXCEL2CSV_DESC: &str = r#"
@@ -357,6 +544,13 @@ Options:
   -s, --sep <SEP>      Separator [default: ,]
   -q, --quote <QUOTE>  Quote char [default: "]
   -h, --help           Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
 "#;
 
 pub const TABLE_DESC: &str = r#"
@@ -401,25 +595,46 @@ Options:
   -E, --export             Export to a file named current-file-searched.csv?
   -h, --help               Print help
 
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
 Column selection syntax:
-  -c 0,1,2,5        -->  cols [0,1,2,5]
-  -c 0-2,5          -->  same as cols [0,1,2,5]
-  -c -1             -->  last column
-  -c -2--1          -->  last two columns
+  -c 0,1,2,5         -->  cols [0,1,2,5]
+  -c 0-2,5           -->  same as cols [0,1,2,5]
+  -c -1              -->  last column
+  -c -2--1           -->  last two columns
+  -c id,amount       -->  columns by header name
+  -c id-amount       -->  columns from id to amount
+  -c '"2020"'        -->  header named 2020 rather than index 2020
+  -c "amount_*"      -->  header names matching a glob pattern
+  -c '/^amount/'     -->  header names matching a regex
 "#;
 
 pub const SORT_DESC: &str = r##"
-Sort data by column(s). The sort is performed in-memory, so that large files
-are not supported. The command supports sorting for at most two columns. 
-
-The default is ascending sort. Descending sort can be specified with the 
--c 0D flag, where D stands for Descending Sort.  
-
-The default is string sorting. Numeric sorting can be specified with the
--c 0N flag, where N stands for Numeric Sorting.
-
-D (descending) and N (numeric) can be placed in arbitrary order, e.g., 
--c 0DN or -c 0ND.
+Sort data by column(s). The command supports sorting by any number of columns, 
+the first column being the primary key.
+
+Data is sorted in memory within a memory budget (--memory, default to 1024MB). 
+For files larger than the budget, sorted runs are spilled to temporary files 
+and merged in the end, so that large files are supported.
+
+Each column can be followed by flags in arbitrary order:
+  D      -->  descending sort, the default is ascending
+  N      -->  numeric sort
+  V      -->  natural sort, e.g., file2 comes before file10
+  T      -->  date or datetime sort, e.g., 2024-01-31 or 1/31/2024 10:00
+  F      -->  nulls first, for N and T only
+  L      -->  nulls last (default), for N and T only
+The default is string sorting, where fields are compared as they are. In
+numeric (N) and date (T) sorts, empty fields are regarded as nulls, as are
+fields that cannot be parsed as numbers or dates.
+
+Columns can also be referred to by header names, e.g., -c amountDN. A header
+name that itself ends with these letters, e.g., id, takes precedence over the flags.
 
 Usage: 
   rsv sort [OPTIONS] [FILENAME]
@@ -427,7 +642,11 @@ Usage:
   rsv sort -c 0D data.csv       # descending sort
   rsv sort -c 0DN data.csv      # sort as numeric values
   rsv sort -c 0DN,2N data.csv   # sort two columns
+  rsv sort -c amountN data.csv  # sort by column named amount
+  rsv sort -c region,dateT,customerV,amountDNF data.csv
+                                # sort four columns
   rsv sort -E data.csv          # export result
+  rsv sort -m 4096 data.csv     # sort with a memory budget of 4096MB
   rsv sort data.xlsx            # sort EXCEL file
 
 Arguments:
@@ -437,16 +656,38 @@ Options:
   -s, --sep <SEP>        Separator [default: ,]
   -q, --quote <QUOTE>    Quote char [default: "]
       --no-header        Whether the file has a header
-  -c, --cols <COLS>      Columns to sort by, e.g., -c 0, -c 0N, -c 0ND [default: 0]
+  -c, --cols <COLS>      Columns to sort by, e.g., -c 0, -c 0N, -c 0ND, -c 0,1DT,2VF [default: 0]
   -S, --sheet <SHEET>    Get the nth worksheet of EXCEL file [default: 0]
+  -m, --memory <MEMORY>  Memory budget in MB [default: 1024]
+      --temp-dir <DIR>   Directory for temporary files [default: system temp directory]
   -E, --export           Export to a file named current-file-sorted.csv?
   -h, --help             Print help
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
 "##;
 
 pub const TO_DESC: &str = r#"
-Save data to disk, can be one of TXT, CSV, TSV, XLSX or XLS. 
+Save data to disk, can be one of TXT, CSV, TSV, XLSX, XLS, PARQUET, JSON or NDJSON. 
 Usually applied to save medium results in a command line chain.
 When export to xlsx, column width in Excel is adjusted automatically.
+When export to parquet, column types are inferred from the first 5000 rows, from
+all rows (--full-scan), or declared in a schema file (--schema): 
+int columns are saved as INT64, float as DOUBLE, date columns (-d) as 
+TIMESTAMP, and others as UTF8 strings.
+When export to json, records are saved as an array of objects (json) or one 
+object per line (ndjson or jsonl), with header names as keys. Values of int 
+and float columns are saved as numbers, and empty fields as null. Values such 
+as NA are null in numeric and date columns, and text in other columns.
+Plain text outputs are compressed by extension, e.g., out.csv.gz, out.csv.zst 
+or out.csv.bz2, and transcoded from UTF-8 by --out-encoding, e.g., gbk.
+Input files are transcoded to UTF-8 by --encoding, which is detected by the BOM 
+or guessed from the first 64KB of a file by default. Set --encoding when a 
+file is UTF-8 in the first 64KB but not afterwards.
 
 Usage:
   rsv to [OPTIONS] <OUT> [FILENAME]
@@ -454,6 +695,16 @@ Usage:
   rsv head data | rsv to out.txt
   rsv head data | rsv to out.tsv
   rsv head data | rsv to out.xlsx
+  rsv head data | rsv to out.parquet
+  rsv to out.parquet -d 1 data.csv
+  rsv to csv data.parquet
+  rsv to out.json data.csv
+  rsv to out.ndjson data.xlsx
+  rsv to out.csv.gz data.csv
+  rsv to out.csv --out-encoding gbk data.csv
+  rsv to out.csv --encoding latin1 data.csv
+  rsv to out.parquet --schema schema.toml data.csv
+  rsv to out.parquet --full-scan --confidence 0.99 data.csv
 
 Arguments:
   <OUT>                              Output file, a file name or a file format
@@ -469,6 +720,13 @@ Options:
   -d, --date-columns <DATE_COLUMNS>  Columns (e.g., "0,2") to format as date      
       --date-formats <DATE_FORMATs>  Column date formats (e.g., "%Y-%m-%d,%m/%d/%Y %H:%M:%S") [default: auto parse]
       --serial_dates                 Return datetimes as Excel serial values instead of formatted strings
+      --encoding <ENCODING>          Encoding of input files, e.g., gbk or latin1 [default: auto]
+      --out-encoding <OUT_ENCODING>  Encoding of CSV, TSV and TXT outputs, e.g., gbk [default: utf-8]
+      --schema <F>                   Schema file (TOML or JSON) declaring column types and date 
+                                     formats, which overrides the guess and the -t, -d and 
+                                     --date-formats flags
+      --full-scan                    Infer column types from all rows rather than the first 5000 rows
+      --confidence <C>               Ratio of non-null values that fit an inferred type [default: 1]
   -h, --help                         Print help
 "#;
 
@@ -490,6 +748,8 @@ Arguments:
   [FILENAME]                     File to open
 
 Options:
+  -s, --sep <SEP>                Separator [default: ,]
+  -q, --quote <QUOTE>            Quote char [default: "]
       --no-header                Whether the file has a header
   -S, --sheet <SHEET>            Get the nth worksheet of EXCEL file [default: 0]
   -n, --n <N>                    Sample size [default: 10]
@@ -526,9 +786,453 @@ Options:
   -E, --export            Export to drop-duplicates.csv
   -h, --help              Print help
 
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Column selection syntax:
+  -c 0,1,2,5         -->    cols [0,1,2,5]
+  -c 0-2,5           -->    same as cols [0,1,2,5]
+  -c -1              -->    last column
+  -c -2--1           -->    last two columns
+  -c id,amount       -->    columns by header name
+  -c id-amount       -->    columns from id to amount
+  -c '"2020"'        -->    header named 2020 rather than index 2020
+  -c "amount_*"      -->    header names matching a glob pattern
+  -c '/^amount/'     -->    header names matching a regex
+"#;
+
+pub const JOIN_DESC: &str = r#"
+Join two files on key column(s). The left file is FILENAME or standard input,
+and the right file is RIGHT. Both files can be CSV, TXT or EXCEL files.
+
+Output columns are all columns of the left file, followed by the non-key columns 
+of the right file. Semi and anti joins output columns of the left file only.
+A record with an empty key matches no record, like nulls in SQL.
+
+Join types (--how):
+  inner      -->  records with keys in both files (default)
+  left       -->  all records of the left file
+  right      -->  all records of the right file
+  full       -->  all records of both files
+  semi       -->  records of the left file with keys in the right file
+  anti       -->  records of the left file with keys not in the right file
+
+Join strategies (--strategy):
+  hash       -->  load the right file into memory, and stream the left file in 
+                  parallel. Output follows the order of the left file.
+  merge      -->  sort both files by keys, spilling to temporary files within 
+                  the memory budget, and merge them. Output is ordered by keys.
+  auto       -->  hash when the right file fits in the memory budget, otherwise 
+                  merge (default)
+
+Usage: 
+  rsv join [OPTIONS] <RIGHT> [FILENAME]
+  rsv join -k id right.csv data.csv               # inner join on column id
+  rsv join -k 0,1 right.csv data.csv              # join on first two columns
+  rsv join -k id -r user_id right.csv data.csv    # keys with different names
+  rsv join --how left -k id right.xlsx data.csv   # left join with an EXCEL file
+  rsv join --how anti -k id right.csv data.csv    # records not in right.csv
+  rsv join --strategy merge right.csv data.csv    # sort-merge join for large files
+  rsv head data.csv | rsv join -k id right.csv    # join standard input
+
+Arguments:
+  <RIGHT>                        File to join with, i.e., the right file
+  [FILENAME]                     File to open, i.e., the left file
+
+Options:
+  -s, --sep <SEP>                Separator [default: ,]
+  -q, --quote <QUOTE>            Quote char [default: "]
+      --no-header                Whether the files have a header
+  -k, --keys <KEYS>              Key columns of the left file [default: 0]
+  -r, --right-keys <RIGHT_KEYS>  Key columns of the right file [default: same as --keys]
+      --how <HOW>                Join type [default: inner]
+      --strategy <STRATEGY>      Join strategy [default: auto]
+  -m, --memory <MEMORY>          Memory budget in MB [default: 1024]
+  -S, --sheet <SHEET>            Get the nth worksheet of the left EXCEL file [default: 0]
+      --right-sheet <SHEET>      Get the nth worksheet of the right EXCEL file [default: 0]
+  -E, --export                   Export to a file named current-file-joined.csv
+  -h, --help                     Print help
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Column selection syntax:
+  -k 0,1             -->    cols [0,1]
+  -k -1              -->    last column
+  -k id,date         -->    columns by header name
+"#;
+
+pub const GROUPBY_DESC: &str = r#"
+Aggregate value columns grouped by one or more key columns. Chunks of the file 
+are aggregated in parallel, and partial aggregates are merged in the end. Output
+is ordered by keys.
+
+Usage: 
+  rsv.exe groupby [OPTIONS] <FILENAME>
+  rsv groupby data.csv                               # count rows by the first column
+  rsv groupby -k 0,2 -a "sum(5),mean(6),countd(1)" data.csv
+  rsv groupby -k region -a "sum(amount),median(amount)" data.csv
+  rsv groupby -k region -a "count,max(amount_*)" data.csv  # max of columns matching amount_*
+  rsv groupby -k 0 -a "sum(1)" --export data.csv     # export result to data-groupby.csv
+  rsv groupby -k 0 -a "sum(1)" data.xlsx             # EXCEL file
+  rsv select -f 0=a data.csv | rsv groupby -k 1      # standard input
+
+Arguments:
+  <FILENAME>           File to open
+
+Options:
+  -s, --sep <SEP>      Separator [default: ,]
+  -q, --quote <QUOTE>  Quote Char [default: "]
+      --no-header      Whether the file has a header
+  -k, --keys <KEYS>    Key columns to group by [default: 0]
+  -a, --aggs <AGGS>    Aggregations [default: count]
+  -E, --export         Export result to a current-file-groupby.csv file
+  -S, --sheet <SHEET>  Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help           Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Aggregations, separated by commas:
+  count              -->    number of rows in the group
+  count(1)           -->    number of non-null values of column 1
+  countd(1)          -->    number of distinct non-null values of column 1
+  sum(1)             -->    sum of numeric values of column 1
+  mean(1)            -->    mean of numeric values of column 1
+  min(1), max(1)     -->    min or max, compared as strings if any value is not a number
+  median(1)          -->    median of numeric values of column 1
+
+Null values (empty, NA, NULL, etc.) are ignored by aggregations, and so are 
+non-numeric values by sum, mean and median, which are empty for a group without 
+numeric values.
+
+Column selection syntax:
+  -k 0,1             -->    cols [0,1]
+  -k 0-2             -->    cols [0,1,2]
+  -k -1              -->    last column
+  -k region,date     -->    columns by header name
+  -a "sum(amount_*)" -->    one aggregation for each column matching a glob pattern
+"#;
+
+pub const PIVOT_DESC: &str = r#"
+Crosstab a value column, with one row per distinct row key and one column per 
+distinct value of the column key. Chunks of the file are aggregated in parallel, 
+and partial aggregates are merged in the end. Rows are ordered by row keys and 
+columns by values of the column key.
+
+Usage: 
+  rsv.exe pivot [OPTIONS] <FILENAME>
+  rsv pivot data.csv                                 # count rows by columns 0 and 1
+  rsv pivot -r 0 -c 2 -v 5 -a sum data.csv           # sum of column 5
+  rsv pivot -r region -c product -v amount -a mean data.csv
+  rsv pivot -r 0,1 -c 2 -v 5 -a sum data.csv         # two row keys
+  rsv pivot -r 0 -c 2 -v 5 -a sum --totals data.csv  # append a total row and column
+  rsv pivot -r 0 -c 2 --percent row data.csv         # percentages of row totals
+  rsv pivot -r 0 -c 2 -v 5 -a sum --export data.csv  # export result to data-pivot.csv
+  rsv pivot -r 0 -c 2 -v 5 -a sum --xlsx data.csv    # export result to data-pivot.xlsx
+  rsv pivot -r 0 -c 2 data.xlsx                      # EXCEL file
+  rsv select -f 0=a data.csv | rsv pivot -r 1 -c 2   # standard input
+
+Arguments:
+  <FILENAME>               File to open
+
+Options:
+  -s, --sep <SEP>          Separator [default: ,]
+  -q, --quote <QUOTE>      Quote Char [default: "]
+      --no-header          Whether the file has a header
+  -r, --rows <ROWS>        Row key columns [default: 0]
+  -c, --cols <COLS>        Column key, whose distinct values become columns [default: 1]
+  -v, --value <VALUE>      Value column to aggregate [default: count rows]
+  -a, --agg <AGG>          Aggregation, count, sum, mean, min or max [default: count]
+  -t, --totals             Append a total column and a total row
+  -p, --percent <PERCENT>  Output percentages of the total, row or col totals
+  -E, --export             Export result to a current-file-pivot.csv file
+      --xlsx               Export result to a current-file-pivot.xlsx file
+  -S, --sheet <SHEET>      Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help               Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Cells without data are empty. Null values (empty, NA, NULL, etc.) are ignored 
+by aggregations, and so are non-numeric values by sum and mean. Totals are 
+aggregated from data rows, e.g., the total of mean is the mean of all values 
+rather than the mean of cells.
+
+Percentage modes, mostly for count and sum:
+  --percent total    -->    cells divided by the grand total
+  --percent row      -->    cells divided by the total of their row
+  --percent col      -->    cells divided by the total of their column
+
+Column selection syntax:
+  -r 0,1             -->    cols [0,1]
+  -r 0-2             -->    cols [0,1,2]
+  -c -1              -->    last column
+  -r region,date     -->    columns by header name
+"#;
+
+pub const MELT_DESC: &str = r#"
+Reshape wide data into long rows, i.e., one output row of id fields, a variable 
+and a value for each value column of a row, where the variable is the header 
+name of the value column. Chunks of the file are processed in parallel and 
+streamed to the output, so it works for large files.
+
+Usage: 
+  rsv.exe melt [OPTIONS] <FILENAME>
+  rsv melt --id 0,1 --value 2-40 data.csv     # ids of columns 0 and 1, values of columns 2 to 40
+  rsv melt --id id data.csv                   # values of all non-id columns
+  rsv melt --id id --value "q*" data.csv      # values of columns matching q*
+  rsv melt --id id --var-name question --value-name answer data.csv
+  rsv melt --id 0 --export data.csv           # export result to data-melted.csv
+  rsv melt --id 0 data.xlsx                   # EXCEL file
+  rsv slice -e 100 data.csv | rsv melt --id 0 # standard input
+
+Arguments:
+  <FILENAME>                    File to open
+
+Options:
+  -s, --sep <SEP>               Separator [default: ,]
+  -q, --quote <QUOTE>           Quote Char [default: "]
+      --no-header               Whether the file has a header
+  -i, --id <ID>                 Id columns kept in each output row [default: none]
+  -v, --value <VALUE>           Value columns to melt [default: all non-id columns]
+      --var-name <VAR_NAME>     Name of the variable column [default: variable]
+      --value-name <VALUE_NAME> Name of the value column [default: value]
+  -E, --export                  Export result to a current-file-melted.csv file
+  -S, --sheet <SHEET>           Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help                    Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Variables are named col0, col1, etc. for a file without a header.
+
+Column selection syntax:
+  -i 0,1             -->    cols [0,1]
+  -v 2-40            -->    cols [2,3,...,40]
+  -v -1              -->    last column
+  -i id,date         -->    columns by header name
+  -v "q*"            -->    columns matching a glob pattern
+"#;
+
+pub const UNMELT_DESC: &str = r#"
+Reshape long rows of id fields, a key and a value into wide data, i.e., the 
+reverse of melt. Each distinct key becomes an output column, in order of first 
+appearance, and adjacent rows of the same id are joined into one output row. 
+The file is read twice, once for keys and once for rows, and rows are streamed
+to the output, so it works for large files.
+
+Usage: 
+  rsv.exe unmelt [OPTIONS] <FILENAME>
+  rsv unmelt data.csv                         # keys in the second last column, values in the last
+  rsv unmelt --id 0,1 -k 2 -v 3 data.csv      # ids of columns 0 and 1
+  rsv unmelt -k question -v answer data.csv   # key and value columns by header name
+  rsv unmelt --export data.csv                # export result to data-unmelted.csv
+  rsv unmelt data.xlsx                        # EXCEL file
+  rsv melt --id 0 data.csv | rsv unmelt       # standard input, round trip of melt
+
+Arguments:
+  <FILENAME>               File to open
+
+Options:
+  -s, --sep <SEP>          Separator [default: ,]
+  -q, --quote <QUOTE>      Quote Char [default: "]
+      --no-header          Whether the file has a header
+  -i, --id <ID>            Id columns [default: all columns other than the key and value]
+  -k, --key <KEY>          Key column, whose distinct values become columns [default: -2]
+  -v, --value <VALUE>      Value column [default: -1]
+  -E, --export             Export result to a current-file-unmelted.csv file
+  -S, --sheet <SHEET>      Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help               Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Rows of an id are expected to be adjacent, e.g., the output of melt, or sorted 
+by ids; otherwise an id gets an output row for each run of adjacent rows. Keys 
+missing for an id are empty, and a repeated key of an id keeps the last value.
+Standard input is spilled to a temporary file, since keys are collected in a 
+first pass before output.
+
 Column selection syntax:
-  -c 0,1,2,5       -->    cols [0,1,2,5]
-  -c 0-2,5         -->    same as cols [0,1,2,5]
-  -c -1            -->    last column
-  -c -2--1         -->    last two columns
+  -i 0,1             -->    cols [0,1]
+  -i 0-2             -->    cols [0,1,2]
+  -k -2              -->    second last column
+  -k question        -->    column by header name
+"#;
+
+pub const TRANSPOSE_DESC: &str = r#"
+Turn rows into columns and columns into rows, i.e., the nth output row holds the 
+nth field of each input row, where the header becomes the first column. A file 
+that fits in memory is transposed at once, and a large file can be transposed 
+in multiple passes with a memory limit, where each pass reads the file for some 
+of the columns. Chunks of the file are processed in parallel.
+
+Usage: 
+  rsv.exe transpose [OPTIONS] <FILENAME>
+  rsv transpose data.csv                  # transpose in memory
+  rsv transpose --max-memory 500 data.csv # multiple passes within about 500MB
+  rsv transpose -s '\t' data.tsv          # TSV file
+  rsv transpose --export data.csv         # export result to data-transposed.csv
+  rsv transpose --xlsx data.csv           # export result to data-transposed.xlsx
+  rsv transpose --sheet 1 data.xlsx       # second sheet of EXCEL file
+  rsv slice -e 10 data.csv | rsv transpose  # standard input
+
+Arguments:
+  <FILENAME>                   File to open
+
+Options:
+  -s, --sep <SEP>              Separator [default: ,]
+  -q, --quote <QUOTE>          Quote Char [default: "]
+      --no-header              Whether the file has a header
+  -m, --max-memory <MB>        Memory limit in MB, to transpose in multiple passes
+  -E, --export                 Export result to a current-file-transposed.csv file
+      --xlsx                   Export result to a current-file-transposed.xlsx file
+  -S, --sheet <SHEET>          Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help                   Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Output rows follow the widest row, with or without a header, so no field is 
+lost and a shorter row is padded with empty fields. The memory of a pass is 
+estimated from a first pass over the input, and standard input is spilled to a 
+temporary file for the passes. An EXCEL output 
+can hold at most 16,384 columns, i.e., rows of the file.
+"#;
+
+pub const CAT_DESC: &str = r#"
+Concatenate files by rows or by columns. Files can be CSV, TXT, EXCEL, Parquet 
+or JSON files, or - for standard input, and rows are streamed to the output.
+
+  rows   -->  stack rows of files and write the header once, where columns are 
+              aligned by header names when the order differs, and a column 
+              missing in a file is filled with empty values. A repeated name 
+              is kept, and its columns are aligned by position
+  cols   -->  paste rows of files side by side, where a file with fewer rows 
+              or fields is padded with empty values
+
+Usage: 
+  rsv.exe cat [OPTIONS] <ACTION> <FILENAMES>...
+  rsv cat rows a.csv b.csv c.xlsx               # stack files, aligned by header names
+  rsv cat rows --source a.csv b.csv             # append a source_file column
+  rsv cat rows --source=file a.csv b.csv        # append a source column named file
+  rsv cat rows --no-header a.csv b.csv          # stack files as is
+  rsv cat rows daily/*.csv --export             # export result to cat.csv
+  rsv cat cols a.csv b.csv                      # paste files side by side
+  rsv select -f 0=a data.csv | rsv cat rows - b.csv  # standard input
+
+Arguments:
+  <ACTION>                 rows or cols
+  <FILENAMES>...           Files to concatenate, or - for standard input
+
+Options:
+  -s, --sep <SEP>          Separator [default: ,]
+  -q, --quote <QUOTE>      Quote Char [default: "]
+      --no-header          Whether files have a header
+      --source[=<NAME>]    Append a column of source file names to rows [default: source_file]
+  -E, --export             Export result to a cat.csv file in the current directory
+  -S, --sheet <SHEET>      Work for the nth worksheet of EXCEL files [default: 0]
+  -h, --help               Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+The output header of rows is the union of headers in order of first appearance. 
+The separator and quote char apply to all CSV and TXT files.
+"#;
+
+pub const DERIVE_DESC: &str = r#"
+Append or replace columns computed from expressions, evaluated for each row. 
+An expression named after an existing column replaces it, otherwise a new column 
+is appended. Chunks of the file are processed in parallel.
+
+Usage: 
+  rsv.exe derive [OPTIONS] --expr <EXPRS> <FILENAME>
+  rsv derive -e "total=c3*c4" data.csv               # append a total column
+  rsv derive -e "total=c3*c4" -e "ratio=c5/c6" data.csv
+  rsv derive -e "amount=round(amount*1.1, 2)" data.csv    # replace the amount column
+  rsv derive -e "sign=if(c2>0, 'pos', 'neg')" data.csv
+  rsv derive -e "key=concat(upper(region), '-', substr(date, 0, 4))" data.csv
+  rsv derive -e "due=date(date)+30" -e "days=date(end)-date(start)" data.csv
+  rsv derive -e "total=c3*c4" --export data.csv      # export to data-derived.csv
+  rsv derive -e "total=c3*c4" data.xlsx              # EXCEL file
+  rsv select -f 0=a data.csv | rsv derive -e "n=len(c1)"  # standard input
+
+Arguments:
+  <FILENAME>               File to open
+
+Options:
+  -s, --sep <SEP>          Separator [default: ,]
+  -q, --quote <QUOTE>      Quote Char [default: "]
+      --no-header          Whether the file has a header
+  -e, --expr <EXPRS>       Expression like name=expr, can be repeated
+  -E, --export             Export result to a current-file-derived.csv file
+  -S, --sheet <SHEET>      Work for the nth worksheet of EXCEL file [default: 0]
+  -h, --help               Print help information
+
+Output dialect:
+      --out-sep <SEP>          Output separator [default: the input separator]
+      --out-quote <QUOTE>      Output quote char [default: "]
+      --quote-style <STYLE>    Quote fields, minimal, all, non-numeric or never [default: minimal]
+      --escape-style <STYLE>   Escape quote chars, double or backslash [default: double]
+      --crlf                   End rows with CRLF rather than LF
+
+Columns:
+  c3 or @3           -->    column 3, counting from 0
+  amount             -->    column by header name
+  `unit price`       -->    column name with spaces or special chars
+
+Operators:
+  + - * / % ^        -->    arithmetic, ^ for power, e.g., 2^2^3 is 2^(2^3)
+  == != > >= < <=    -->    compared as numbers if both sides are numbers, else as strings
+  && || !            -->    logical and, or, not
+  'text' or "text"   -->    string literal; true, false and null are also literals
+
+Functions:
+  concat(a, b, ...)         upper(a)        lower(a)        trim(a)        len(a)
+  substr(a, start, len)     start counts from the end when negative, len is optional
+  replace(a, from, to)      if(cond, then, else)            coalesce(a, b, ...)
+  round(a, digits)          abs(a)
+  date(a, format)           parse a date by the format, e.g., %d/%m/%Y, where a value 
+                            not matching it is null, or inferred without a format
+  year(d)  month(d)  day(d)  date_format(d, "%Y/%m")
+
+Date arithmetic:
+  date(c1) + 7       -->    7 days after the date
+  date(c1) - date(c2) ->    days between two dates
+
+Invalid operations, e.g., a number multiplied by text, produce an empty field.
 "#;
@@ -1,9 +1,10 @@
 use crate::args::Size;
 use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::encoding::TextEncoding;
 use std::fs::File;
 
 impl Size {
-    pub fn csv_run(&self) -> CliResult {
+    pub fn csv_run(&self, _enc: TextEncoding) -> CliResult {
         let file = File::open(self.path())?;
         let filesize_bytes = file.metadata()?.len() as f64;
 
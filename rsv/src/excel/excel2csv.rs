@@ -1,10 +1,13 @@
 use crate::args::Excel2csv;
-use rsv_lib::utils::{cli_result::CliResult, reader::ExcelReader, util::werr_exit, writer::Writer};
+use rsv_lib::utils::encoding::TextEncoding;
+use rsv_lib::utils::{cli_result::CliResult, error::RsvError, reader::ExcelReader, writer::Writer};
 
 impl Excel2csv {
-    pub fn excel_run(&self) -> CliResult {
+    pub fn excel_run(&self, _enc: TextEncoding) -> CliResult {
         if self.filename.is_none() {
-            werr_exit!("Please provide a file path.");
+            return Err(RsvError::InvalidArgument(
+                "Please provide a file path.".to_owned(),
+            ));
         }
         let path = &self.path();
 
@@ -13,14 +16,12 @@ impl Excel2csv {
 
         // open files
         let range = ExcelReader::new(path, self.sheet)?;
-        let mut wtr = Writer::new(&out)?;
-
-        // const
-        let sep = [self.sep as u8];
+        let dialect = self.dialect.of(self.sep, self.quote);
+        let mut wtr = Writer::new(&out)?.dialect(dialect);
 
         // excel2csv
         for r in range.iter() {
-            wtr.write_excel_line(r, &sep)?;
+            wtr.write_excel_line(r)?;
         }
 
         println!("Saved to file: {}", out.display());
@@ -0,0 +1,45 @@
+use rsv_lib::utils::{cli_result::CliResult, error::RsvError};
+use std::{io::ErrorKind, process};
+
+// Exit codes by error category, so that shell scripts can tell a bad column
+// from a bad file. clap exits with 2 on invalid arguments as well.
+pub const OTHER: i32 = 1;
+pub const INVALID_ARGUMENT: i32 = 2;
+pub const IO: i32 = 3;
+pub const ENCODING: i32 = 4;
+pub const EXCEL: i32 = 5;
+pub const COLUMN: i32 = 6;
+pub const SYNTAX: i32 = 7;
+pub const BAD_NUMBER: i32 = 8;
+pub const VALIDATION: i32 = 9;
+
+pub fn exit_code(e: &RsvError) -> i32 {
+    match e {
+        RsvError::Other(_) => OTHER,
+        RsvError::InvalidArgument(_) => INVALID_ARGUMENT,
+        RsvError::Io(_) => IO,
+        RsvError::Encoding(_) => ENCODING,
+        RsvError::Excel(_) | RsvError::Parquet(_) | RsvError::Json(_) => EXCEL,
+        RsvError::ColumnNotFound(_) | RsvError::ColumnOutOfRange { .. } => COLUMN,
+        RsvError::FilterSyntax { .. } | RsvError::ExprSyntax(_) | RsvError::Regex(_) => SYNTAX,
+        RsvError::BadNumber(_) => BAD_NUMBER,
+        RsvError::Validation(_) => VALIDATION,
+    }
+}
+
+pub trait E {
+    fn handle_err(&self) {}
+}
+
+impl E for CliResult {
+    fn handle_err(&self) {
+        if let Err(e) = self {
+            // output closed by a downstream command, e.g., rsv sort | head
+            if matches!(e, RsvError::Io(e) if e.kind() == ErrorKind::BrokenPipe) {
+                process::exit(0);
+            }
+            eprintln!("Error: {e}");
+            process::exit(exit_code(e));
+        }
+    }
+}
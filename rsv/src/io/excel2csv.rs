@@ -1,11 +1,12 @@
 use crate::args::Excel2csv;
-use rsv_lib::utils::{cli_result::CliResult, util::werr_exit};
+use rsv_lib::utils::encoding::TextEncoding;
+use rsv_lib::utils::{cli_result::CliResult, error::RsvError};
 
 impl Excel2csv {
-    pub fn io_run(&self) -> CliResult {
-        werr_exit!(
-            "Error: File <{}> is not an excel file.",
+    pub fn io_run(&self, _enc: TextEncoding) -> CliResult {
+        Err(RsvError::InvalidArgument(format!(
+            "File <{}> is not an excel file.",
             self.path().display()
-        )
+        )))
     }
 }
@@ -1,20 +1,2 @@
-pub mod clean;
-pub mod count;
-pub mod estimate;
 pub mod excel2csv;
-pub mod flatten;
-pub mod frequency;
-pub mod head;
-pub mod headers;
-pub mod sample;
-pub mod search;
-pub mod select;
 pub mod size;
-pub mod slice;
-pub mod sort;
-pub mod split;
-pub mod stats;
-pub mod table;
-pub mod tail;
-pub mod to;
-pub mod unique;
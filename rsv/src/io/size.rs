@@ -1,9 +1,10 @@
 use crate::args::Size;
 use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::encoding::TextEncoding;
 use std::io::{Read, stdin};
 
 impl Size {
-    pub fn io_run(&self) -> CliResult {
+    pub fn io_run(&self, _enc: TextEncoding) -> CliResult {
         let mut buffer = [0; 1024]; // A buffer to read into
         let mut total_bytes_read = 0;
 
@@ -2,21 +2,31 @@ mod args;
 mod cmd_desc;
 mod csv;
 mod excel;
+mod exit_code;
 mod io;
+mod source;
 
 use args::{
-    Clean, Count, Estimate, Excel2csv, Flatten, Frequency, Head, Headers, Sample, Search, Select,
-    Size, Slice, Sort, Split, Stats, Table, Tail, To, Unique,
+    Cat, Clean, Count, Derive, Estimate, Excel2csv, Flatten, Frequency, Groupby, Head, Headers,
+    Join, Melt, Pivot, Sample, Schema, Search, Select, Size, Slice, Sort, Split, Stats, Table,
+    Tail, To, Transpose, Unique, Unmelt, Validate,
 };
 use clap::{Parser, Subcommand};
 use cmd_desc::{
-    CLEAN_DESC, COUNT_DESC, ESTIMATE_DESC, EXCEL2CSV_DESC, FLATTEN_DESC, FREQUENCY_DESC, HEAD_DESC,
-    HEADER_DESC, SAMPLE_DESC, SEARCH_DESC, SELECT_DESC, SLICE_DESC, SORT_DESC, SPLIT_DESC,
-    STATS_DESC, TABLE_DESC, TAIL_DESC, TO_DESC, UNIQUE_DESC,
+    CAT_DESC, CLEAN_DESC, COUNT_DESC, DERIVE_DESC, ESTIMATE_DESC, EXCEL2CSV_DESC, FLATTEN_DESC,
+    FREQUENCY_DESC, GROUPBY_DESC, HEAD_DESC, HEADER_DESC, JOIN_DESC, MELT_DESC, PIVOT_DESC,
+    SAMPLE_DESC, SCHEMA_DESC, SEARCH_DESC, SELECT_DESC, SLICE_DESC, SORT_DESC, SPLIT_DESC,
+    STATS_DESC, TABLE_DESC, TAIL_DESC, TO_DESC, TRANSPOSE_DESC, UNIQUE_DESC, UNMELT_DESC,
+    VALIDATE_DESC,
 };
 
 use cmd_desc::SIZE_DESC;
-use rsv_lib::utils::{cli_result::E, file::is_excel, filename::full_path};
+use exit_code::E;
+use rsv_lib::utils::{
+    encoding::TextEncoding,
+    file::{is_excel, is_sheet},
+    filename::full_path,
+};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -24,11 +34,15 @@ use std::path::PathBuf;
 #[command(author = "ribbondz@163.com")]
 #[command(version = "0.4.20")]
 #[command(
-    about = "A Rust command-line tool for handling both large and small CSV, TXT, and EXCEL files."
+    about = "A Rust command-line tool for handling both large and small CSV, TXT, EXCEL, Parquet and JSON files."
 )]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Encoding of input files and stdin, e.g., gbk, gb18030, latin1 or utf-16le,
+    /// detected by the BOM or the first 64KB by default
+    #[arg(long, global = true, default_value = "auto")]
+    encoding: TextEncoding,
 }
 
 #[derive(Subcommand)]
@@ -73,6 +87,16 @@ enum Commands {
         override_help = CLEAN_DESC
     )]
     Clean(Clean),
+    #[command(
+        about = "Check a file for bad rows, quotes, headers and line endings",
+        override_help = VALIDATE_DESC
+    )]
+    Validate(Validate),
+    #[command(
+        about = "Infer a schema file of column types and constraints",
+        override_help = SCHEMA_DESC
+    )]
+    Schema(Schema),
     #[command(
         about = "Frequency table for column(s)",
         override_help=FREQUENCY_DESC
@@ -119,7 +143,7 @@ enum Commands {
     )]
     Table(Table),
     #[command(
-        about = "Save data to disk, can be one of TXT, CSV, TSV, XLSX or XLS",
+        about = "Save data to disk, can be one of TXT, CSV, TSV, XLSX, XLS, PARQUET or JSON",
         override_help = TO_DESC
     )]
     To(To),
@@ -133,6 +157,50 @@ enum Commands {
         override_help = UNIQUE_DESC
     )]
     Unique(Unique),
+    #[command(
+        about = "Join two files on key column(s)",
+        override_help = JOIN_DESC
+    )]
+    Join(Join),
+    #[command(
+        about = "Aggregate value column(s) grouped by key column(s)",
+        override_help = GROUPBY_DESC
+    )]
+    Groupby(Groupby),
+    #[command(
+        about = "Crosstab a value column by row keys and a column key",
+        alias = "crosstab",
+        override_help = PIVOT_DESC
+    )]
+    Pivot(Pivot),
+    #[command(
+        about = "Reshape wide data into long rows of id, variable and value",
+        alias = "unpivot",
+        override_help = MELT_DESC
+    )]
+    Melt(Melt),
+    #[command(
+        about = "Reshape long rows of id, variable and value into wide data",
+        alias = "spread",
+        override_help = UNMELT_DESC
+    )]
+    Unmelt(Unmelt),
+    #[command(
+        about = "Turn rows into columns and columns into rows",
+        override_help = TRANSPOSE_DESC
+    )]
+    Transpose(Transpose),
+    #[command(
+        about = "Concatenate files by rows with header alignment, or by columns",
+        override_help = CAT_DESC
+    )]
+    Cat(Cat),
+    #[command(
+        about = "Append or replace columns computed from expressions",
+        alias = "mutate",
+        override_help = DERIVE_DESC
+    )]
+    Derive(Derive),
 }
 
 macro_rules! command_run {
@@ -143,40 +211,125 @@ macro_rules! command_run {
                 full_path(p)
             }
 
-            pub fn run(&self) {
+            pub fn run(&self, enc: TextEncoding) {
                 match &self.filename {
-                    Some(f) => match is_excel(&full_path(f)) {
-                        true => self.excel_run(),
-                        false => self.csv_run(),
+                    Some(f) => match is_sheet(&full_path(f)) {
+                        true => self.excel_run(enc),
+                        false => self.csv_run(enc),
                     },
-                    None => self.io_run(),
+                    None => self.io_run(enc),
                 }
                 .handle_err()
             }
         }
     };
+
+    // commands written once for all inputs, i.e., files, sheets and stdin
+    ($cmd:ident, source) => {
+        impl $cmd {
+            pub fn run(&self, enc: TextEncoding) {
+                let path = self.filename.as_ref().map(|f| full_path(f));
+                rsv_lib::utils::source::open(path.as_deref(), self.sep, self.quote, self.sheet, enc)
+                    .and_then(|src| self.source_run(path.as_deref(), src))
+                    .handle_err()
+            }
+        }
+    };
+
+    // commands written once for all inputs, which also read other files in the encoding
+    ($cmd:ident, source, enc) => {
+        impl $cmd {
+            pub fn run(&self, enc: TextEncoding) {
+                let path = self.filename.as_ref().map(|f| full_path(f));
+                rsv_lib::utils::source::open(path.as_deref(), self.sep, self.quote, self.sheet, enc)
+                    .and_then(|src| self.source_run(path.as_deref(), src, enc))
+                    .handle_err()
+            }
+        }
+    };
+
+    // commands of several inputs, where - is stdin
+    ($cmd:ident, sources) => {
+        impl $cmd {
+            pub fn run(&self, enc: TextEncoding) {
+                let paths = self
+                    .filenames
+                    .iter()
+                    .map(|f| (f != "-").then(|| full_path(f)))
+                    .collect::<Vec<_>>();
+                self.sources_run(&paths, enc).handle_err()
+            }
+        }
+    };
 }
 
-command_run!(Count);
-command_run!(Estimate);
-command_run!(Head);
-command_run!(Tail);
-command_run!(Headers);
-command_run!(Clean);
-command_run!(Flatten);
-command_run!(Frequency);
-command_run!(Split);
-command_run!(Slice);
-command_run!(Select);
-command_run!(Stats);
+command_run!(Estimate, source, enc);
+command_run!(Head, source);
+command_run!(Tail, source);
+command_run!(Headers, source);
+command_run!(Clean, source);
+command_run!(Schema, source);
+command_run!(Flatten, source);
+command_run!(Frequency, source);
+command_run!(Split, source);
+command_run!(Slice, source);
+command_run!(Select, source);
+command_run!(Stats, source);
 command_run!(Excel2csv);
-command_run!(Table);
-command_run!(Sort);
-command_run!(Search);
-command_run!(Sample);
-command_run!(To);
-command_run!(Unique);
+command_run!(Table, source);
+command_run!(Sort, source);
+command_run!(Sample, source);
+command_run!(To, source);
+command_run!(Unique, source);
 command_run!(Size);
+command_run!(Join, source, enc);
+command_run!(Groupby, source);
+command_run!(Pivot, source);
+command_run!(Melt, source);
+command_run!(Unmelt, source);
+command_run!(Transpose, source);
+command_run!(Cat, sources);
+command_run!(Derive, source);
+
+// count also counts files in a directory
+impl Count {
+    pub fn run(&self, enc: TextEncoding) {
+        let path = self.filename.as_ref().map(|f| full_path(f));
+        match path.as_deref() {
+            Some(p) if p.is_dir() => self.dir_run(p),
+            _ => {
+                rsv_lib::utils::source::open(path.as_deref(), self.sep, self.quote, self.sheet, enc)
+                    .and_then(|src| self.source_run(path.as_deref(), src))
+            }
+        }
+        .handle_err()
+    }
+}
+
+// validate reads raw bytes of a file or stdin
+impl Validate {
+    pub fn run(&self, enc: TextEncoding) {
+        let path = self.filename.as_ref().map(|f| full_path(f));
+        self.bytes_run(path.as_deref(), enc).handle_err()
+    }
+}
+
+// search runs on all sheets of an EXCEL file with -S all
+impl Search {
+    pub fn run(&self, enc: TextEncoding) {
+        let path = self.filename.as_ref().map(|f| full_path(f));
+        match path.as_deref() {
+            Some(p) if is_excel(p) && self.sheet == "all" => self.sheets_run(p),
+            _ => self
+                .sheet_index()
+                .and_then(|sheet| {
+                    rsv_lib::utils::source::open(path.as_deref(), self.sep, self.quote, sheet, enc)
+                })
+                .and_then(|src| self.source_run(path.as_deref(), src)),
+        }
+        .handle_err()
+    }
+}
 
 fn main() {
     let cli = Cli::parse();
@@ -184,25 +337,35 @@ fn main() {
     // You can check for the existence of subcommands, and if found use their
     // matches just as you would the top level cmd
     match &cli.command {
-        Commands::Count(count) => count.run(),
-        Commands::Size(size) => size.run(),
-        Commands::Estimate(estimate) => estimate.run(),
-        Commands::Head(head) => head.run(),
-        Commands::Tail(tail) => tail.run(),
-        Commands::Headers(headers) => headers.run(),
-        Commands::Clean(clean) => clean.run(),
-        Commands::Frequency(frequency) => frequency.run(),
-        Commands::Split(split) => split.run(),
-        Commands::Select(select) => select.run(),
-        Commands::Flatten(flatten) => flatten.run(),
-        Commands::Slice(slice) => slice.run(),
-        Commands::Stats(stats) => stats.run(),
-        Commands::Excel2csv(excel2csv) => excel2csv.run(),
-        Commands::Table(table) => table.run(),
-        Commands::Search(search) => search.run(),
-        Commands::Sort(sort) => sort.run(),
-        Commands::To(to) => to.run(),
-        Commands::Sample(sample) => sample.run(),
-        Commands::Unique(unique) => unique.run(),
+        Commands::Count(count) => count.run(cli.encoding),
+        Commands::Size(size) => size.run(cli.encoding),
+        Commands::Estimate(estimate) => estimate.run(cli.encoding),
+        Commands::Head(head) => head.run(cli.encoding),
+        Commands::Tail(tail) => tail.run(cli.encoding),
+        Commands::Headers(headers) => headers.run(cli.encoding),
+        Commands::Clean(clean) => clean.run(cli.encoding),
+        Commands::Validate(validate) => validate.run(cli.encoding),
+        Commands::Schema(schema) => schema.run(cli.encoding),
+        Commands::Frequency(frequency) => frequency.run(cli.encoding),
+        Commands::Split(split) => split.run(cli.encoding),
+        Commands::Select(select) => select.run(cli.encoding),
+        Commands::Flatten(flatten) => flatten.run(cli.encoding),
+        Commands::Slice(slice) => slice.run(cli.encoding),
+        Commands::Stats(stats) => stats.run(cli.encoding),
+        Commands::Excel2csv(excel2csv) => excel2csv.run(cli.encoding),
+        Commands::Table(table) => table.run(cli.encoding),
+        Commands::Search(search) => search.run(cli.encoding),
+        Commands::Sort(sort) => sort.run(cli.encoding),
+        Commands::To(to) => to.run(cli.encoding),
+        Commands::Sample(sample) => sample.run(cli.encoding),
+        Commands::Unique(unique) => unique.run(cli.encoding),
+        Commands::Join(join) => join.run(cli.encoding),
+        Commands::Groupby(groupby) => groupby.run(cli.encoding),
+        Commands::Pivot(pivot) => pivot.run(cli.encoding),
+        Commands::Melt(melt) => melt.run(cli.encoding),
+        Commands::Unmelt(unmelt) => unmelt.run(cli.encoding),
+        Commands::Transpose(transpose) => transpose.run(cli.encoding),
+        Commands::Cat(cat) => cat.run(cli.encoding),
+        Commands::Derive(derive) => derive.run(cli.encoding),
     }
 }
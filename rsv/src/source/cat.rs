@@ -0,0 +1,125 @@
+use crate::args::Cat;
+use rsv_lib::utils::cat::ColumnAligner;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::encoding::TextEncoding;
+use rsv_lib::utils::error::{RsvError, RsvResult};
+use rsv_lib::utils::filename::new_file;
+use rsv_lib::utils::source::{self, RecordSource};
+use rsv_lib::utils::writer::Writer;
+use std::io;
+use std::path::PathBuf;
+
+impl Cat {
+    pub fn sources_run(&self, paths: &[Option<PathBuf>], enc: TextEncoding) -> CliResult {
+        if self.action == "cols" && self.source.is_some() {
+            return Err(RsvError::InvalidArgument(
+                "--source only works with cat rows.".to_owned(),
+            ));
+        }
+
+        // an error of a file is reported with its name, as files are many
+        let mut sources = paths
+            .iter()
+            .zip(&self.filenames)
+            .map(|(p, f)| {
+                source::open(p.as_deref(), self.sep, self.quote, self.sheet, enc).map_err(|e| {
+                    match e {
+                        RsvError::Io(e) => {
+                            RsvError::Io(io::Error::new(e.kind(), format!("{f}: {e}")))
+                        }
+                        e => e,
+                    }
+                })
+            })
+            .collect::<RsvResult<Vec<_>>>()?;
+
+        let out = new_file("cat.csv");
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?
+            .dialect(self.dialect.of(self.sep, self.quote));
+        match self.action.as_str() {
+            "rows" => self.cat_rows(&mut sources, &mut wtr)?,
+            _ => self.cat_cols(&mut sources, &mut wtr)?,
+        }
+
+        if self.export {
+            println!("Saved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+
+    // stack rows of files, with columns aligned by header names
+    fn cat_rows(&self, sources: &mut [Box<dyn RecordSource>], wtr: &mut Writer) -> CliResult {
+        let aligner = match self.no_header {
+            true => None,
+            false => {
+                let headers = sources
+                    .iter_mut()
+                    .map(|s| s.next_row().transpose().map(Option::unwrap_or_default))
+                    .collect::<io::Result<Vec<_>>>()?;
+                Some(ColumnAligner::new(&headers))
+            }
+        };
+
+        if let Some(a) = &aligner {
+            let mut header = a.header().to_vec();
+            header.extend(self.source.clone());
+            wtr.write_fields_unchecked(&header);
+        }
+
+        for (n, src) in sources.iter_mut().enumerate() {
+            let name = match self.filenames[n].as_str() {
+                "-" => "stdin",
+                f => f,
+            };
+            while let Some(r) = src.next_line() {
+                let r = r?;
+                let row = src.split(&r).collect::<Vec<_>>();
+                let mut row = match &aligner {
+                    Some(a) => a.align(n, &row),
+                    None => row.iter().map(|f| f.as_ref()).collect(),
+                };
+                if self.source.is_some() {
+                    row.push(name);
+                }
+                wtr.write_fields_unchecked(&row);
+            }
+        }
+
+        Ok(())
+    }
+
+    // paste rows of files side by side, where a file with fewer rows or fields is
+    // padded to the width of its first row
+    fn cat_cols(&self, sources: &mut [Box<dyn RecordSource>], wtr: &mut Writer) -> CliResult {
+        let mut widths = vec![0; sources.len()];
+        let mut first = true;
+
+        loop {
+            let mut row = vec![];
+            let mut done = true;
+            for (src, width) in sources.iter_mut().zip(widths.iter_mut()) {
+                match src.next_row() {
+                    Some(r) => {
+                        let mut r = r?;
+                        if first {
+                            *width = r.len();
+                        }
+                        r.resize(r.len().max(*width), String::new());
+                        row.extend(r);
+                        done = false;
+                    }
+                    None => row.extend(vec![String::new(); *width]),
+                }
+            }
+            if done {
+                break;
+            }
+
+            wtr.write_fields_unchecked(&row);
+            first = false;
+        }
+
+        Ok(())
+    }
+}
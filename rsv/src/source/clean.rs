@@ -0,0 +1,48 @@
+use crate::args::Clean;
+use regex::Regex;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::{Path, PathBuf};
+
+impl Clean {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        // new file, or stdout for stdin without an output file
+        let out = match self.output.is_empty() {
+            true => export_path(path, "cleaned"),
+            false => PathBuf::from(&self.output),
+        };
+        let export = path.is_some() || !self.output.is_empty();
+        let mut wtr = Writer::file_or_stdout(export, &out)?;
+
+        // progress
+        let mut prog = Progress::new();
+
+        // copy
+        let re = Regex::new(&self.escape)?;
+        let mut i = 0;
+        while let Some(r) = src.next_line() {
+            let r = r?;
+            wtr.write_str(re.replace_all(&r, ""))?;
+
+            // progress print
+            if export {
+                prog.add_bytes(r.len() + 1);
+                if i % 50_000 == 0 {
+                    prog.add_chunks(1);
+                    prog.print();
+                }
+            }
+
+            i += 1;
+        }
+
+        if export {
+            prog.print();
+            println!("\nSaved to file: {}", out.display());
+        }
+
+        Ok(())
+    }
+}
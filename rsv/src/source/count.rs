@@ -0,0 +1,54 @@
+use crate::args::Count;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::source::RecordSource;
+use std::path::Path;
+
+impl Count {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        // progress
+        let mut prog = Progress::new();
+
+        // records rather than lines, as a quoted field can span lines
+        let mut n = 0;
+        while let Some(r) = src.next_line() {
+            r?;
+            n += 1;
+        }
+
+        if !self.no_header && n > 0 {
+            n -= 1;
+        }
+
+        println!("{n}");
+        if path.is_some() {
+            prog.print_elapsed_time();
+        }
+
+        Ok(())
+    }
+
+    pub fn dir_run(&self, path: &Path) -> CliResult {
+        let mut file_n = 0;
+        let mut dir_n = 0;
+
+        path.read_dir()?.for_each(|i| {
+            if let Ok(e) = i {
+                if e.file_type().unwrap().is_file() {
+                    file_n += 1;
+                } else {
+                    dir_n += 1;
+                }
+            }
+        });
+
+        println!(
+            "{} files and {} sub-directories in {}",
+            file_n,
+            dir_n,
+            path.display()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::args::Derive;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::derive::Deriver;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::borrow::Cow;
+use std::path::Path;
+use std::thread;
+
+impl Derive {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // header
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+
+        // expressions
+        let names = match self.no_header {
+            true => (0..first.len()).map(|i| format!("col{i}")).collect(),
+            false => first.clone(),
+        };
+        let deriver = Deriver::new(&self.exprs, names)?;
+
+        // wtr
+        let out = export_path(path, "derived");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+        match self.no_header {
+            true => wtr.write_fields_unchecked(&deriver.derive(&first)),
+            false => wtr.write_fields_unchecked(deriver.header()),
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // process
+        let mut prog = Progress::new();
+        for task in rx {
+            let lines = task
+                .lines
+                .par_iter()
+                .map(|r| {
+                    let r = CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>();
+                    let d = deriver.derive(&r);
+                    d.into_iter().map(Cow::into_owned).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            wtr.write_fields_of_lines_unchecked(&lines);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::args::{Count, Estimate};
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::compress::{self, Codec};
+use rsv_lib::utils::constants::MB_USIZE;
+use rsv_lib::utils::encoding::TextEncoding;
+use rsv_lib::utils::file::is_sheet;
+use rsv_lib::utils::reader::RecordReader;
+use rsv_lib::utils::source::RecordSource;
+use std::fs::metadata;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+impl Estimate {
+    pub fn source_run(
+        &self,
+        path: Option<&Path>,
+        src: Box<dyn RecordSource>,
+        enc: TextEncoding,
+    ) -> CliResult {
+        // sheets and stdin are counted, as their sizes tell little about rows
+        let Some(path) = path.filter(|p| !is_sheet(p)) else {
+            let count = Count {
+                filename: self.filename.clone(),
+                sep: self.sep,
+                quote: self.quote,
+                no_header: false,
+                sheet: self.sheet,
+            };
+            return count.source_run(path, src);
+        };
+
+        // read 20000 lines to estimate bytes per line
+        let mut filesize = metadata(path)?.len() as f64;
+        let compressed = Codec::detect(path)?.is_some();
+        let (file, consumed) = compress::open_counted(path, enc)?;
+
+        let mut total_bytes = 0;
+        let mut n = 0;
+        for l in RecordReader::new(BufReader::new(file), self.sep, self.quote).skip(1) {
+            total_bytes += l?.len() + 1;
+            n += 1;
+            // a compressed file is read for at least 16MB, so that data buffered by
+            // the decoder has little effect on the compression ratio
+            if n > 20000 && (!compressed || total_bytes > 16 * MB_USIZE) {
+                break;
+            }
+        }
+
+        // the size of a compressed file is scaled up by the ratio of decompressed
+        // bytes to compressed bytes read so far
+        let consumed = consumed.load(Ordering::Relaxed) as f64;
+        if compressed && consumed > 0.0 {
+            filesize *= total_bytes as f64 / consumed;
+        }
+
+        // estimate line count
+        let mut estimate_n = filesize / ((total_bytes as f64) / (n as f64));
+
+        // default to have a header
+        if estimate_n > 1.0 {
+            estimate_n -= 1.0;
+        }
+
+        println!("{}", estimate_n as usize);
+
+        Ok(())
+    }
+}
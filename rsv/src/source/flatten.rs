@@ -0,0 +1,45 @@
+use crate::args::Flatten;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::source::RecordSource;
+use rsv_lib::utils::table::Table;
+use std::path::Path;
+
+impl Flatten {
+    pub fn source_run(&self, _: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+
+        // columns, and the first row is a record when there is no header
+        let (columns, mut first) = match self.no_header {
+            true => (
+                (1..=first.len()).map(|i| format!("col{i}")).collect(),
+                Some(first),
+            ),
+            false => (first, None),
+        };
+
+        // read file
+        let n = self.n as usize; // overflow is allowed when self.n is negative.
+        for i in 0..n {
+            let r = match first.take() {
+                Some(r) => r,
+                None => match src.next_row() {
+                    Some(r) => r?,
+                    None => break,
+                },
+            };
+
+            println!(" {} row{}", &self.delimiter, i + 1);
+            let r = r
+                .iter()
+                .zip(&columns)
+                .map(|(v, k)| [k.as_str(), v.as_str()])
+                .collect::<Vec<_>>();
+            Table::from_records(r).print_blank().unwrap();
+        }
+
+        Ok(())
+    }
+}
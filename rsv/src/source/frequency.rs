@@ -0,0 +1,96 @@
+use crate::args::Frequency;
+use crossbeam_channel::bounded;
+use dashmap::DashMap;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::file;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::util::sort_frequency;
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+impl Frequency {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // the first row is needed to resolve column names
+        let Some(first) = src.next_line() else {
+            return Ok(());
+        };
+        let first = first?;
+        let col = Columns::new(&self.cols)
+            .header(src.split(&first).collect_owned())
+            .parse()?;
+
+        // count a row
+        let freq = DashMap::new();
+        let bad_rows = AtomicUsize::new(0);
+        let count = |r: &str| {
+            let r = CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>();
+            if col.max >= r.len() {
+                bad_rows.fetch_add(1, Ordering::Relaxed);
+            } else {
+                let r = col.select_owned_vector(&r);
+                *freq.entry(r).or_insert(0) += 1;
+            }
+        };
+
+        // header
+        let names: Vec<String> = if self.no_header {
+            count(&first);
+            col.artificial_cols_with_appended_n()
+        } else {
+            let r = src.split(&first).collect::<Vec<_>>();
+            if col.max >= r.len() {
+                bad_rows.fetch_add(1, Ordering::Relaxed);
+                col.artificial_cols_with_appended_n()
+            } else {
+                col.select_owned_vector_and_append_n(&r)
+            }
+        };
+
+        // read by chunks
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // process
+        let mut prog = Progress::new();
+        for task in rx {
+            task.lines.par_iter().for_each(|r| count(r));
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        let bad_rows = bad_rows.into_inner();
+        if bad_rows > 0 {
+            eprintln!("[info] bad lines ignored: {bad_rows}");
+        }
+
+        // apply head n
+        let mut freq = freq.into_iter().collect::<Vec<(_, _)>>();
+        let n = (self.n > 0).then_some(self.n as usize);
+        sort_frequency(&mut freq, self.ascending, n);
+
+        // export or print, where an error of a closed pipe is ignored
+        let out = export_path(path, "frequency");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+        let r = file::write_frequency(&mut wtr, &names, freq);
+        if self.export {
+            r?;
+            println!("\nSaved to file: {}", out.display());
+        }
+
+        Ok(())
+    }
+}
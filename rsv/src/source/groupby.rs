@@ -0,0 +1,94 @@
+use crate::args::Groupby;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::ColumnResolver;
+use rsv_lib::utils::groupby::GroupBy;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Groupby {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // header
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+
+        // keys and aggregations
+        let mut resolver = match self.no_header {
+            true => ColumnResolver::from_total(first.len()),
+            false => ColumnResolver::from_names(first.clone()),
+        };
+        let mut group = GroupBy::new(&self.keys, &self.aggs, &mut resolver)?;
+        let names = match self.no_header {
+            true => {
+                group.parse_line_by_fields(&first);
+                vec![]
+            }
+            false => first,
+        };
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // partial aggregates of chunks are merged into the total
+        let mut prog = Progress::new();
+        for task in rx {
+            let partial = task
+                .lines
+                .par_chunks(1_000)
+                .map(|lines| {
+                    let mut g = group.empty();
+                    lines.iter().for_each(|l| {
+                        g.parse_line_by_fields(
+                            &CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>(),
+                        )
+                    });
+                    g
+                })
+                .reduce(
+                    || group.empty(),
+                    |mut a, b| {
+                        a.merge(b);
+                        a
+                    },
+                );
+            group.merge(partial);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        if group.bad_rows() > 0 {
+            eprintln!("[info] bad lines ignored: {}", group.bad_rows());
+        }
+
+        // write
+        let out = export_path(path, "groupby");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+        wtr.write_fields_unchecked(&group.header(&names));
+        group
+            .into_rows()
+            .iter()
+            .for_each(|r| wtr.write_fields_unchecked(r));
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::args::Head;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+
+impl Head {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let out = export_path(path, "head");
+        let dialect = self.dialect.of(src.sep(), src.quote());
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?.dialect(dialect);
+
+        // show head n
+        for _ in 0..self.n + 1 - self.no_header as usize {
+            let Some(r) = src.next_line() else { break };
+            wtr.write_str_unchecked(r?);
+        }
+
+        if self.export {
+            println!("Saved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
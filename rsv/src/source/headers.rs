@@ -0,0 +1,16 @@
+use crate::args::Headers;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::source::RecordSource;
+use std::path::Path;
+
+impl Headers {
+    pub fn source_run(&self, _: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        if let Some(r) = src.next_row() {
+            r?.iter()
+                .enumerate()
+                .for_each(|(i, v)| println!(" {i:<5}{v}"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use crate::args::Join;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::constants::MB_USIZE;
+use rsv_lib::utils::encoding::TextEncoding;
+use rsv_lib::utils::filename::{TempDir, full_path};
+use rsv_lib::utils::join::{
+    HashJoinTable, JoinFile, JoinStrategy, JoinType, Joiner, sort_records_to_file,
+};
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::iter;
+use std::path::Path;
+use std::thread;
+
+impl Join {
+    pub fn source_run(
+        &self,
+        path: Option<&Path>,
+        mut src: Box<dyn RecordSource>,
+        enc: TextEncoding,
+    ) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+        let right_path = full_path(&self.right);
+
+        // wtr
+        let out = export_path(path, "joined");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+
+        // left header and the right file
+        let Some(first) = src.next_line() else {
+            return Ok(());
+        };
+        let first = first?;
+        let left_names = src.split(&first).collect_owned();
+        let right = JoinFile {
+            path: &right_path,
+            sep: self.sep,
+            quote: self.quote,
+            enc,
+            sheet: self.right_sheet,
+            no_header: self.no_header,
+        };
+        let right_names = right.header()?;
+
+        // keys
+        let how = self.how.parse::<JoinType>()?;
+        let right_keys = self.right_keys.as_deref().unwrap_or(&self.keys);
+        let joiner = Joiner::new(
+            how,
+            &self.keys,
+            right_keys,
+            left_names.clone(),
+            right_names.clone(),
+        )?;
+
+        // header, or the first record when there is no header
+        let first = match self.no_header {
+            true => Some(first),
+            false => {
+                wtr.write_fields_unchecked(&joiner.header(&left_names, &right_names));
+                None
+            }
+        };
+
+        // join
+        let budget = self.memory * MB_USIZE;
+        match self.strategy.parse::<JoinStrategy>()? {
+            JoinStrategy::Hash => self.hash_join(&joiner, &right, first, src, &mut wtr)?,
+            JoinStrategy::Auto if right.fits_in(budget) => {
+                self.hash_join(&joiner, &right, first, src, &mut wtr)?
+            }
+            _ => {
+                let records = first
+                    .map(Ok)
+                    .into_iter()
+                    .chain(iter::from_fn(|| src.next_line()));
+                let dir = TempDir::new("rsv-join")?;
+                let l = sort_records_to_file(
+                    records,
+                    joiner.left_sort(),
+                    sep,
+                    quote,
+                    budget,
+                    &dir.file("left.csv"),
+                )?;
+                let r = right.sorted_rows(joiner.right_sort(), budget, &dir.file("right.csv"))?;
+                joiner.merge_join(l, r, |row| wtr.write_fields_unchecked(&row));
+            }
+        }
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+
+    // probe a hash table of the right file with records of the source in parallel
+    fn hash_join(
+        &self,
+        joiner: &Joiner,
+        right: &JoinFile,
+        first: Option<String>,
+        mut src: Box<dyn RecordSource>,
+        wtr: &mut Writer,
+    ) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+        let table = HashJoinTable::new(joiner, right.rows()?);
+        let probe = |lines: &[String], wtr: &mut Writer| {
+            lines
+                .par_iter()
+                .map(|r| table.probe(&CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>()))
+                .collect::<Vec<_>>()
+                .iter()
+                .flatten()
+                .for_each(|r| wtr.write_fields_unchecked(r))
+        };
+
+        if let Some(first) = first {
+            probe(&[first], wtr);
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        let mut prog = Progress::new();
+        for task in rx {
+            probe(&task.lines, wtr);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        // rows only in the right file
+        table
+            .unmatched()
+            .iter()
+            .for_each(|r| wtr.write_fields_unchecked(r));
+
+        Ok(())
+    }
+}
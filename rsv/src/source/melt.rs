@@ -0,0 +1,78 @@
+use crate::args::Melt;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::melt::Melter;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Melt {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // header
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+        let names = match self.no_header {
+            true => (0..first.len()).map(|i| format!("col{i}")).collect(),
+            false => first.clone(),
+        };
+
+        // id and value columns
+        let melter = Melter::new(&self.id, &self.value, names)?;
+
+        // wtr
+        let out = export_path(path, "melted");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+        wtr.write_fields_unchecked(&melter.header(&self.var_name, &self.value_name));
+
+        let mut bad_rows = 0;
+        if self.no_header {
+            match melter.melt(&first) {
+                Some(lines) => wtr.write_fields_of_lines_unchecked(&lines),
+                None => bad_rows += 1,
+            }
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // process
+        let mut prog = Progress::new();
+        for task in rx {
+            let melted = task
+                .lines
+                .par_iter()
+                .map(|r| melter.melt(&CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>()))
+                .collect::<Vec<_>>();
+            bad_rows += melted.iter().filter(|m| m.is_none()).count();
+            let lines = melted.into_iter().flatten().flatten().collect::<Vec<_>>();
+            wtr.write_fields_of_lines_unchecked(&lines);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        if bad_rows > 0 {
+            eprintln!("[info] bad lines ignored: {bad_rows}");
+        }
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+pub mod cat;
+pub mod clean;
+pub mod count;
+pub mod derive;
+pub mod estimate;
+pub mod flatten;
+pub mod frequency;
+pub mod groupby;
+pub mod head;
+pub mod headers;
+pub mod join;
+pub mod melt;
+pub mod pivot;
+pub mod sample;
+pub mod schema;
+pub mod search;
+pub mod select;
+pub mod slice;
+pub mod sort;
+pub mod split;
+pub mod stats;
+pub mod table;
+pub mod tail;
+pub mod to;
+pub mod transpose;
+pub mod unique;
+pub mod unmelt;
+pub mod validate;
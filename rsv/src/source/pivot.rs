@@ -0,0 +1,120 @@
+use crate::args::Pivot;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::ColumnResolver;
+use rsv_lib::utils::pivot;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::to::write_excel_rows;
+use rsv_lib::utils::writer::{Dialect, Writer};
+use std::path::Path;
+use std::thread;
+
+impl Pivot {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // header
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+
+        // row keys, column key and value
+        let mut resolver = match self.no_header {
+            true => ColumnResolver::from_total(first.len()),
+            false => ColumnResolver::from_names(first.clone()),
+        };
+        let mut table = pivot::Pivot::new(
+            &self.rows,
+            &self.cols,
+            self.value.as_deref(),
+            self.agg,
+            &mut resolver,
+        )?;
+        let names = match self.no_header {
+            true => {
+                table.parse_line_by_fields(&first);
+                vec![]
+            }
+            false => first,
+        };
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // partial aggregates of chunks are merged into the total
+        let mut prog = Progress::new();
+        let export = self.export || self.xlsx;
+        for task in rx {
+            let partial = task
+                .lines
+                .par_chunks(1_000)
+                .map(|lines| {
+                    let mut p = table.empty();
+                    lines.iter().for_each(|l| {
+                        p.parse_line_by_fields(
+                            &CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>(),
+                        )
+                    });
+                    p
+                })
+                .reduce(
+                    || table.empty(),
+                    |mut a, b| {
+                        a.merge(b);
+                        a
+                    },
+                );
+            table.merge(partial);
+
+            if export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+        if export {
+            println!();
+        }
+
+        if table.bad_rows() > 0 {
+            eprintln!("[info] bad lines ignored: {}", table.bad_rows());
+        }
+
+        // write
+        let (header, rows) = table.into_table(&names, self.totals, self.percent);
+        let out = export_path(path, "pivot");
+        self.write_table(&header, &rows, &out, self.dialect.of(sep, quote))
+    }
+
+    // write to stdout, or export to a csv or an excel file
+    fn write_table(
+        &self,
+        header: &[String],
+        rows: &[Vec<String>],
+        out: &Path,
+        dialect: Dialect,
+    ) -> CliResult {
+        if self.xlsx {
+            let out = out.with_extension("xlsx");
+            write_excel_rows(&out, header, rows)?;
+            println!("Saved to file: {}", out.display());
+            return Ok(());
+        }
+
+        let mut wtr = Writer::file_or_stdout(self.export, out)?.dialect(dialect);
+        wtr.write_fields_unchecked(header);
+        rows.iter().for_each(|r| wtr.write_fields_unchecked(r));
+
+        if self.export {
+            println!("Saved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::args::Sample;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::priority_queue::Sampler;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::table::Table;
+use rsv_lib::utils::writer::Writer;
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::Instant;
+
+impl Sample {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let time_limit = (self.time_limit - 0.7).clamp(0.0, f32::MAX);
+
+        // header
+        let header = match self.no_header {
+            true => None,
+            false => match src.next_line() {
+                Some(r) => Some(r?),
+                None => return Ok(()),
+            },
+        };
+
+        // read
+        let mut sampler = Sampler::new(self.n, self.seed.map(|s| s as u64));
+        let time = Instant::now();
+        while let Some(r) = src.next_line() {
+            sampler.push(r?);
+
+            let line_n = sampler.pushed();
+            if time_limit > 0.0 && line_n % 10000 == 0 && time.elapsed().as_secs_f32() >= time_limit
+            {
+                break;
+            }
+        }
+
+        match (self.export, self.show_number) {
+            (true, _) => write_to_file(path, header, sampler)?,
+            (false, true) => print_to_stdout(header, sampler),
+            (false, false) => print_to_stdout_no_number(header, sampler)?,
+        }
+
+        Ok(())
+    }
+}
+
+fn write_to_file(
+    path: Option<&Path>,
+    header: Option<String>,
+    sampler: Sampler<String>,
+) -> CliResult {
+    // new file
+    let out = export_path(path, "sampled");
+    let mut wtr = Writer::new(&out)?;
+    if let Some(r) = header {
+        wtr.write_str_unchecked(r);
+    }
+    for r in sampler.into_sorted_items() {
+        wtr.write_str_unchecked(&r.item);
+    }
+
+    println!("Saved to file: {}", out.display());
+
+    Ok(())
+}
+
+fn print_to_stdout(header: Option<String>, sampler: Sampler<String>) {
+    let mut table = Table::new();
+
+    // header
+    if let Some(h) = header {
+        table.add_record([Cow::Borrowed("#"), Cow::Borrowed(""), Cow::from(h)]);
+    }
+
+    // samples
+    sampler.into_sorted_items().into_iter().for_each(|i| {
+        table.add_record([
+            Cow::from(i.line_n_as_string()),
+            Cow::Borrowed("->"),
+            Cow::from(i.item),
+        ])
+    });
+
+    table.print_blank_unchecked();
+}
+
+fn print_to_stdout_no_number(header: Option<String>, sampler: Sampler<String>) -> CliResult {
+    let mut wtr = Writer::stdout()?;
+
+    // header
+    if let Some(h) = header {
+        wtr.write_str_unchecked(h);
+    }
+
+    // samples
+    sampler.into_sorted_items().into_iter().for_each(|i| {
+        wtr.write_str_unchecked(i.item);
+    });
+
+    Ok(())
+}
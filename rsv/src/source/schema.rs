@@ -0,0 +1,25 @@
+use crate::args::Schema;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::schema;
+use rsv_lib::utils::source::RecordSource;
+use std::fs;
+use std::path::Path;
+
+impl Schema {
+    pub fn source_run(&self, _path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        // all rows are scanned, so that types hold for the whole file
+        let s = schema::Schema::infer(src.as_mut(), self.no_header)?;
+
+        let out = self.output.as_ref().map(Path::new);
+        let text = s.to_string_by_extension(out)?;
+        match out {
+            Some(out) => {
+                fs::write(out, text)?;
+                println!("Saved to file: {}", out.display());
+            }
+            None => print!("{text}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::args::Search;
+use calamine::{Reader, open_workbook_auto};
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::error::{RsvError, RsvResult};
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::regex::Re;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, SheetSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Search {
+    pub fn source_run(&self, path: Option<&Path>, src: Box<dyn RecordSource>) -> CliResult {
+        let out = export_path(path, "searched");
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?
+            .dialect(self.dialect.of(src.sep(), src.quote()));
+
+        let matched = self.search(src, &mut wtr)?;
+
+        if self.export {
+            println!("\nMatched rows: {matched}");
+            println!("Saved to file: {}", out.display());
+        }
+
+        Ok(())
+    }
+
+    // all sheets of an EXCEL file are searched in turn, each headed by its name
+    pub fn sheets_run(&self, path: &Path) -> CliResult {
+        let sheets = open_workbook_auto(path)?.sheet_names();
+        let out = export_path(Some(path), "searched");
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(',', '"'));
+
+        let mut matched = 0;
+        for (i, sheet) in sheets.iter().enumerate() {
+            writeln!(wtr.0, "[{sheet}]")?;
+            matched += self.search(Box::new(SheetSource::open(path, i)?), &mut wtr)?;
+            writeln!(wtr.0)?;
+        }
+
+        if self.export {
+            println!("\nMatched rows: {matched}");
+            println!("Saved to file: {}", out.display());
+        }
+
+        Ok(())
+    }
+
+    pub fn sheet_index(&self) -> RsvResult<usize> {
+        self.sheet
+            .parse::<usize>()
+            .map_err(|_| RsvError::InvalidArgument(format!("{} is not a valid int.", self.sheet)))
+    }
+
+    // search a source and write matched rows, returning the number of matches
+    fn search(&self, mut src: Box<dyn RecordSource>, wtr: &mut Writer) -> RsvResult<usize> {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // the first row resolves column names
+        let Some(first) = src.next_line() else {
+            return Ok(0);
+        };
+        let first = first?;
+        let fields = src.split(&first).collect_owned();
+        let (cols, filter) = match self.no_header {
+            true => (
+                Columns::new(&self.out).total_col(fields.len()).parse()?,
+                Columns::new(&self.col).total_col(fields.len()).parse()?,
+            ),
+            false => (
+                Columns::new(&self.out).header(fields.clone()).parse()?,
+                Columns::new(&self.col).header(fields.clone()).parse()?,
+            ),
+        };
+        let re = Re::new(&self.pattern)?;
+        let search = |lines: &[String], wtr: &mut Writer| {
+            search_lines(lines, sep, quote, &re, &cols, &filter, wtr)
+        };
+
+        // header, or the first record
+        let mut matched = 0;
+        match self.no_header {
+            true => matched += search(&[first], wtr),
+            false if cols.select_all => wtr.write_str_unchecked(&first),
+            false => wtr.write_fields_unchecked(&cols.select_owned_vector(&fields)),
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(2);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // regex search
+        let mut prog = Progress::new();
+        for task in rx {
+            matched += search(&task.lines, wtr);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        Ok(matched)
+    }
+}
+
+fn search_lines(
+    lines: &[String],
+    sep: char,
+    quote: char,
+    re: &Re,
+    cols: &Columns,
+    filter: &Columns,
+    wtr: &mut Writer,
+) -> usize {
+    match (filter.select_all, cols.select_all) {
+        (true, true) => {
+            let lines = lines
+                .par_iter()
+                .filter(|&i| re.is_match(i))
+                .collect::<Vec<_>>();
+            wtr.write_strings_unchecked(&lines);
+            lines.len()
+        }
+        (true, false) => {
+            let lines = lines
+                .par_iter()
+                .filter_map(|r| {
+                    re.is_match(r).then(|| {
+                        cols.select_owned_vector(
+                            &CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>(),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+            wtr.write_fields_of_lines_unchecked(&lines);
+            lines.len()
+        }
+        (false, true) => {
+            let lines = lines
+                .par_iter()
+                .filter(|r| {
+                    re.is_match_in(
+                        &CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>(),
+                        filter,
+                    )
+                })
+                .collect::<Vec<_>>();
+            wtr.write_strings_unchecked(&lines);
+            lines.len()
+        }
+        (false, false) => {
+            let lines = lines
+                .par_iter()
+                .filter_map(|r| {
+                    let r = CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>();
+                    re.is_match_in(&r, filter)
+                        .then(|| cols.select_owned_vector(&r))
+                })
+                .collect::<Vec<_>>();
+            wtr.write_fields_of_lines_unchecked(&lines);
+            lines.len()
+        }
+    }
+}
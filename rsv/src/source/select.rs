@@ -0,0 +1,98 @@
+use crate::args::Select;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::filter::Filter;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Select {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+        let out = export_path(path, "selected");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+
+        // the first row resolves column names
+        let Some(first) = src.next_line() else {
+            return Ok(());
+        };
+        let first = first?;
+        let names = src.split(&first).collect_owned();
+        let cols = Columns::new(&self.cols).header(names.clone()).parse()?;
+        let filter = Filter::new(&self.filter).header(names).parse()?;
+        let write = |lines: &[String], wtr: &mut Writer| {
+            write_lines(lines, sep, quote, &cols, &filter, wtr)
+        };
+
+        // header, or the first record
+        match self.no_header {
+            true => write(&[first], &mut wtr),
+            false if cols.select_all => wtr.write_str_unchecked(&first),
+            false => {
+                let r = CsvRowSplitter::new(&first, sep, quote).collect::<Vec<_>>();
+                let r = cols.iter().map(|&i| &r[i]).collect::<Vec<_>>();
+                wtr.write_fields_unchecked(&r);
+            }
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+
+        // read
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        // process
+        let mut prog = Progress::new();
+        for task in rx {
+            write(&task.lines, &mut wtr);
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
+
+fn write_lines(
+    lines: &[String],
+    sep: char,
+    quote: char,
+    cols: &Columns,
+    filter: &Filter,
+    wtr: &mut Writer,
+) {
+    // filter
+    let filtered = lines
+        .par_iter()
+        .filter_map(|row| filter.record_valid_map(row, sep, quote))
+        .collect::<Vec<(_, _)>>();
+
+    // write
+    for (r, f) in filtered {
+        // write the line directly
+        if cols.select_all {
+            wtr.write_str_unchecked(r.unwrap());
+            continue;
+        }
+
+        // write by fields
+        let f = f.unwrap_or_else(|| CsvRowSplitter::new(r.unwrap(), sep, quote).collect());
+        let row = cols.iter().map(|&i| &f[i]).collect::<Vec<_>>();
+        wtr.write_fields_unchecked(&row);
+    }
+}
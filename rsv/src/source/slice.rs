@@ -0,0 +1,39 @@
+use crate::args::Slice;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::util::slice_range;
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+
+impl Slice {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        // [start, end) of the slice
+        let (start, end) = slice_range(self.start, self.end, self.length, self.index)?;
+
+        let out = export_path(path, "slice");
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?;
+
+        // header
+        if !self.no_header {
+            let Some(r) = src.next_line() else {
+                return Ok(());
+            };
+            wtr.write_str_unchecked(r?);
+        }
+
+        // slice, and stop reading at the end
+        for n in 0..end {
+            let Some(r) = src.next_line() else { break };
+            let r = r?;
+            if n >= start {
+                wtr.write_str_unchecked(r);
+            }
+        }
+
+        if self.export {
+            println!("Saved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
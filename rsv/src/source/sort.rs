@@ -0,0 +1,73 @@
+use crate::args::Sort;
+use crossbeam_channel::bounded;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::ColumnResolver;
+use rsv_lib::utils::constants::MB_USIZE;
+use rsv_lib::utils::external_sort::ExternalSorter;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::sort::SortColumns;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Sort {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // wtr
+        let out = export_path(path, "sorted");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+
+        // header
+        let Some(first) = src.next_line() else {
+            return Ok(());
+        };
+        let first = first?;
+        let fields = src.split(&first).collect_owned();
+
+        // cols
+        let mut resolver = match self.no_header {
+            true => ColumnResolver::from_total(fields.len()),
+            false => ColumnResolver::from_names(fields),
+        };
+        let cols = SortColumns::from(&self.cols, &mut resolver)?;
+
+        // sort in memory, spill sorted runs to disk when the memory budget is exceeded
+        let mut sorter = ExternalSorter::new(&cols, sep, quote, self.memory * MB_USIZE)
+            .temp_dir(self.temp_dir.as_deref());
+        match self.no_header {
+            true => sorter.push(vec![first])?,
+            false => wtr.write_str_unchecked(first),
+        }
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        let mut prog = Progress::new();
+        for task in rx {
+            sorter.push(task.lines)?;
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+
+        // merge sorted runs
+        if self.export && sorter.run_n() > 0 {
+            println!("\nMerging {} sorted runs", sorter.run_n());
+        }
+        sorter.finish(&mut wtr)?;
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use crate::args::Split;
+use crossbeam_channel::bounded;
+use dashmap::DashMap;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::ColumnResolver;
+use rsv_lib::utils::compress::output_extension;
+use rsv_lib::utils::error::RsvError;
+use rsv_lib::utils::filename::{dir_file, split_chunk_file, split_dir, split_value_file};
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::reader::Task;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::RecordSource;
+use rsv_lib::utils::writer::{Dialect, Writer};
+use std::fs::create_dir;
+use std::path::Path;
+use std::thread;
+
+impl Split {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+        let is_sequential_split = self.size.is_some();
+
+        // output files are compressed the same way as the input file by default
+        let compress_ext = output_extension(self.compress.as_deref(), path)?;
+
+        // header, and the column to split upon
+        let (first_row, col) = if self.no_header {
+            (
+                String::new(),
+                ColumnResolver::default().resolve_one(&self.col)?,
+            )
+        } else {
+            let Some(r) = src.next_line() else {
+                return Ok(());
+            };
+            let r = r?;
+            let names = src.split(&r).collect_owned();
+            let total = names.len();
+            let col = ColumnResolver::from_names(names).resolve_one(&self.col)?;
+            if col >= total {
+                return Err(RsvError::ColumnOutOfRange { col, total });
+            }
+            (r, col)
+        };
+
+        // new directory
+        let dir = split_dir(path);
+        create_dir(&dir)?;
+
+        // parallel queue
+        let (tx, rx) = bounded(1);
+        let line_buffer_n = match is_sequential_split {
+            true => self.size.unwrap(),
+            false => 50_000,
+        };
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, line_buffer_n));
+
+        // process batch work
+        let mut prog = Progress::new();
+        let dialect = self.dialect.of(sep, quote);
+        let mut bad_rows = 0;
+        match is_sequential_split {
+            true => {
+                for task in rx {
+                    let out = dir_file(&dir, &split_chunk_file(path, task.chunk, &compress_ext));
+                    sequential_task_handle(task, &mut prog, &out, &first_row, &dialect)?;
+                }
+            }
+            false => {
+                let header_inserted: DashMap<String, bool> = DashMap::new();
+                for task in rx {
+                    bad_rows += task_handle(
+                        self,
+                        col,
+                        task,
+                        &mut prog,
+                        &dir,
+                        &first_row,
+                        &compress_ext,
+                        &dialect,
+                        &header_inserted,
+                    )?;
+                }
+            }
+        }
+        reader.join().unwrap()?;
+
+        if bad_rows > 0 {
+            eprintln!("\n[info] bad lines ignored: {bad_rows}");
+        }
+
+        println!("\nSaved to directory: {}", dir.display());
+
+        Ok(())
+    }
+}
+
+fn sequential_task_handle(
+    task: Task,
+    prog: &mut Progress,
+    out: &Path,
+    first_row: &str,
+    dialect: &Dialect,
+) -> CliResult {
+    // progress
+    prog.add_chunks(1);
+    prog.add_bytes(task.bytes);
+
+    // write
+    let mut wtr = Writer::append_to(out)?.dialect(dialect.clone());
+    wtr.write_header(first_row)?;
+    wtr.write_strings(&task.lines)?;
+
+    prog.print();
+
+    Ok(())
+}
+
+// rows of a chunk are appended to files of their values in the column, and the
+// number of rows without the column is returned
+#[allow(clippy::too_many_arguments)]
+fn task_handle(
+    args: &Split,
+    col: usize,
+    task: Task,
+    prog: &mut Progress,
+    dir: &Path,
+    first_row: &str,
+    compress_ext: &str,
+    dialect: &Dialect,
+    header_inserted: &DashMap<String, bool>,
+) -> Result<usize, RsvError> {
+    // progress
+    prog.add_chunks(1);
+    prog.add_bytes(task.bytes);
+
+    // parallel process
+    let (sep, quote) = (dialect.in_sep, dialect.in_quote);
+    let batch_work = DashMap::new();
+    let bad_rows = task
+        .lines
+        .par_iter()
+        .filter(|r| {
+            let mut seg = CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>();
+            if col >= seg.len() {
+                return true;
+            }
+            batch_work
+                .entry(seg.swap_remove(col).into_owned())
+                .or_insert_with(Vec::new)
+                .push(*r);
+            false
+        })
+        .count();
+
+    // parallel save to disk
+    batch_work
+        .into_iter()
+        .collect::<Vec<(_, _)>>()
+        .par_iter()
+        .try_for_each(|(field, rows)| -> CliResult {
+            // file path
+            let filename = split_value_file(field, compress_ext);
+            let out = dir_file(dir, &filename);
+
+            // write
+            let mut wtr = Writer::append_to(&out)?.dialect(dialect.clone());
+            if !args.no_header && !header_inserted.contains_key(&filename) {
+                header_inserted.insert(filename, true);
+                wtr.write_str(first_row)?;
+            }
+            wtr.write_strings(rows)
+        })?;
+
+    prog.print();
+
+    Ok(bad_rows)
+}
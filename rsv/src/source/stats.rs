@@ -0,0 +1,197 @@
+use crate::args::Stats;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::column_stats::ColumnStats;
+use rsv_lib::utils::column_type::ColumnTypes;
+use rsv_lib::utils::column_type_infer::TypeInference;
+use rsv_lib::utils::compress;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::reader::Task;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::schema::Schema;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::thread;
+
+impl Stats {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // a full scan reads the source twice, where stdin is spilled to a temporary file
+        if self.full_scan {
+            src.keep_for_rewind()?;
+        }
+
+        // header
+        let Some(first) = src.next_line() else {
+            return Ok(());
+        };
+        let first = first?;
+        let fields = src.split(&first).collect_owned();
+        let total = fields.len();
+        let (cols, names) = match self.no_header {
+            true => {
+                let cols = Columns::new(&self.cols).total_col(total).parse()?;
+                let names = cols.artificial_n_cols(total);
+                (cols, names)
+            }
+            false => (
+                Columns::new(&self.cols).header(fields.clone()).parse()?,
+                fields,
+            ),
+        };
+
+        // types of all rows, or guessed from the first 5000 rows, which are kept for stats
+        let mut lines = vec![];
+        if self.no_header {
+            lines.push(first);
+        }
+        let infer = match self.full_scan {
+            true => {
+                src.rewind()?;
+                if !self.no_header {
+                    src.next_line().transpose()?;
+                }
+                let infer = TypeInference::from_source(src.as_mut(), &cols, total)?;
+                src.rewind()?;
+                if !self.no_header {
+                    src.next_line().transpose()?;
+                }
+                lines.clear();
+                Some(infer)
+            }
+            false => {
+                while lines.len() < 5000 {
+                    match src.next_line() {
+                        Some(r) => lines.push(r?),
+                        None => break,
+                    }
+                }
+                None
+            }
+        };
+        let mut col_type = match &infer {
+            Some(i) => i.column_types(self.confidence, &self.text_columns, &[]),
+            None if lines.is_empty() => return Ok(()),
+            None => {
+                let rows = lines
+                    .iter()
+                    .map(|r| CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                ColumnTypes::guess_from_lines(&rows, &cols, &self.text_columns, &[])
+            }
+        };
+
+        self.apply_schema(&mut col_type, &names)?;
+        self.report_inference(infer.as_ref(), &col_type, &names);
+
+        // stats holder
+        let mut stat = self.column_stats(&col_type, &names);
+        let empty_stat = stat.clone();
+
+        // read by chunks, starting with the rows read for type guessing
+        let (tx, rx) = bounded(1);
+        let first = Task {
+            bytes: lines.iter().map(|l| l.len()).sum(),
+            lines,
+            chunk: 0,
+        };
+        let reader = thread::spawn(move || match tx.send(first) {
+            Ok(_) => src.send_to_channel_by_chunks(tx, 20_000),
+            Err(_) => Ok(()),
+        });
+
+        // merge stats of each chunk, which is processed in parallel, with progress
+        // shown for files
+        let mut prog = Progress::new();
+        for task in rx {
+            let r = task
+                .lines
+                .par_chunks(1000)
+                .map(|chunk| {
+                    let mut s = empty_stat.clone();
+                    for l in chunk {
+                        s.parse_line(l, sep, quote);
+                    }
+                    s
+                })
+                .collect::<Vec<_>>();
+            r.into_iter().for_each(|s| stat.merge(s));
+
+            if path.is_some() {
+                prog.add_bytes(task.bytes);
+                prog.add_chunks(1);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+        if path.is_some() {
+            prog.clear();
+        }
+
+        if stat.bad_rows > 0 {
+            eprintln!("[info] bad lines ignored: {}", stat.bad_rows);
+        }
+
+        // refine result
+        stat.cal_unique_and_mean();
+
+        // print
+        if self.export {
+            let out = export_path(path, "stats");
+            let mut wtr = BufWriter::new(compress::create(&out)?);
+            wtr.write_all(stat.to_csv().as_bytes())?;
+            println!("Saved to file: {}", out.display());
+        } else {
+            stat.print();
+        }
+
+        println!("Total rows: {}", stat.rows);
+        if path.is_some() {
+            prog.print_elapsed_time();
+        }
+
+        Ok(())
+    }
+
+    // stats holder with the percentiles and histogram bins to report
+    fn column_stats(&self, col_type: &ColumnTypes, names: &[String]) -> ColumnStats {
+        let mut stat = ColumnStats::new(col_type, names);
+        stat.percentiles(&self.percentiles);
+        if let Some(bins) = self.histogram {
+            stat.histogram(bins);
+        }
+        if self.full_scan {
+            stat.fix_types();
+        }
+
+        stat
+    }
+
+    // columns with values violating inferred types
+    fn report_inference(
+        &self,
+        infer: Option<&TypeInference>,
+        col_type: &ColumnTypes,
+        names: &[String],
+    ) {
+        if let Some(i) = infer {
+            i.report(col_type, names)
+                .iter()
+                .for_each(|l| eprintln!("{l}"));
+        }
+    }
+
+    // types declared in the schema override guessed ones
+    fn apply_schema(&self, col_type: &mut ColumnTypes, names: &[String]) -> CliResult {
+        if let Some(f) = &self.schema {
+            let schema = Schema::load(Path::new(f))?;
+            col_type.apply_schema(&schema, names, self.no_header)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::args::Table;
+use rsv_lib::utils::source::RecordSource;
+use rsv_lib::utils::{cli_result::CliResult, table::Table as T};
+use std::path::Path;
+
+impl Table {
+    pub fn source_run(&self, _: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let mut rows = vec![];
+        while let Some(r) = src.next_row() {
+            rows.push(r?);
+        }
+
+        T::from_records(rows).print_blank()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::args::Tail;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::collections::VecDeque;
+use std::path::Path;
+
+impl Tail {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let n = if self.n == 0 { usize::MAX - 10 } else { self.n };
+        let out = export_path(path, "tail");
+        let dialect = self.dialect.of(src.sep(), src.quote());
+        let mut wtr = Writer::file_or_stdout(self.export, &out)?.dialect(dialect);
+
+        // header
+        if !self.no_header {
+            let Some(r) = src.next_line() else {
+                return Ok(());
+            };
+            wtr.write_str_unchecked(r?);
+        }
+
+        // keep the last n rows in a ring buffer
+        let mut lines = VecDeque::new();
+        while let Some(r) = src.next_line() {
+            if lines.len() >= n {
+                lines.pop_front();
+            }
+            lines.push_back(r?);
+        }
+
+        lines.iter().for_each(|r| wtr.write_str_unchecked(r));
+
+        if self.export {
+            println!("Saved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
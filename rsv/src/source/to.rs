@@ -0,0 +1,258 @@
+use crate::args::To;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::column_type::ColumnTypes;
+use rsv_lib::utils::column_type_infer::TypeInference;
+use rsv_lib::utils::date_format_infer::DateSmartParser;
+use rsv_lib::utils::error::RsvResult;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::schema::Schema;
+use rsv_lib::utils::source::RecordSource;
+use rsv_lib::utils::to::{
+    TypedWriter, is_valid_excel, is_valid_json, is_valid_parquet, is_valid_plain_text,
+    out_filename, strip_compression_suffix, write_excel_line,
+};
+use rsv_lib::utils::writer::{Dialect, Writer};
+use rust_xlsxwriter::*;
+use std::path::Path;
+
+// the header and column types, with rows read to guess the types, which are
+// written before the rest of the source
+struct Guess {
+    names: Vec<String>,
+    ctypes: ColumnTypes,
+    infer: Option<TypeInference>,
+    equal_width: bool,
+    lines: Vec<String>,
+}
+
+impl To {
+    pub fn source_run(&self, _path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let out = self.out.to_lowercase();
+
+        match out.as_str() {
+            v if is_valid_plain_text(v) => self.to_csv(src.as_mut(), &out),
+            v if is_valid_excel(v) => self.to_excel(src.as_mut(), &out),
+            v if is_valid_parquet(v) || is_valid_json(v) => self.to_typed(src.as_mut(), &out),
+            _ => Err(format!("output file format <{out}> is un-recognized.").into()),
+        }
+    }
+
+    fn to_csv(&self, src: &mut dyn RecordSource, out: &str) -> CliResult {
+        let sep = match strip_compression_suffix(out).ends_with("tsv") {
+            true => '\t',
+            false => ',',
+        };
+        let out = out_filename(out);
+
+        // rows are written as they are unless the separator changes
+        let dialect = Dialect {
+            sep,
+            in_sep: src.sep(),
+            in_quote: src.quote(),
+            ..Default::default()
+        };
+        let mut wtr = Writer::new(&out)?
+            .encoding(self.out_encoding)
+            .dialect(dialect);
+        while let Some(l) = src.next_line() {
+            wtr.write_str(l?)?;
+        }
+        wtr.0.flush()?;
+
+        println!("Saved to file: {}", out.display());
+
+        Ok(())
+    }
+
+    fn to_excel(&self, src: &mut dyn RecordSource, out: &str) -> CliResult {
+        let Some(mut g) = self.guess(src)? else {
+            return Ok(());
+        };
+
+        // wtr
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let (date_columns, date_formats) = self.apply_schema(&mut g.ctypes, &g.names)?;
+        report_inference(g.infer.as_ref(), &g.ctypes, &g.names);
+        let ctypes = match g.equal_width {
+            true => {
+                g.ctypes.update_excel_column_width(sheet)?;
+                Some(&g.ctypes)
+            }
+            false => None,
+        };
+
+        // header
+        let mut n = 0;
+        if !self.no_header {
+            sheet.write_row(0, 0, &g.names)?;
+            n += 1;
+        }
+
+        // copy
+        let (sep, quote) = (src.sep(), src.quote());
+        let parser = DateSmartParser::new();
+        let date_fmt = Format::new().set_num_format("yyyy-mm-dd");
+        let datetime_fmt = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+        let lines = g.lines.into_iter().map(Ok);
+        for r in lines.chain(std::iter::from_fn(|| src.next_line())) {
+            let r = r?;
+            let l = CsvRowSplitter::new(&r, sep, quote).collect::<Vec<_>>();
+            write_excel_line(
+                sheet,
+                n,
+                &l,
+                ctypes,
+                &date_columns,
+                &date_formats,
+                self.serial_dates,
+                &parser,
+                &date_fmt,
+                &datetime_fmt,
+            )?;
+            n += 1;
+        }
+
+        // out path
+        let out = out_filename(out);
+        workbook.save(&out)?;
+
+        println!("Saved to file: {}", out.display());
+
+        Ok(())
+    }
+
+    fn to_typed(&self, src: &mut dyn RecordSource, out: &str) -> CliResult {
+        let Some(mut g) = self.guess(src)? else {
+            return Ok(());
+        };
+
+        // wtr
+        let (date_columns, date_formats) = self.apply_schema(&mut g.ctypes, &g.names)?;
+        report_inference(g.infer.as_ref(), &g.ctypes, &g.names);
+        let out = out_filename(out);
+        let mut wtr = TypedWriter::new(&out, &g.names, &g.ctypes, &date_columns, &date_formats)?;
+
+        // copy
+        let (sep, quote) = (src.sep(), src.quote());
+        let lines = g.lines.into_iter().map(Ok);
+        for r in lines.chain(std::iter::from_fn(|| src.next_line())) {
+            let r = r?;
+            wtr.write_row(&CsvRowSplitter::new(&r, sep, quote).collect::<Vec<_>>())?;
+        }
+        wtr.close()?;
+
+        println!("Saved to file: {}", out.display());
+
+        Ok(())
+    }
+
+    // types of all rows with --full-scan, where the source is read again for rows,
+    // or guessed from the first 5000 rows, and None without rows
+    fn guess(&self, src: &mut dyn RecordSource) -> RsvResult<Option<Guess>> {
+        if self.full_scan {
+            src.keep_for_rewind()?;
+        }
+
+        // header
+        let Some(first) = src.next_line() else {
+            return Ok(None);
+        };
+        let first = first?;
+        let fields = src.split(&first).collect_owned();
+        let cols = Columns::new("").total_col(fields.len()).parse()?;
+        let (names, mut lines) = match self.no_header {
+            true => (vec![], vec![first]),
+            false => (fields.clone(), vec![]),
+        };
+
+        if self.full_scan {
+            src.rewind()?;
+            if !self.no_header {
+                src.next_line().transpose()?;
+            }
+            let infer = TypeInference::from_source(src, &cols, fields.len())?;
+            if infer.cols.iter().all(|c| c.nulls() + c.non_nulls() == 0) {
+                return Ok(None);
+            }
+            src.rewind()?;
+            if !self.no_header {
+                src.next_line().transpose()?;
+            }
+
+            let ctypes =
+                infer.column_types(self.confidence, &self.text_columns, &self.date_columns);
+            return Ok(Some(Guess {
+                names,
+                ctypes,
+                infer: Some(infer),
+                equal_width: true,
+                lines: vec![],
+            }));
+        }
+
+        while lines.len() < 5000 {
+            match src.next_line() {
+                Some(r) => lines.push(r?),
+                None => break,
+            }
+        }
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        let (sep, quote) = (src.sep(), src.quote());
+        let rows = lines
+            .iter()
+            .map(|r| CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let equal_width = rows.iter().all(|r| r.len() == rows[0].len());
+        let ctypes = match equal_width {
+            true => {
+                ColumnTypes::guess_from_lines(&rows, &cols, &self.text_columns, &self.date_columns)
+            }
+            false => {
+                ColumnTypes::guess_from_io(&rows, &cols, &self.text_columns, &self.date_columns)
+            }
+        };
+
+        Ok(Some(Guess {
+            names,
+            ctypes,
+            infer: None,
+            equal_width,
+            lines,
+        }))
+    }
+
+    /// Types and date formats declared in the schema override guessed types, as
+    /// well as the text columns, date columns and date formats of the flags.
+    /// Without a header, fields of the schema are matched by position.
+    fn apply_schema(
+        &self,
+        ctypes: &mut ColumnTypes,
+        names: &[String],
+    ) -> RsvResult<(Vec<usize>, Vec<String>)> {
+        let Some(f) = &self.schema else {
+            return Ok((self.date_columns.clone(), self.date_formats.clone()));
+        };
+
+        let schema = Schema::load(Path::new(f))?;
+        let names = match self.no_header {
+            true => (0..ctypes.iter().count()).map(|i| i.to_string()).collect(),
+            false => names.to_vec(),
+        };
+        ctypes.apply_schema(&schema, &names, self.no_header)?;
+        schema.date_columns(&names, self.no_header)
+    }
+}
+
+// columns with values violating inferred types
+fn report_inference(infer: Option<&TypeInference>, ctypes: &ColumnTypes, names: &[String]) {
+    if let Some(i) = infer {
+        i.report(ctypes, names)
+            .iter()
+            .for_each(|l| eprintln!("{l}"));
+    }
+}
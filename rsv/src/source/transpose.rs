@@ -0,0 +1,152 @@
+use crate::args::Transpose;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::error::RsvResult;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::to::write_excel_rows;
+use rsv_lib::utils::transpose::{Transposer, columns_per_pass};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Transpose {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // columns transposed in each pass, all at once unless memory is limited, where
+        // output rows follow the widest row, and stdin is spilled to a temporary file
+        let passes = match self.max_memory {
+            None => vec![(0, None)],
+            Some(mb) => {
+                src.keep_for_rewind()?;
+                let (width, bytes, rows) = self.measure(src.as_mut())?;
+                if width == 0 {
+                    return Ok(());
+                }
+                let n = columns_per_pass(bytes as u64, bytes as f64 / rows as f64, width, mb);
+                (0..width)
+                    .step_by(n)
+                    .map(|i| (i, Some((i + n).min(width))))
+                    .collect()
+            }
+        };
+
+        // excel output is limited to 16,384 columns, i.e., rows of the file, and is
+        // saved in the end
+        let out = export_path(path, "transposed");
+        let mut wtr = match self.xlsx {
+            true => None,
+            false => Some(
+                Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote)),
+            ),
+        };
+        let mut excel_rows = vec![];
+
+        let mut prog = Progress::new();
+        for (i, (start, end)) in passes.into_iter().enumerate() {
+            if self.max_memory.is_some() {
+                src.rewind()?;
+            }
+            let rows = self.transpose_columns(src.as_mut(), start, end, &mut prog)?;
+            if i == 0 && rows.is_empty() {
+                return Ok(());
+            }
+            match wtr.as_mut() {
+                Some(wtr) => rows.iter().for_each(|r| wtr.write_fields_unchecked(r)),
+                None => excel_rows.extend(rows),
+            }
+        }
+
+        if self.xlsx {
+            println!();
+            return self.write_excel(excel_rows, &out);
+        }
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+
+    // a pass over the source for output rows of the input columns from start to end
+    fn transpose_columns(
+        &self,
+        src: &mut dyn RecordSource,
+        start: usize,
+        end: Option<usize>,
+        prog: &mut Progress,
+    ) -> RsvResult<Vec<Vec<String>>> {
+        let (sep, quote) = (src.sep(), src.quote());
+        let mut t = Transposer::new(start, end);
+
+        thread::scope(|s| -> RsvResult<()> {
+            let (tx, rx) = bounded(1);
+            let reader = s.spawn(|| src.send_to_channel_by_chunks(tx, 10_000));
+            for task in rx {
+                task.lines
+                    .par_chunks(1_000)
+                    .map(|lines| {
+                        let mut part = t.empty();
+                        lines.iter().for_each(|l| {
+                            part.add_fields(&CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>())
+                        });
+                        part
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .for_each(|part| t.merge(part));
+
+                if self.export || self.xlsx {
+                    prog.add_chunks(1);
+                    prog.add_bytes(task.bytes);
+                    prog.print();
+                }
+            }
+            reader.join().unwrap()?;
+            Ok(())
+        })?;
+
+        Ok(t.into_rows())
+    }
+
+    // the widest row, bytes and rows of the source, to plan the passes
+    fn measure(&self, src: &mut dyn RecordSource) -> RsvResult<(usize, usize, usize)> {
+        let (sep, quote) = (src.sep(), src.quote());
+        let (mut width, mut bytes, mut rows) = (0, 0, 0);
+
+        thread::scope(|s| -> RsvResult<()> {
+            let (tx, rx) = bounded(1);
+            let reader = s.spawn(|| src.send_to_channel_by_chunks(tx, 10_000));
+            for task in rx {
+                let n = task
+                    .lines
+                    .par_iter()
+                    .map(|l| CsvRowSplitter::new(l, sep, quote).count())
+                    .max()
+                    .unwrap_or_default();
+                width = width.max(n);
+                bytes += task.bytes + task.lines.len();
+                rows += task.lines.len();
+            }
+            reader.join().unwrap()?;
+            Ok(())
+        })?;
+
+        Ok((width, bytes, rows.max(1)))
+    }
+
+    fn write_excel(&self, rows: Vec<Vec<String>>, out: &Path) -> CliResult {
+        let out = out.with_extension("xlsx");
+        let (header, rows) = match rows.split_first() {
+            Some((header, rows)) => (header, rows),
+            None => (&vec![], &rows[..]),
+        };
+        write_excel_rows(&out, header, rows)?;
+        println!("Saved to file: {}", out.display());
+
+        Ok(())
+    }
+}
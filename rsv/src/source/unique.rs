@@ -0,0 +1,138 @@
+use crate::args::Unique;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::Columns;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::unique::UniqueRows;
+use rsv_lib::utils::writer::Writer;
+use std::io;
+use std::iter;
+use std::path::Path;
+
+impl Unique {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+        let all_cols = self.cols == "-1";
+
+        // wtr
+        let out = export_path(path, "drop-duplicates");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+
+        // the first row is needed to resolve column names
+        let Some(first_row) = src.next_line() else {
+            return Ok(());
+        };
+        let first_row = first_row?;
+
+        // cols
+        let cols = if all_cols {
+            None
+        } else {
+            let names = src.split(&first_row).collect_owned();
+            let cols = match self.no_header {
+                true => Columns::new(&self.cols).total_col(names.len()),
+                false => Columns::new(&self.cols).header(names),
+            };
+            Some(cols.parse()?)
+        };
+
+        // header
+        let first_row = match self.no_header {
+            true => Some(Ok(first_row)),
+            false => {
+                wtr.write_str_unchecked(&first_row);
+                None
+            }
+        };
+        let lines = first_row
+            .into_iter()
+            .chain(iter::from_fn(|| src.next_line()));
+        let key = |r: &str, cols: &Columns| {
+            cols.select_owned_string(&CsvRowSplitter::new(r, sep, quote).collect::<Vec<_>>())
+        };
+
+        // read
+        match (self.keep_last, cols) {
+            (true, None) => keep_last_and_all_cols(lines, &mut wtr)?,
+            (true, Some(cols)) => keep_last_and_partial_cols(lines, &mut wtr, |r| key(r, &cols))?,
+            (false, None) => keep_first_and_all_cols(lines, &mut wtr)?,
+            (false, Some(cols)) => keep_first_and_partial_cols(lines, &mut wtr, |r| key(r, &cols))?,
+        };
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
+
+fn keep_first_and_all_cols(
+    rdr: impl Iterator<Item = io::Result<String>>,
+    wtr: &mut Writer,
+) -> CliResult {
+    let mut unique_holder = ahash::HashSet::default();
+    for r in rdr {
+        let r = r?;
+        if !unique_holder.contains(&r) {
+            wtr.write_str_unchecked(&r);
+            unique_holder.insert(r);
+        }
+    }
+
+    Ok(())
+}
+
+fn keep_first_and_partial_cols(
+    rdr: impl Iterator<Item = io::Result<String>>,
+    wtr: &mut Writer,
+    key: impl Fn(&str) -> String,
+) -> CliResult {
+    let mut unique = UniqueRows::new(false);
+    for r in rdr {
+        let r = r?;
+        if let Some(r) = unique.push(key(&r), r) {
+            wtr.write_str_unchecked(r);
+        }
+    }
+
+    Ok(())
+}
+
+// the last row of each key is kept along with its position in a single pass, so
+// that stdin is not read twice, and unique rows are written in the order of positions
+fn keep_last_and_all_cols(
+    rdr: impl Iterator<Item = io::Result<String>>,
+    wtr: &mut Writer,
+) -> CliResult {
+    let mut unique_n = ahash::HashMap::default();
+    for (i, r) in rdr.enumerate() {
+        unique_n.insert(r?, i);
+    }
+
+    let mut rows = unique_n.into_iter().collect::<Vec<_>>();
+    rows.sort_unstable_by_key(|(_, i)| *i);
+    rows.iter().for_each(|(r, _)| wtr.write_str_unchecked(r));
+
+    Ok(())
+}
+
+fn keep_last_and_partial_cols(
+    rdr: impl Iterator<Item = io::Result<String>>,
+    wtr: &mut Writer,
+    key: impl Fn(&str) -> String,
+) -> CliResult {
+    let mut unique = UniqueRows::new(true);
+    for r in rdr {
+        let r = r?;
+        unique.push(key(&r), r);
+    }
+
+    unique
+        .finish()
+        .iter()
+        .for_each(|r| wtr.write_str_unchecked(r));
+
+    Ok(())
+}
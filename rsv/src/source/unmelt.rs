@@ -0,0 +1,112 @@
+use crate::args::Unmelt;
+use crossbeam_channel::bounded;
+use rayon::prelude::*;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::column::ColumnResolver;
+use rsv_lib::utils::melt::Unmelter;
+use rsv_lib::utils::progress::Progress;
+use rsv_lib::utils::row_split::CsvRowSplitter;
+use rsv_lib::utils::source::{RecordSource, export_path};
+use rsv_lib::utils::writer::Writer;
+use std::path::Path;
+use std::thread;
+
+impl Unmelt {
+    pub fn source_run(&self, path: Option<&Path>, mut src: Box<dyn RecordSource>) -> CliResult {
+        let (sep, quote) = (src.sep(), src.quote());
+
+        // stdin is spilled to a temporary file for the second pass
+        src.keep_for_rewind()?;
+
+        // header
+        let Some(first) = src.next_row() else {
+            return Ok(());
+        };
+        let first = first?;
+
+        // id, key and value columns
+        let mut resolver = match self.no_header {
+            true => ColumnResolver::from_total(first.len()),
+            false => ColumnResolver::from_names(first.clone()),
+        };
+        let mut unmelter = Unmelter::new(&self.id, &self.key, &self.value, &mut resolver)?;
+        let names = match self.no_header {
+            true => {
+                unmelter.add_variable_by_fields(&first);
+                vec![]
+            }
+            false => first,
+        };
+
+        // first pass to collect variables, which become output columns
+        thread::scope(|s| -> CliResult {
+            let (tx, rx) = bounded(1);
+            let reader = s.spawn(|| src.send_to_channel_by_chunks(tx, 10_000));
+            for task in rx {
+                task.lines
+                    .par_chunks(1_000)
+                    .map(|lines| {
+                        let lines = lines
+                            .iter()
+                            .map(|l| CsvRowSplitter::new(l, sep, quote).collect::<Vec<_>>())
+                            .collect::<Vec<_>>();
+                        unmelter.distinct_variables(&lines)
+                    })
+                    .collect::<Vec<_>>()
+                    .iter()
+                    .flatten()
+                    .for_each(|v| unmelter.add_variable(v));
+            }
+            reader.join().unwrap()?;
+            Ok(())
+        })?;
+
+        // wtr
+        let out = export_path(path, "unmelted");
+        let mut wtr =
+            Writer::file_or_stdout(self.export, &out)?.dialect(self.dialect.of(sep, quote));
+        wtr.write_fields_unchecked(&unmelter.header(&names));
+
+        // second pass to join adjacent rows of an id into a wide row
+        src.rewind()?;
+        if !self.no_header {
+            src.next_line().transpose()?;
+        }
+        let (tx, rx) = bounded(1);
+        let reader = thread::spawn(move || src.send_to_channel_by_chunks(tx, 10_000));
+
+        let mut prog = Progress::new();
+        for task in rx {
+            let lines = task
+                .lines
+                .par_iter()
+                .map(|r| CsvRowSplitter::new(r, sep, quote).collect_owned())
+                .collect::<Vec<_>>();
+            lines.iter().for_each(|r| {
+                if let Some(r) = unmelter.push(r) {
+                    wtr.write_fields_unchecked(&r)
+                }
+            });
+
+            if self.export {
+                prog.add_chunks(1);
+                prog.add_bytes(task.bytes);
+                prog.print();
+            }
+        }
+        reader.join().unwrap()?;
+        if let Some(r) = unmelter.finish() {
+            wtr.write_fields_unchecked(&r)
+        }
+
+        if unmelter.bad_rows() > 0 {
+            eprintln!("[info] bad lines ignored: {}", unmelter.bad_rows());
+        }
+
+        if self.export {
+            println!("\nSaved to file: {}", out.display())
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::args::Validate;
+use calamine::Data;
+use rsv_lib::utils::cli_result::CliResult;
+use rsv_lib::utils::compress;
+use rsv_lib::utils::encoding::{self, TextEncoding};
+use rsv_lib::utils::error::RsvError;
+use rsv_lib::utils::file::is_sheet;
+use rsv_lib::utils::schema::Schema;
+use rsv_lib::utils::validate::Validator;
+use rsv_lib::utils::writer::Writer;
+use std::io::{BufRead, BufReader, BufWriter, stdin};
+use std::path::Path;
+
+impl Validate {
+    // files and stdin are read as raw bytes rather than records of a source, so
+    // that invalid UTF-8 and BOMs are reported, unless an encoding is given
+    pub fn bytes_run(&self, path: Option<&Path>, enc: TextEncoding) -> CliResult {
+        match (path, enc) {
+            (Some(p), _) if is_sheet(p) => Err(RsvError::InvalidArgument(
+                "rsv validate does not support Excel files.".to_owned(),
+            )),
+            (Some(p), TextEncoding::Auto) => self.validate(BufReader::new(compress::open_raw(p)?)),
+            (Some(p), _) => self.validate(BufReader::new(compress::open(p, enc)?)),
+            (None, TextEncoding::Auto) => self.validate(BufReader::new(stdin())),
+            (None, _) => self.validate(encoding::stdin_reader(enc)?),
+        }
+    }
+
+    // problems are printed as CSV rows, and the summary goes to stderr
+    fn validate<R: BufRead>(&self, rdr: R) -> CliResult {
+        let mut validator = Validator::new(self.sep, self.quote, self.no_header);
+        if let Some(f) = &self.reject {
+            let wtr = BufWriter::new(compress::create(Path::new(f))?);
+            validator = validator.reject(Box::new(wtr));
+        }
+        if let Some(f) = &self.schema {
+            validator = validator.schema(Schema::load(Path::new(f))?);
+        }
+
+        let mut wtr = Writer::stdout()?;
+        wtr.write_str("line,issue,detail")?;
+        let report = validator.run(rdr, |i| {
+            let line = [
+                Data::Int(i.line as i64),
+                Data::String(i.kind.to_string()),
+                Data::String(i.detail.clone()),
+            ];
+            wtr.write_excel_line(&line).map_err(std::io::Error::other)
+        })?;
+
+        if let Some(f) = &self.reject {
+            eprintln!("Bad rows saved to file: {f}");
+        }
+
+        match report.total() {
+            0 => {
+                eprintln!("{report}");
+                Ok(())
+            }
+            _ => Err(RsvError::Validation(report.to_string())),
+        }
+    }
+}
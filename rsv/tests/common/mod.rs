@@ -1,4 +1,10 @@
-use std::env::{consts::OS, current_exe};
+use execute::shell;
+use std::{
+    env::{consts::OS, current_exe, temp_dir},
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+    process::Output,
+};
 
 pub fn rsv() -> String {
     let mut p = current_exe().unwrap();
@@ -22,6 +28,7 @@ pub fn rsv() -> String {
     p.to_str().unwrap().to_owned()
 }
 
+#[allow(dead_code)]
 pub fn data_path(f: &str) -> String {
     let mut p = current_exe().unwrap();
 
@@ -38,3 +45,50 @@ pub fn data_path(f: &str) -> String {
     println!("file path: {}", p.display());
     p.to_str().unwrap().to_owned()
 }
+
+/// A temporary directory of a test, e.g., cat-tests/rows, with fixture files
+/// written into it.
+#[allow(dead_code)]
+pub fn temp_dir_with(dir: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = temp_dir().join(dir);
+    create_dir_all(&dir).unwrap();
+    for (f, content) in files {
+        std::fs::write(dir.join(f), content).unwrap();
+    }
+
+    dir
+}
+
+/// Run a shell command, where rsv is the binary and FILE a test data file, in
+/// the directory if given.
+#[allow(dead_code)]
+pub fn cmd_output(cmd: &str, file: &str, dir: Option<&Path>) -> Output {
+    let mut cmd = cmd.replace("rsv", &rsv());
+    if cmd.contains("FILE") {
+        cmd = cmd.replace("FILE", &data_path(file));
+    }
+    if let Some(dir) = dir {
+        cmd = format!("cd {} && {}", dir.display(), cmd);
+    }
+
+    shell(cmd).output().unwrap()
+}
+
+#[allow(dead_code)]
+pub fn stdout_of(out: Output) -> String {
+    String::from_utf8(out.stdout).unwrap()
+}
+
+/// Standard output of a command, with CRLF line endings as LF.
+#[allow(dead_code)]
+pub fn output_of_cmd(cmd: &str, file: &str) -> String {
+    stdout_of(cmd_output(cmd, file, None)).replace('\r', "")
+}
+
+/// Standard output of a command run in a temporary directory, e.g.,
+/// json-tests/blank-lines.
+#[allow(dead_code)]
+pub fn output_of_cmd_in_temp_dir(cmd: &str, file: &str, dir: &str) -> String {
+    let dir = temp_dir_with(dir, &[]);
+    stdout_of(cmd_output(cmd, file, Some(&dir))).replace('\r', "")
+}
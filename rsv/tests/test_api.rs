@@ -0,0 +1,159 @@
+use rsv_lib::{Frequency, RsvError, Sample, Search, Select, Slice, Sort, Split, Unique};
+use std::env::temp_dir;
+#[allow(dead_code)]
+mod common;
+use crate::common::data_path;
+
+fn rows(data: &[Vec<String>]) -> Vec<String> {
+    data.iter().map(|r| r.join(",")).collect()
+}
+
+#[test]
+fn test_select() {
+    let r = Select::new(&data_path("sales.csv"))
+        .filter("3N>=70")
+        .cols("customer,amount")
+        .run()
+        .unwrap();
+    assert_eq!(r.header, ["customer", "amount"]);
+    assert_eq!(rows(&r.data), ["c10,100", "c1,200", "c2,70"]);
+
+    // excel and csv files give the same result
+    let csv = Select::new(&data_path("customers.csv")).run().unwrap();
+    let excel = Select::new(&data_path("customers.xlsx")).run().unwrap();
+    assert_eq!(csv.data, excel.data);
+}
+
+#[test]
+fn test_stream_rows_to_callback() {
+    let mut n = 0;
+    let header = Search::new(&data_path("sales.csv"), "^c1")
+        .cols("customer")
+        .out("customer")
+        .for_each(|r| {
+            assert!(r[0].starts_with("c1"));
+            n += 1;
+        })
+        .unwrap();
+    assert_eq!(header, ["customer"]);
+    assert_eq!(n, 3);
+}
+
+#[test]
+fn test_frequency() {
+    let r = Frequency::new(&data_path("sales.csv"))
+        .cols("region")
+        .run()
+        .unwrap();
+    assert_eq!(r.header, ["region", "n"]);
+    assert_eq!(rows(&r.data), ["east,4", "west,2"]);
+}
+
+#[test]
+fn test_sort() {
+    let r = Sort::new(&data_path("sales.csv"), "amountND")
+        .run()
+        .unwrap();
+    let amounts = r.data.iter().map(|r| r[3].as_str()).collect::<Vec<_>>();
+    assert_eq!(amounts, ["200", "100", "70", "50", "30", ""]);
+}
+
+#[test]
+fn test_unique() {
+    let path = data_path("sales.csv");
+    let r = Unique::new(&path).cols("customer").run().unwrap();
+    assert_eq!(
+        rows(&r.data),
+        [
+            "east,2024-01-02,c10,100",
+            "west,1/5/2024,c2,",
+            "east,12/31/2023,c1,30"
+        ]
+    );
+
+    let r = Unique::new(&path)
+        .cols("customer")
+        .keep_last(true)
+        .run()
+        .unwrap();
+    assert_eq!(
+        rows(&r.data),
+        [
+            "east,2024-01-02,c10,100",
+            "west,2024-01-05,c1,200",
+            "east,2024-01-02,c2,70"
+        ]
+    );
+}
+
+#[test]
+fn test_sample_and_slice() {
+    let path = data_path("sales.csv");
+    let a = Sample::new(&path, 3).seed(7).run().unwrap();
+    let b = Sample::new(&path, 3).seed(7).run().unwrap();
+    assert_eq!(a.data.len(), 3);
+    assert_eq!(a.data, b.data);
+
+    let r = Slice::new(&path).start(1).length(2).run().unwrap();
+    assert_eq!(
+        rows(&r.data),
+        ["west,1/5/2024,c2,", "east,2024-01-02,c2,50"]
+    );
+    let r = Slice::new(&path).no_header(true).index(0).run().unwrap();
+    assert_eq!(r.header, ["col0", "col1", "col2", "col3"]);
+    assert_eq!(rows(&r.data), ["region,date,customer,amount"]);
+
+    let r = Slice::new(&path).start(3).end(1).run();
+    assert!(matches!(r, Err(RsvError::InvalidArgument(_))));
+}
+
+#[test]
+fn test_split() {
+    let dir = temp_dir().join("api-tests").join("split");
+    let _ = std::fs::remove_dir_all(&dir);
+    let r = Split::new(&data_path("sales.csv"))
+        .col("region")
+        .dir(&dir)
+        .run()
+        .unwrap();
+    assert_eq!(r.data.len(), 2);
+    assert_eq!(r.data[0][1], "4");
+
+    let east = std::fs::read_to_string(dir.join("east.csv")).unwrap();
+    assert!(east.starts_with("region,date,customer,amount\neast,2024-01-02,c10,100\n"));
+}
+
+#[test]
+fn test_split_quotes_fields() {
+    let dir = temp_dir().join("api-tests").join("split-quotes");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let data = dir.join("data.csv");
+    std::fs::write(&data, "k,v\na,\"x, \"\"y\"\"\"\nb,z\n").unwrap();
+
+    Split::new(data.to_str().unwrap())
+        .dir(dir.join("out"))
+        .run()
+        .unwrap();
+    let a = std::fs::read_to_string(dir.join("out").join("a.csv")).unwrap();
+    assert_eq!(a, "k,v\na,\"x, \"\"y\"\"\"\n");
+}
+
+#[test]
+fn test_errors_are_returned() {
+    let r = Select::new(&data_path("sales.csv")).cols("9").run();
+    assert!(matches!(
+        r,
+        Err(RsvError::ColumnOutOfRange { col: 9, total: 4 })
+    ));
+
+    let r = Select::new(&data_path("not-existing.csv")).run();
+    assert!(matches!(r, Err(RsvError::Io(_))));
+    let r = Select::new(&data_path("sales.csv"))
+        .filter("0 ?? east")
+        .run();
+    assert!(matches!(r, Err(RsvError::FilterSyntax { pos: 2, .. })));
+
+    let r = Select::new(&data_path("sales.csv")).cols("price").run();
+    assert!(matches!(r, Err(RsvError::ColumnNotFound(_))));
+}
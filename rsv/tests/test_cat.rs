@@ -0,0 +1,75 @@
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+// daily files with reordered and missing columns
+const A: &str = "id,name\n1,ann\n2,bob\n";
+const B: &str = "name,age,id\ncat,3,3\n";
+
+fn output_of_cmd(cmd: &str, dir: &str) -> String {
+    let dir = temp_dir_with(&format!("cat-tests/{dir}"), &[("a.csv", A), ("b.csv", B)]);
+    stdout_of(cmd_output(cmd, "", Some(&dir)))
+}
+
+#[test]
+fn test_cat_rows() {
+    assert_eq!(
+        output_of_cmd("rsv cat rows a.csv b.csv", "rows"),
+        "id,name,age\n1,ann,\n2,bob,\n3,cat,3\n"
+    );
+    assert_eq!(
+        output_of_cmd("rsv cat rows --no-header a.csv b.csv", "no-header"),
+        format!("{A}{B}")
+    );
+}
+
+#[test]
+fn test_cat_rows_source() {
+    assert_eq!(
+        output_of_cmd("rsv cat rows --source a.csv b.csv", "source"),
+        "id,name,age,source_file\n1,ann,,a.csv\n2,bob,,a.csv\n3,cat,3,b.csv\n"
+    );
+    assert_eq!(
+        output_of_cmd("cat b.csv | rsv cat rows --source=file a.csv -", "stdin"),
+        "id,name,age,file\n1,ann,,a.csv\n2,bob,,a.csv\n3,cat,3,stdin\n"
+    );
+}
+
+#[test]
+fn test_cat_rows_duplicate_names() {
+    let cmd = "printf 'id,x,x\\n1,a,b\\n' > c.csv && rsv cat rows c.csv b.csv";
+    assert_eq!(
+        output_of_cmd(cmd, "duplicate"),
+        "id,x,x,name,age\n1,a,b,,\n3,,,cat,3\n"
+    );
+}
+
+#[test]
+fn test_cat_missing_file() {
+    let dir = temp_dir_with("cat-tests/missing", &[("a.csv", A)]);
+    let out = cmd_output("rsv cat rows a.csv nope.csv", "", Some(&dir));
+    assert_eq!(out.status.code(), Some(3));
+    assert!(
+        String::from_utf8(out.stderr)
+            .unwrap()
+            .starts_with("Error: nope.csv: ")
+    );
+}
+
+#[test]
+fn test_cat_excel() {
+    assert_eq!(
+        output_of_cmd(
+            "rsv to b.xlsx b.csv > /dev/null && rsv cat rows a.csv b.xlsx",
+            "excel"
+        ),
+        "id,name,age\n1,ann,\n2,bob,\n3,cat,3\n"
+    );
+}
+
+#[test]
+fn test_cat_cols() {
+    assert_eq!(
+        output_of_cmd("rsv cat cols a.csv b.csv", "cols"),
+        "id,name,name,age,id\n1,ann,cat,3,3\n2,bob,,,\n"
+    );
+}
@@ -0,0 +1,54 @@
+mod common;
+use crate::common::{output_of_cmd, output_of_cmd_in_temp_dir};
+
+// run the command in a temporary directory, since outputs are saved next to inputs
+#[test]
+fn test_gzip_input() {
+    assert!(output_of_cmd("rsv count FILE", "sales.csv.gz").starts_with("6\n"));
+    assert_eq!(
+        output_of_cmd(
+            "rsv select -f '3N>=70' -c customer,amount FILE",
+            "sales.csv.gz"
+        ),
+        "customer,amount\nc10,100\nc1,200\nc2,70\n"
+    );
+    assert_eq!(
+        output_of_cmd("rsv frequency -c region FILE", "sales.csv.gz"),
+        output_of_cmd("rsv frequency -c region FILE", "sales.csv")
+    );
+}
+
+#[test]
+fn test_compression_detected_by_magic_bytes() {
+    let cmd = "cp FILE sales.dat && rsv head -n 1 sales.dat";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv.gz", "compress-tests/magic-bytes");
+    assert_eq!(
+        out,
+        "region,date,customer,amount\neast,2024-01-02,c10,100\n"
+    );
+}
+
+#[test]
+fn test_to_compressed_csv() {
+    let cmd = "rsv to sales.csv.zst FILE && rsv tail -n 2 sales.csv.zst";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv", "compress-tests/to-zst");
+    assert!(
+        out.ends_with(
+            "region,date,customer,amount\nwest,2024-01-05,c1,200\neast,2024-01-02,c2,70\n"
+        )
+    );
+}
+
+#[test]
+fn test_export_keeps_compression() {
+    let cmd = "rsv to sales.csv.bz2 FILE && rsv select -c customer -E sales.csv.bz2 && rsv head -n 2 sales-selected.csv.bz2";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv", "compress-tests/export-bz2");
+    assert!(out.ends_with("sales-selected.csv.bz2\ncustomer\nc10\nc2\n"));
+}
+
+#[test]
+fn test_split_compressed_outputs() {
+    let cmd = "rm -rf sales-split-* && cp FILE sales.csv && rsv split --size 4 -z gz sales.csv && rsv count sales-split-*/sales-split2.csv.gz";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv", "compress-tests/split-gz");
+    assert!(out.contains("\n2\n"));
+}
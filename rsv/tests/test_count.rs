@@ -59,3 +59,11 @@ fn test_count_io_no_header() {
     let n = count(&data_path("hotel_reservation.csv"), true, true).unwrap();
     assert_eq!(n, 11);
 }
+
+#[test]
+fn test_count_io_multiline_records() {
+    // a quoted field spanning lines is one record
+    let cmd = format!("printf 'a,b\\n1,\"x\\ny\"\\n2,z\\n' | {} count", rsv());
+    let output = shell(cmd).output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2\n");
+}
@@ -0,0 +1,68 @@
+mod common;
+use crate::common::output_of_cmd;
+
+#[test]
+fn test_derive_csv_append_and_replace() {
+    let cmd = r#"rsv derive -e "total=c3*2" -e "region=upper(region)" -e "size=if(amount >= 70, 'big', 'small')" FILE"#;
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,date,customer,amount,total,size
+EAST,2024-01-02,c10,100,200,big
+WEST,1/5/2024,c2,,,small
+EAST,2024-01-02,c2,50,100,small
+EAST,12/31/2023,c1,30,60,small
+WEST,2024-01-05,c1,200,400,big
+EAST,2024-01-02,c2,70,140,big
+"
+    );
+}
+
+#[test]
+fn test_derive_csv_strings_and_dates() {
+    let cmd = r#"rsv derive -e "key=concat(region, '-', substr(customer, 1))" -e "due=date(date) + 30" FILE"#;
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,date,customer,amount,key,due
+east,2024-01-02,c10,100,east-10,2024-02-01
+west,1/5/2024,c2,,west-2,2024-02-04
+east,2024-01-02,c2,50,east-2,2024-02-01
+east,12/31/2023,c1,30,east-1,2024-01-30
+west,2024-01-05,c1,200,west-1,2024-02-04
+east,2024-01-02,c2,70,east-2,2024-02-01
+"
+    );
+}
+
+#[test]
+fn test_derive_csv_chunks() {
+    let cmd = r#"rsv derive -e "price=round(avg_price_per_room * 1.1, 1)" FILE | rsv select -c 0,price | rsv tail -n 2"#;
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.csv"),
+        "Booking_ID,price\nINN36274,104\nINN36275,177.8\n"
+    );
+}
+
+#[test]
+fn test_derive_excel() {
+    let cmd = r#"rsv derive -e "n=no_of_adults + no_of_children" FILE | rsv select -c 0,n | rsv head -n 2"#;
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "Booking_ID,n\nINN00001,2\nINN00002,2\n"
+    );
+}
+
+#[test]
+fn test_derive_io() {
+    let cmd = r#"rsv slice FILE | rsv derive -e "amount=coalesce(amount, 0) % 60""#;
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,date,customer,amount
+east,2024-01-02,c10,40
+west,1/5/2024,c2,0
+east,2024-01-02,c2,50
+east,12/31/2023,c1,30
+west,2024-01-05,c1,20
+east,2024-01-02,c2,10
+"
+    );
+}
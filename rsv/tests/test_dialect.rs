@@ -0,0 +1,63 @@
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+// fields with a comma and escaped quotes
+const DATA: &str = "a,b,c\n1,\"x, y\",3\n2,\"say \"\"hi\"\"\",4\n";
+
+fn output_of_cmd(cmd: &str, dir: &str) -> String {
+    let dir = temp_dir_with(&format!("dialect-tests/{dir}"), &[("data.csv", DATA)]);
+    stdout_of(cmd_output(cmd, "", Some(&dir)))
+}
+
+#[test]
+fn test_selected_fields_are_quoted() {
+    assert_eq!(
+        output_of_cmd("rsv select -c b,c data.csv", "select"),
+        "b,c\n\"x, y\",3\n\"say \"\"hi\"\"\",4\n"
+    );
+    assert_eq!(
+        output_of_cmd("rsv search -p hi -o b data.csv", "search"),
+        "b\n\"say \"\"hi\"\"\"\n"
+    );
+}
+
+#[test]
+fn test_out_sep() {
+    assert_eq!(
+        output_of_cmd("rsv head --out-sep ';' data.csv", "head"),
+        "a;b;c\n1;x, y;3\n2;\"say \"\"hi\"\"\";4\n"
+    );
+    assert_eq!(
+        output_of_cmd(
+            "rsv select --out-sep '\\t' data.csv > d.tsv && rsv select -s '\\t' -c 0,1 d.tsv",
+            "tsv"
+        ),
+        "a\tb\n1\tx, y\n2\t\"say \"\"hi\"\"\"\n"
+    );
+}
+
+#[test]
+fn test_quote_style() {
+    assert_eq!(
+        output_of_cmd(
+            "rsv select -c a,b --quote-style non-numeric --escape-style backslash data.csv",
+            "non-numeric"
+        ),
+        "\"a\",\"b\"\n1,\"x, y\"\n2,\"say \\\"hi\\\"\"\n"
+    );
+    assert_eq!(
+        output_of_cmd("rsv select -c a --quote-style all --crlf data.csv", "all"),
+        "\"a\"\r\n\"1\"\r\n\"2\"\r\n"
+    );
+}
+
+#[test]
+fn test_unquoted_fields_are_not_unescaped() {
+    let dir = temp_dir_with(
+        "dialect-tests/unquoted",
+        &[("raw.csv", "a,b\n5\"\"x,\"y\"\"\"\n")],
+    );
+
+    let out = cmd_output("rsv select -c a,b --out-sep ';' raw.csv", "", Some(&dir));
+    assert_eq!(stdout_of(out), "a;b\n\"5\"\"\"\"x\";\"y\"\"\"\n");
+}
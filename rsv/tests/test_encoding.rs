@@ -0,0 +1,50 @@
+mod common;
+use crate::common::{output_of_cmd, output_of_cmd_in_temp_dir};
+
+// run the command in a temporary directory, since outputs are saved next to inputs
+#[test]
+fn test_gbk_detected() {
+    assert_eq!(
+        output_of_cmd("rsv select -f 城市=上海 FILE", "cities-gbk.csv"),
+        "城市,人口,日期\n上海,2487,2024年1月5日\n"
+    );
+    assert_eq!(
+        output_of_cmd("cat FILE | rsv head -n 1", "cities-gbk.csv"),
+        "城市,人口,日期\n北京,2154,2024年1月2日\n"
+    );
+}
+
+#[test]
+fn test_given_encoding() {
+    assert_eq!(
+        output_of_cmd("rsv headers --encoding gb18030 FILE", "cities-gbk.csv"),
+        output_of_cmd("rsv headers FILE", "cities-gbk.csv")
+    );
+
+    let cmd =
+        "printf 'name\\nJos\\351\\n' > latin1.csv && rsv tail -n 1 --encoding latin1 latin1.csv";
+    let out = output_of_cmd_in_temp_dir(cmd, "", "encoding-tests/latin1");
+    assert_eq!(out, "name\nJosé\n");
+}
+
+#[test]
+fn test_bom() {
+    let cmd = "printf '\\357\\273\\277a,b\\n1,2\\n' > bom.csv && rsv select -c a bom.csv";
+    let out = output_of_cmd_in_temp_dir(cmd, "", "encoding-tests/bom");
+    assert_eq!(out, "a\n1\n");
+
+    let cmd = "printf '\\377\\376a\\000,\\000b\\000\\n\\000' > utf16.csv && rsv headers utf16.csv";
+    let out = output_of_cmd_in_temp_dir(cmd, "", "encoding-tests/utf16");
+    assert_eq!(out, " 0    a\n 1    b\n");
+}
+
+#[test]
+fn test_out_encoding() {
+    let cmd = "rsv to utf8.csv FILE && rsv to out.csv --out-encoding gbk utf8.csv && cmp -s out.csv FILE && echo same";
+    let out = output_of_cmd_in_temp_dir(cmd, "cities-gbk.csv", "encoding-tests/out-gbk");
+    assert!(out.ends_with("same\n"));
+
+    let cmd = "printf 'a\\n\\360\\237\\230\\200\\n' | rsv to out.csv --out-encoding gbk; echo $?";
+    let out = output_of_cmd_in_temp_dir(cmd, "", "encoding-tests/unmappable");
+    assert_eq!(out, "4\n");
+}
@@ -0,0 +1,90 @@
+use execute::shell;
+use std::{env::temp_dir, fs::create_dir_all};
+mod common;
+use crate::common::{data_path, rsv};
+
+fn exit_code_of_cmd(cmd: &str) -> Option<i32> {
+    let cmd = cmd.replace("rsv", &rsv());
+    let cmd = cmd.replace("file", &data_path("sales.csv"));
+
+    shell(cmd).output().unwrap().status.code()
+}
+
+#[test]
+fn test_success() {
+    assert_eq!(exit_code_of_cmd("rsv select -c 0 file"), Some(0));
+}
+
+#[test]
+fn test_column_error() {
+    assert_eq!(exit_code_of_cmd("rsv select -c 9 file"), Some(6));
+    assert_eq!(exit_code_of_cmd("rsv select -c price file"), Some(6));
+}
+
+#[test]
+fn test_column_out_of_range() {
+    // a column index beyond the header is an error rather than a column of empty fields
+    let dir = temp_dir().join("exit-code-tests").join("out-of-range");
+    create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("abc.csv"), "a,b,c\n1,2,3\n4,5,6\n").unwrap();
+
+    for cmd in [
+        "groupby -k 99",
+        "groupby -a 'sum(99)'",
+        "pivot -r 0 -c 99",
+        "melt -i 99",
+        "derive -e 'x=c99'",
+        "sort -c 99",
+    ] {
+        let cmd = format!("cd {} && rsv {cmd} abc.csv", dir.display());
+        assert_eq!(exit_code_of_cmd(&cmd), Some(6), "{cmd}");
+    }
+}
+
+#[test]
+fn test_syntax_error() {
+    assert_eq!(exit_code_of_cmd("rsv select -f '0 ?? east' file"), Some(7));
+    assert_eq!(exit_code_of_cmd("rsv derive -e 'x=@0 + #' file"), Some(7));
+}
+
+#[test]
+fn test_bad_number() {
+    assert_eq!(exit_code_of_cmd("rsv select -f '3N>=abc' file"), Some(8));
+}
+
+#[test]
+fn test_io_error() {
+    assert_eq!(exit_code_of_cmd("rsv select not-existing.csv"), Some(3));
+}
+
+#[test]
+fn test_bad_format_error() {
+    let dir = temp_dir().join("exit-code-tests").join("bad-format");
+    create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("bad.parquet"), "a,b\n1,2\n").unwrap();
+
+    std::fs::write(dir.join("bad.json"), "[{\"a\": 1},").unwrap();
+
+    for f in ["bad.parquet", "bad.json"] {
+        let cmd = format!("rsv head {}", dir.join(f).display());
+        assert_eq!(exit_code_of_cmd(&cmd), Some(5), "{f}");
+    }
+}
+
+#[test]
+fn test_read_error_after_first_chunk() {
+    // rows are read in chunks in a thread, whose error is the error of the command
+    let dir = temp_dir().join("exit-code-tests");
+    create_dir_all(&dir).unwrap();
+    let bad = dir.join("bad-utf8.csv");
+    let cmd = format!(
+        "(echo a,b; seq 1 30000 | sed 's/$/,x/'; printf '\\377,1\\n') > {}",
+        bad.display()
+    );
+    shell(cmd).output().unwrap();
+
+    for cmd in ["select", "sort -c a", "stats", "frequency -c b"] {
+        let cmd = format!("rsv {cmd} {} > /dev/null", bad.display());
+        assert_eq!(exit_code_of_cmd(&cmd), Some(4));
+    }
+}
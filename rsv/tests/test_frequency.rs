@@ -13,7 +13,8 @@ fn end_row_of_cmd(cmd: &str, file: &str) -> String {
 
     String::from_utf8(output.stdout)
         .unwrap()
-        .split('\n').rfind(|i| !i.is_empty())
+        .split('\n')
+        .rfind(|i| !i.is_empty())
         .unwrap()
         .to_owned()
 }
@@ -65,3 +66,22 @@ fn test_frequency_two_column_ascending_excel() {
     let cmd = "rsv frequency -c 1,2 -n 6 -a file";
     assert_eq!(end_row_of_cmd(cmd, "hotel_reservation.xlsx"), "0,3,5");
 }
+
+#[test]
+fn test_frequency_quotes_fields() {
+    let dir = std::env::temp_dir().join("frequency-tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("data.csv"), "k,v\n\"a, b\",1\n\"a, b\",2\nc,3\n").unwrap();
+
+    let cmd = format!(
+        "cd {} && {} frequency data.csv && {} frequency --out-sep ';' data.csv",
+        dir.display(),
+        rsv(),
+        rsv()
+    );
+    let out = shell(cmd).output().unwrap();
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "k,n\n\"a, b\",2\nc,1\nk;n\na, b;2\nc;1\n"
+    );
+}
@@ -0,0 +1,89 @@
+use execute::shell;
+mod common;
+use crate::common::{output_of_cmd, rsv};
+
+#[test]
+fn test_groupby_csv_aggregations() {
+    let cmd = "rsv groupby -k region -a 'count,sum(amount),mean(3),min(amount),max(date),countd(customer),median(amount),count(amount)' FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,count,sum(amount),mean(amount),min(amount),max(date),countd(customer),median(amount),count(amount)
+east,4,250,62.5,30,2024-01-02,3,60,4
+west,2,200,200,200,2024-01-05,2,200,1
+"
+    );
+}
+
+#[test]
+fn test_groupby_csv_multi_keys() {
+    let cmd = "rsv groupby -k 0,2 FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,customer,count
+east,c1,1
+east,c10,1
+east,c2,2
+west,c1,1
+west,c2,1
+"
+    );
+}
+
+#[test]
+fn test_groupby_csv_chunks() {
+    let cmd = "rsv groupby -k type_of_meal_plan -a 'count,mean(avg_price_per_room),max(no_of_adults)' FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.csv"),
+        "type_of_meal_plan,count,mean(avg_price_per_room),max(no_of_adults)
+Meal Plan 1,27835,103.593139,4
+Meal Plan 2,3305,115.306106,3
+Meal Plan 3,5,41.2,2
+Not Selected,5130,94.90861,3
+"
+    );
+}
+
+#[test]
+fn test_groupby_excel() {
+    let cmd = "rsv groupby -k type_of_meal_plan -a 'count,max(no_of_adults)' FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "type_of_meal_plan,count,max(no_of_adults)
+Meal Plan 1,27835,4
+Meal Plan 2,3305,3
+Meal Plan 3,5,2
+Not Selected,5130,3
+"
+    );
+}
+
+#[test]
+fn test_groupby_io() {
+    let cmd = "rsv slice FILE | rsv groupby -k region -a 'sum(amount)'";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,sum(amount)\neast,250\nwest,200\n"
+    );
+}
+
+#[test]
+fn test_groupby_bad_lines() {
+    // a short row is skipped and reported on stderr
+    let cmd = format!("printf 'k,v\\na,1\\nb\\n' | {} groupby -a 'sum(v)'", rsv());
+    let out = shell(cmd).output().unwrap();
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "k,sum(v)\na,1\n");
+    assert_eq!(
+        String::from_utf8(out.stderr).unwrap(),
+        "[info] bad lines ignored: 1\n"
+    );
+}
+
+#[test]
+fn test_groupby_null_group() {
+    // sum is empty for a group without numeric values, like mean, min and max
+    let cmd = "printf 'k,v\\na,1\\nb,\\nb,NA\\n' | rsv groupby -a 'sum(v),mean(v),max(v)'";
+    assert_eq!(
+        output_of_cmd(cmd, ""),
+        "k,sum(v),mean(v),max(v)\na,1,1,1\nb,,,\n"
+    );
+}
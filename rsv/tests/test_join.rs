@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+mod common;
+use crate::common::{cmd_output, data_path, stdout_of, temp_dir_with};
+
+// run in the data directory, where LEFT and RIGHT are files of it
+fn output_of_join(cmd: &str) -> String {
+    let cmd = cmd
+        .replace("LEFT", "sales.csv")
+        .replace("RIGHT_EXCEL", "customers.xlsx")
+        .replace("RIGHT", "customers.csv");
+    let dir = PathBuf::from(data_path(""));
+    stdout_of(cmd_output(&cmd, "", Some(&dir))).replace('\r', "")
+}
+
+#[test]
+fn test_join_csv_inner() {
+    let cmd = "rsv join -k customer RIGHT LEFT";
+    assert_eq!(
+        output_of_join(cmd),
+        "region,date,customer,amount,name
+west,1/5/2024,c2,,Bob
+east,2024-01-02,c2,50,Bob
+east,12/31/2023,c1,30,Alice
+west,2024-01-05,c1,200,Alice
+east,2024-01-02,c2,70,Bob
+"
+    );
+}
+
+#[test]
+fn test_join_csv_full() {
+    let cmd = "rsv join --how full -k 2 -r 0 RIGHT LEFT";
+    let out = output_of_join(cmd);
+    assert!(out.contains("east,2024-01-02,c10,100,\n"));
+    assert!(out.ends_with(",,c3,,Carol\n"));
+    assert_eq!(out.lines().count(), 8);
+}
+
+#[test]
+fn test_join_csv_merge() {
+    let cmd = "rsv join --strategy merge --how left -k customer RIGHT LEFT";
+    assert_eq!(
+        output_of_join(cmd),
+        "region,date,customer,amount,name
+east,12/31/2023,c1,30,Alice
+west,2024-01-05,c1,200,Alice
+east,2024-01-02,c10,100,
+west,1/5/2024,c2,,Bob
+east,2024-01-02,c2,50,Bob
+east,2024-01-02,c2,70,Bob
+"
+    );
+}
+
+#[test]
+fn test_join_csv_semi_anti() {
+    let cmd = "rsv join --how semi -k customer RIGHT LEFT";
+    assert_eq!(output_of_join(cmd).lines().count(), 6);
+
+    let cmd = "rsv join --how anti -k customer RIGHT LEFT";
+    assert_eq!(
+        output_of_join(cmd),
+        "region,date,customer,amount\neast,2024-01-02,c10,100\n"
+    );
+
+    let cmd = "rsv join --strategy merge --how anti -k customer RIGHT LEFT";
+    assert_eq!(
+        output_of_join(cmd),
+        "region,date,customer,amount\neast,2024-01-02,c10,100\n"
+    );
+}
+
+#[test]
+fn test_join_excel_right() {
+    let cmd = "rsv join --how right -k customer RIGHT_EXCEL LEFT";
+    assert!(output_of_join(cmd).ends_with(",,c3,,Carol\n"));
+}
+
+#[test]
+fn test_join_io() {
+    let cmd = "rsv slice LEFT | rsv join --how anti -k customer RIGHT";
+    assert_eq!(
+        output_of_join(cmd),
+        "region,date,customer,amount\neast,2024-01-02,c10,100\n"
+    );
+
+    let cmd = "rsv slice LEFT | rsv join --strategy merge -k customer RIGHT";
+    assert_eq!(output_of_join(cmd).lines().count(), 6);
+}
+
+#[test]
+fn test_join_empty_keys() {
+    let dir = temp_dir_with(
+        "join-tests",
+        &[
+            ("left.csv", "id,x\n1,a\n,b\n"),
+            ("right.csv", "id,y\n,c\n1,d\n"),
+        ],
+    );
+
+    // an empty key matches no record, with either strategy
+    for strategy in ["hash", "merge"] {
+        let cmd = format!("rsv join --how full --strategy {strategy} right.csv left.csv");
+        let out = stdout_of(cmd_output(&cmd, "", Some(&dir)));
+        let mut rows = out.lines().skip(1).collect::<Vec<_>>();
+        rows.sort();
+        assert_eq!(rows, [",,c", ",b,", "1,a,d"]);
+    }
+}
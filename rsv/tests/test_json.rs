@@ -0,0 +1,83 @@
+mod common;
+use crate::common::{
+    cmd_output, output_of_cmd, output_of_cmd_in_temp_dir, stdout_of, temp_dir_with,
+};
+
+// run the command in a temporary directory, since <rsv to> saves to the current directory
+#[test]
+fn test_ndjson_flattened_columns() {
+    let cmd = "rsv select -c id,user.name,user.age,user.geo.lat FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "api.ndjson"),
+        "id,user.name,user.age,user.geo.lat
+1,Ann,30,
+2,Bob,,
+3,Cid,41,1.25
+"
+    );
+}
+
+#[test]
+fn test_ndjson_filter() {
+    let cmd = "rsv select -f 'user.age>=35 | active=true' -c id FILE";
+    assert_eq!(output_of_cmd(cmd, "api.ndjson"), "id\n2\n3\n");
+}
+
+#[test]
+fn test_csv_to_json() {
+    let cmd = "rsv to sales.json FILE && cat sales.json && rsv tail -n 1 sales.json";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv", "json-tests/csv-to-json");
+    assert!(out.ends_with(
+        r#"[
+{"region":"east","date":"2024-01-02","customer":"c10","amount":100},
+{"region":"west","date":"1/5/2024","customer":"c2","amount":null},
+{"region":"east","date":"2024-01-02","customer":"c2","amount":50},
+{"region":"east","date":"12/31/2023","customer":"c1","amount":30},
+{"region":"west","date":"2024-01-05","customer":"c1","amount":200},
+{"region":"east","date":"2024-01-02","customer":"c2","amount":70}
+]
+region,date,customer,amount
+east,2024-01-02,c2,70
+"#
+    ));
+}
+
+#[test]
+fn test_io_to_ndjson() {
+    let cmd = "rsv slice -e 2 FILE | rsv to out.ndjson && cat out.ndjson";
+    let out = output_of_cmd_in_temp_dir(cmd, "hotel_reservation.csv", "json-tests/io-to-ndjson");
+    assert!(out.ends_with(
+        r#"
+{"Booking_ID":"INN00001","no_of_adults":2,"no_of_children":0,"type_of_meal_plan":"Meal Plan 1","room_type_reserved":"Room_Type 1","market_segment_type":"Offline","avg_price_per_room":65,"no_of_special_requests":0}
+{"Booking_ID":"INN00002","no_of_adults":2,"no_of_children":0,"type_of_meal_plan":"Not Selected","room_type_reserved":"Room_Type 1","market_segment_type":"Online","avg_price_per_room":106.68,"no_of_special_requests":1}
+"#
+    ));
+}
+
+#[test]
+fn test_to_json_null_text() {
+    let dir = temp_dir_with(
+        "json-tests/null-text",
+        &[("names.csv", "name,n\nNA,NA\nnull,1\n,2\n")],
+    );
+
+    let cmd = "rsv to names.ndjson names.csv > /dev/null && cat names.ndjson";
+    assert_eq!(
+        stdout_of(cmd_output(cmd, "", Some(&dir))),
+        r#"{"name":"NA","n":null}
+{"name":"null","n":1}
+{"name":null,"n":2}
+"#
+    );
+}
+
+#[test]
+fn test_ndjson_leading_blank_lines() {
+    let dir = temp_dir_with(
+        "json-tests/blank-lines",
+        &[("a.ndjson", "\n  \n{\"a\":1}\n\n{\"a\":2}\n{\"a\":\n")],
+    );
+
+    let out = stdout_of(cmd_output("rsv head a.ndjson 2>&1", "", Some(&dir)));
+    assert!(out.contains("invalid JSON at line 6"));
+}
@@ -0,0 +1,58 @@
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+// a survey with one column per question
+const DATA: &str = "id,name,q1,q2,q3\n1,ann,a,b,c\n2,bob,\"x, y\",,z\n";
+
+fn output_of_cmd(cmd: &str, dir: &str) -> String {
+    let dir = temp_dir_with(&format!("melt-tests/{dir}"), &[("data.csv", DATA)]);
+    stdout_of(cmd_output(cmd, "", Some(&dir)))
+}
+
+#[test]
+fn test_melt() {
+    assert_eq!(
+        output_of_cmd("rsv melt --id 0,1 --value 2-3 data.csv", "melt"),
+        "id,name,variable,value\n1,ann,q1,a\n1,ann,q2,b\n2,bob,q1,\"x, y\"\n2,bob,q2,\n"
+    );
+    assert_eq!(
+        output_of_cmd(
+            "rsv slice data.csv | rsv melt -i id -v 'q*' --var-name question --value-name answer",
+            "melt-io"
+        ),
+        "id,question,answer\n1,q1,a\n1,q2,b\n1,q3,c\n2,q1,\"x, y\"\n2,q2,\n2,q3,z\n"
+    );
+}
+
+#[test]
+fn test_unmelt() {
+    assert_eq!(
+        output_of_cmd(
+            "rsv melt -i id,name data.csv > long.csv && rsv unmelt long.csv",
+            "unmelt"
+        ),
+        DATA
+    );
+    assert_eq!(
+        output_of_cmd("rsv melt -i id data.csv | rsv unmelt", "unmelt-io"),
+        DATA
+    );
+    assert_eq!(
+        output_of_cmd(
+            "rsv melt -i id,name data.csv > long.csv && rsv unmelt -i 0 -k variable -v value long.csv",
+            "unmelt-id"
+        ),
+        "id,q1,q2,q3\n1,a,b,c\n2,\"x, y\",,z\n"
+    );
+}
+
+#[test]
+fn test_melt_excel() {
+    assert_eq!(
+        output_of_cmd(
+            "rsv melt -i 0 data.csv | rsv to long.xlsx > /dev/null && rsv unmelt long.xlsx",
+            "excel"
+        ),
+        DATA
+    );
+}
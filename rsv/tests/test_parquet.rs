@@ -0,0 +1,66 @@
+mod common;
+use crate::common::{output_of_cmd, output_of_cmd_in_temp_dir};
+
+// run the command in a temporary directory, since <rsv to> saves to the current directory
+#[test]
+fn test_parquet_head() {
+    let cmd = "rsv head -n 3 FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.parquet"),
+        "region,date,customer,amount
+east,2024-01-02,c10,100
+west,2024-01-05,c2,
+east,2024-01-02,c2,50
+"
+    );
+}
+
+#[test]
+fn test_parquet_row_groups() {
+    // rows span two row groups, which are read one at a time
+    let cmd = "(echo id; seq 1 150000) > ids.csv && rsv to ids.parquet ids.csv > /dev/null && rsv slice -s 99999 -e 100002 ids.parquet && rsv tail -n 1 ids.parquet";
+    assert_eq!(
+        output_of_cmd_in_temp_dir(cmd, "sales.parquet", "parquet-tests/row-groups"),
+        "id\n100000\n100001\n100002\nid\n150000\n"
+    );
+}
+
+#[test]
+fn test_parquet_select_and_groupby() {
+    let cmd = "rsv select -f region=east -c customer,amount FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.parquet"),
+        "customer,amount\nc10,100\nc2,50\nc1,30\nc2,70\n"
+    );
+
+    let cmd = "rsv groupby -k date -a 'sum(amount)' FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.parquet"),
+        "date,sum(amount)\n2023-12-31,30\n2024-01-02,220\n2024-01-05,200\n"
+    );
+}
+
+#[test]
+fn test_csv_to_parquet() {
+    let cmd = "rsv to sales.parquet -d 1 FILE && rsv head -n 2 sales.parquet";
+    let out = output_of_cmd_in_temp_dir(cmd, "sales.csv", "parquet-tests/csv-to-parquet");
+    assert!(out.ends_with(
+        "region,date,customer,amount
+east,2024-01-02,c10,100
+west,2024-01-05,c2,
+"
+    ));
+}
+
+#[test]
+fn test_io_and_parquet_to_parquet() {
+    let cmd = "rsv slice -e 2 FILE | rsv to io.parquet && rsv to copy.parquet io.parquet && rsv to csv copy.parquet && cat export.csv";
+    let out =
+        output_of_cmd_in_temp_dir(cmd, "hotel_reservation.csv", "parquet-tests/io-to-parquet");
+    assert!(out.ends_with(
+        "Booking_ID,no_of_adults,no_of_children,type_of_meal_plan,room_type_reserved,market_segment_type,avg_price_per_room,no_of_special_requests
+INN00001,2,0,Meal Plan 1,Room_Type 1,Offline,65,0
+INN00002,2,0,Not Selected,Room_Type 1,Online,106.68,1
+"
+    ));
+}
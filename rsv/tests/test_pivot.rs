@@ -0,0 +1,79 @@
+mod common;
+use crate::common::{output_of_cmd, output_of_cmd_in_temp_dir};
+
+#[test]
+fn test_pivot_csv_totals() {
+    let cmd = "rsv pivot -r region -c customer -v amount -a sum --totals FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,c1,c10,c2,total
+east,30,100,120,250
+west,200,,,200
+total,230,100,120,450
+"
+    );
+}
+
+#[test]
+fn test_pivot_csv_percent() {
+    let cmd = "rsv pivot -r 0 -c 2 --percent total FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,c1,c10,c2
+east,16.666667,16.666667,33.333333
+west,16.666667,,16.666667
+"
+    );
+}
+
+#[test]
+fn test_pivot_csv_chunks() {
+    let cmd =
+        "rsv pivot -r type_of_meal_plan -c no_of_adults -v avg_price_per_room -a mean -t FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.csv")
+            .lines()
+            .map(|l| l.rsplit(',').next().unwrap())
+            .collect::<Vec<_>>(),
+        [
+            "total",
+            "103.593139",
+            "115.306106",
+            "41.2",
+            "94.90861",
+            "103.423539"
+        ]
+    );
+}
+
+#[test]
+fn test_pivot_excel() {
+    let cmd = "rsv pivot -r type_of_meal_plan -c no_of_adults FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "type_of_meal_plan,0,1,2,3,4
+Meal Plan 1,137,6469,19050,2163,16
+Meal Plan 2,2,535,2645,123,
+Meal Plan 3,,,5,,
+Not Selected,,691,4408,31,
+"
+    );
+}
+
+#[test]
+fn test_pivot_io() {
+    let cmd = "rsv slice FILE | rsv pivot -r region -c customer -v amount -a max -p col";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,c1,c10,c2\neast,15,100,100\nwest,100,,\n"
+    );
+}
+
+#[test]
+fn test_pivot_export_xlsx() {
+    let cmd = "cp FILE sales.csv && rsv pivot -r 0 -c 2 -v 3 -a sum --xlsx sales.csv > /dev/null && rsv head sales-pivot.xlsx";
+    assert_eq!(
+        output_of_cmd_in_temp_dir(cmd, "sales.csv", "pivot-tests/xlsx"),
+        "region,c1,c10,c2\neast,30,100,120\nwest,200,,\n"
+    );
+}
@@ -0,0 +1,97 @@
+use std::process::Output;
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+const SCHEMA: &str = r#"
+[[columns]]
+name = "region"
+values = ["east"]
+
+[[columns]]
+name = "date"
+type = "date"
+date_format = "%Y-%m-%d"
+
+[[columns]]
+name = "amount"
+type = "float"
+nullable = false
+max = 150
+"#;
+
+// run the command in a temporary directory, where schema files are saved
+fn output_of_cmd_in_temp_dir(cmd: &str, dir: &str) -> Output {
+    let dir = temp_dir_with(&format!("schema-tests/{dir}"), &[("schema.toml", SCHEMA)]);
+    cmd_output(cmd, "sales.csv", Some(&dir))
+}
+
+fn stdout_of_cmd_in_temp_dir(cmd: &str, dir: &str) -> String {
+    stdout_of(output_of_cmd_in_temp_dir(cmd, dir))
+}
+
+#[test]
+fn test_infer() {
+    assert_eq!(
+        stdout_of_cmd_in_temp_dir("rsv schema infer FILE", "infer"),
+        r#"[[columns]]
+name = "region"
+type = "string"
+nullable = false
+values = ["east", "west"]
+
+[[columns]]
+name = "date"
+type = "date"
+nullable = false
+
+[[columns]]
+name = "customer"
+type = "string"
+nullable = false
+values = ["c10", "c2", "c1"]
+
+[[columns]]
+name = "amount"
+type = "int"
+nullable = true
+min = 30
+max = 200
+"#
+    );
+}
+
+#[test]
+fn test_inferred_schema_is_valid() {
+    let cmd = "rsv schema infer -o inferred.json FILE && rsv validate --schema inferred.json FILE";
+    let out = output_of_cmd_in_temp_dir(cmd, "round-trip");
+    assert_eq!(out.status.code(), Some(0));
+}
+
+#[test]
+fn test_validate_by_schema() {
+    let out = output_of_cmd_in_temp_dir("rsv validate --schema schema.toml FILE", "validate");
+    assert_eq!(out.status.code(), Some(9));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "line,issue,detail\n\
+        3,value,<region> should be one of east: west\n\
+        3,type,<date> should be date of format %Y-%m-%d: 1/5/2024\n\
+        3,null,<amount> should not be null\n\
+        5,type,<date> should be date of format %Y-%m-%d: 12/31/2023\n\
+        6,value,<region> should be one of east: west\n\
+        6,range,\"<amount> should be in [, 150]: 200\"\n"
+    );
+}
+
+#[test]
+fn test_stats_by_schema() {
+    let out = stdout_of_cmd_in_temp_dir("rsv stats -c amount --schema schema.toml FILE", "stats");
+    assert!(out.contains("│ amount │ float │"));
+}
+
+#[test]
+fn test_missing_column() {
+    let cmd = "printf 'id\\n1\\n' | rsv stats --schema schema.toml";
+    let out = output_of_cmd_in_temp_dir(cmd, "missing");
+    assert_eq!(out.status.code(), Some(6));
+}
@@ -187,3 +187,109 @@ fn test_select_io_column_filter3() {
         "INN36275,0,Room_Type 1"
     );
 }
+
+#[test]
+fn test_select_csv_multi_line_record1() {
+    let cmd = "rsv select -f 0=3 -c 0,3 file";
+    assert_eq!(end_row_of_cmd(cmd, "multi-line.csv"), "3,30");
+}
+
+#[test]
+fn test_select_csv_multi_line_record2() {
+    let cmd = "rsv select -f \"3N>=20\" -c 1 file";
+    assert_eq!(end_row_of_cmd(cmd, "multi-line.csv"), "Dan");
+}
+
+#[test]
+fn test_select_io_multi_line_record() {
+    let cmd = "rsv head FILE | rsv select -f 0=3 -c 0,3";
+    assert_eq!(end_row_of_cmd(cmd, "multi-line.csv"), "3,30");
+}
+
+#[test]
+fn test_select_csv_column_name1() {
+    let cmd = "rsv select -f no_of_adultsN=2 -c Booking_ID,no_of_children,room_type_reserved file";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "INN36275,0,Room_Type 1"
+    );
+}
+
+#[test]
+fn test_select_csv_column_name2() {
+    let cmd = "rsv select -c type_of_meal_plan-market_segment_type file";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "Meal Plan 1,Room_Type 1,Offline"
+    );
+}
+
+#[test]
+fn test_select_csv_column_pattern() {
+    let cmd = "rsv select -c \"no_of_*\" file";
+    assert_eq!(end_row_of_cmd(cmd, "hotel_reservation.csv"), "2,0,0");
+
+    let cmd = "rsv select -c '/_type$/' file";
+    assert_eq!(end_row_of_cmd(cmd, "hotel_reservation.csv"), "Offline");
+}
+
+#[test]
+fn test_select_excel_column_name() {
+    let cmd = "rsv select -f no_of_adultsN=2 -c Booking_ID,no_of_children,room_type_reserved file";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "INN36275,0,Room_Type 1"
+    );
+}
+
+#[test]
+fn test_select_io_column_name() {
+    let cmd = "rsv slice file | rsv select -f no_of_adultsN=2 -c Booking_ID,no_of_children,room_type_reserved";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "INN36275,0,Room_Type 1"
+    );
+}
+
+#[test]
+fn test_select_csv_filter_expr() {
+    let cmd = "rsv select -f 'customer=c1 | amountN>90' file | rsv count";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "3");
+
+    let cmd = "rsv select -f '(customer=c1 | amountN>90) & !(region ~ \"^w\")' file";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "east,12/31/2023,c1,30");
+
+    let cmd = "rsv select -f 'is_null(amount) | contains(date, \"/2023\")' file | rsv count";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "2");
+}
+
+#[test]
+fn test_select_csv_filter_in_between() {
+    let cmd = "rsv select -f 'customer not in (c1, c2)' file";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "east,2024-01-02,c10,100");
+
+    let cmd = "rsv select -f 'amountN between 50 and 100' file | rsv count";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "3");
+
+    let cmd = "rsv select -f 'regionI=WEST & starts_with(date, 1)' file";
+    assert_eq!(end_row_of_cmd(cmd, "sales.csv"), "west,1/5/2024,c2,");
+}
+
+#[test]
+fn test_select_excel_filter_expr() {
+    let cmd = "rsv select -f 'no_of_adultsN=3 & type_of_meal_planI ~ \"plan 2\"' -c 0-3 file";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "INN36183,3,0,Meal Plan 2"
+    );
+}
+
+#[test]
+fn test_select_io_filter_expr() {
+    let cmd =
+        "rsv slice file | rsv select -f 'starts_with(market_segment_type, Com) & !is_null(0)'";
+    assert_eq!(
+        end_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "INN36166,1,0,Meal Plan 1,Room_Type 1,Complementary,79,0"
+    );
+}
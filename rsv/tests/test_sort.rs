@@ -0,0 +1,99 @@
+mod common;
+use crate::common::output_of_cmd;
+
+fn first_row_of_cmd(cmd: &str, file: &str) -> String {
+    output_of_cmd(cmd, file)
+        .split('\n')
+        .nth(1)
+        .unwrap_or_default()
+        .to_owned()
+}
+
+#[test]
+fn test_sort_csv_multi_columns() {
+    let cmd = "rsv sort -c region,dateT,customerV,amountDN FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv").replace('\r', ""),
+        "region,date,customer,amount
+east,12/31/2023,c1,30
+east,2024-01-02,c2,70
+east,2024-01-02,c2,50
+east,2024-01-02,c10,100
+west,2024-01-05,c1,200
+west,1/5/2024,c2,
+"
+    );
+}
+
+#[test]
+fn test_sort_csv_nulls() {
+    let cmd = "rsv sort -c amountN FILE";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv")
+            .trim_end()
+            .rsplit('\n')
+            .next(),
+        Some("west,1/5/2024,c2,")
+    );
+
+    let cmd = "rsv sort -c amountNF FILE";
+    assert_eq!(first_row_of_cmd(cmd, "sales.csv"), "west,1/5/2024,c2,");
+
+    let cmd = "rsv sort -c amountDNF FILE";
+    assert_eq!(first_row_of_cmd(cmd, "sales.csv"), "west,1/5/2024,c2,");
+
+    // a string sort compares an empty field as it is
+    let cmd = "rsv sort -c amount FILE";
+    assert_eq!(first_row_of_cmd(cmd, "sales.csv"), "west,1/5/2024,c2,");
+
+    let cmd = "rsv sort -c amountF FILE";
+    assert_eq!(output_of_cmd(cmd, "sales.csv"), "");
+}
+
+#[test]
+fn test_sort_csv_three_columns() {
+    let cmd = "rsv sort -c 1DN,2DN,6N FILE";
+    assert_eq!(
+        first_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "INN23367,4,0,Meal Plan 1,Room_Type 7,Complementary,0,4"
+    );
+}
+
+#[test]
+fn test_sort_excel_three_columns() {
+    let cmd = "rsv sort -c 1DN,2DN,6N FILE";
+    assert_eq!(
+        first_row_of_cmd(cmd, "hotel_reservation.xlsx"),
+        "INN23367,4,0,Meal Plan 1,Room_Type 7,Complementary,0,4"
+    );
+}
+
+#[test]
+fn test_sort_io_three_columns() {
+    let cmd = "rsv slice FILE | rsv sort -c 1DN,2DN,6N";
+    assert_eq!(
+        first_row_of_cmd(cmd, "hotel_reservation.csv"),
+        "INN23367,4,0,Meal Plan 1,Room_Type 7,Complementary,0,4"
+    );
+}
+
+#[test]
+fn test_sort_csv_external() {
+    // a zero memory budget spills every chunk to a sorted run
+    let cmd = "rsv sort -c 1DN,2DN,6N FILE";
+    let external = "rsv sort -m 0 -c 1DN,2DN,6N FILE";
+    assert_eq!(
+        output_of_cmd(external, "hotel_reservation.csv"),
+        output_of_cmd(cmd, "hotel_reservation.csv")
+    );
+}
+
+#[test]
+fn test_sort_io_external() {
+    let cmd = "rsv sort -c 1DN,2DN,6N FILE";
+    let external = "rsv slice FILE | rsv sort -m 0 -c 1DN,2DN,6N";
+    assert_eq!(
+        output_of_cmd(external, "hotel_reservation.csv"),
+        output_of_cmd(cmd, "hotel_reservation.csv")
+    );
+}
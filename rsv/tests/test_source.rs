@@ -0,0 +1,46 @@
+mod common;
+use crate::common::{cmd_output, output_of_cmd, stdout_of, temp_dir_with};
+
+#[test]
+fn test_same_output_for_all_sources() {
+    for cmd in [
+        "head -n 2",
+        "tail -n 2",
+        "flatten -n 2",
+        "flatten --no-header -n 1",
+    ] {
+        let csv = output_of_cmd(&format!("rsv {cmd} FILE"), "customers.csv");
+        assert_eq!(
+            output_of_cmd(&format!("rsv {cmd} FILE"), "customers.xlsx"),
+            csv
+        );
+        assert_eq!(
+            output_of_cmd(&format!("cat FILE | rsv {cmd}"), "customers.csv"),
+            csv
+        );
+    }
+}
+
+#[test]
+fn test_tail_with_separator() {
+    let cmd = "cat FILE | tr ',' '\\t' | rsv tail -n 1 -s '\\t' | rsv select -s '\\t' -c name";
+    assert_eq!(output_of_cmd(cmd, "customers.csv"), "name\nCarol\n");
+}
+
+#[test]
+fn test_sheet_cells_quoted_as_csv() {
+    // quotes, commas and line breaks in cells are quoted as in RFC 4180
+    let csv = "name,n\n\"say \"\"hi\"\"\",1\n\"a\nb\",2\n\"x, y\",3\n";
+    let dir = temp_dir_with("source-tests/quoted-cells", &[("quotes.csv", csv)]);
+
+    for f in ["quotes.xlsx", "quotes.parquet"] {
+        let cmd = format!("rsv to {f} quotes.csv > /dev/null && rsv head {f}");
+        assert_eq!(stdout_of(cmd_output(&cmd, "", Some(&dir))), csv);
+
+        let cmd = format!("rsv select -f 'name=say \"hi\"' {f}");
+        assert_eq!(
+            stdout_of(cmd_output(&cmd, "", Some(&dir))),
+            "name,n\n\"say \"\"hi\"\"\",1\n"
+        );
+    }
+}
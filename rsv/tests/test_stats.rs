@@ -0,0 +1,57 @@
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+// run the command in a temporary directory with small data
+fn output_of_cmd(cmd: &str, dir: &str) -> String {
+    let data = (1..=100).map(|i| format!("{i},s{i}\n")).collect::<String>();
+    let data = format!("v,name\n{data}");
+    let dir = temp_dir_with(&format!("stats-tests/{dir}"), &[("data.csv", &data)]);
+    stdout_of(cmd_output(cmd, "", Some(&dir))).replace('\r', "")
+}
+
+#[test]
+fn test_export_percentiles() {
+    let out = output_of_cmd(
+        "rsv stats -E -p 25,99 data.csv > /dev/null && cat data-stats.csv",
+        "export",
+    );
+    let lines = out
+        .lines()
+        .filter(|l| !l.contains("Saved") && !l.contains("Total"));
+    assert_eq!(
+        lines.collect::<Vec<_>>(),
+        [
+            "col,type,min,max,mean,sum,stddev,variance,median,p25,p99,unique,null,min_length,max_length",
+            "v,int,1,100,50.50,5050,29.01,841.67,50,25,99,100,0,1,3",
+            "name,string,s1,s99,-,-,-,-,-,-,-,100,0,2,4",
+        ]
+    );
+}
+
+#[test]
+fn test_histogram() {
+    let out = output_of_cmd("rsv stats -c v --histogram 4 data.csv", "histogram");
+    assert!(out.contains(
+        "v
+  [1, 25.75)      ######################################## 25
+  [25.75, 50.50)  ######################################## 25
+  [50.50, 75.25)  ######################################## 25
+  [75.25, 100]    ######################################## 25
+"
+    ));
+}
+
+#[test]
+fn test_invalid_percentile() {
+    let out = output_of_cmd("rsv stats -p 101 data.csv", "invalid");
+    assert!(!out.contains("Total rows"));
+}
+
+#[test]
+fn test_bad_lines_reported_on_stderr() {
+    let out = output_of_cmd(
+        "printf 'a,b\\n1,2\\n3\\n' | rsv stats 2>&1 > /dev/null",
+        "bad-lines",
+    );
+    assert_eq!(out, "[info] bad lines ignored: 1\n");
+}
@@ -0,0 +1,37 @@
+mod common;
+use crate::common::output_of_cmd;
+
+#[test]
+fn test_stdin_stats() {
+    let cmd = "cat FILE | rsv stats -c 1,2,6";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.csv"),
+        "┌────────────────────┬───────┬──────┬────────┬────────┬────────┬─────────┬────────┬──────┐
+│ col                │ type  │ min  │ max    │ mean   │ stddev │ ~median │ unique │ null │
+├────────────────────┼───────┼──────┼────────┼────────┼────────┼─────────┼────────┼──────┤
+│ no_of_adults       │ int   │ 0    │ 4      │ 1.84   │ 0.52   │ 2       │ 5      │ 0    │
+│ no_of_children     │ int   │ 0    │ 10     │ 0.11   │ 0.40   │ 0       │ 6      │ 0    │
+│ avg_price_per_room │ float │ 0.00 │ 540.00 │ 103.42 │ 35.09  │ 99.45   │ -      │ 0    │
+└────────────────────┴───────┴──────┴────────┴────────┴────────┴─────────┴────────┴──────┘
+Total rows: 36275
+"
+    );
+}
+
+#[test]
+fn test_stdin_tail() {
+    let cmd = "cat FILE | rsv tail -n 2 | rsv select -c 0";
+    assert_eq!(
+        output_of_cmd(cmd, "hotel_reservation.csv"),
+        "Booking_ID\nINN36274\nINN36275\n"
+    );
+}
+
+#[test]
+fn test_stdin_unique_keep_last() {
+    let cmd = "cat FILE | rsv unique -c region --keep-last";
+    assert_eq!(
+        output_of_cmd(cmd, "sales.csv"),
+        "region,date,customer,amount\nwest,2024-01-05,c1,200\neast,2024-01-02,c2,70\n"
+    );
+}
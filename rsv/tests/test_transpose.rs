@@ -0,0 +1,55 @@
+mod common;
+use crate::common::{cmd_output, stdout_of, temp_dir_with};
+
+// a long row and a short row
+const DATA: &str = "id,name,q1\n1,ann,a\n2,bob,\"x, y\",extra\n3\n";
+
+fn output_of_cmd(cmd: &str, dir: &str) -> String {
+    let dir = temp_dir_with(&format!("transpose-tests/{dir}"), &[("data.csv", DATA)]);
+    stdout_of(cmd_output(cmd, "", Some(&dir)))
+}
+
+#[test]
+fn test_transpose() {
+    let expected = "id,1,2,3\nname,ann,bob,\nq1,a,\"x, y\",\n,,extra,\n";
+    assert_eq!(output_of_cmd("rsv transpose data.csv", "memory"), expected);
+    assert_eq!(
+        output_of_cmd("rsv transpose --max-memory 0 data.csv", "passes"),
+        expected
+    );
+    assert_eq!(
+        output_of_cmd("cat data.csv | rsv transpose", "io"),
+        expected
+    );
+    assert_eq!(
+        output_of_cmd("cat data.csv | rsv transpose --max-memory 0", "io-passes"),
+        expected
+    );
+}
+
+#[test]
+fn test_transpose_no_header() {
+    let expected = "id,1,2,3\nname,ann,bob,\nq1,a,\"x, y\",\n,,extra,\n";
+    assert_eq!(
+        output_of_cmd("rsv transpose --no-header data.csv", "no-header"),
+        expected
+    );
+    assert_eq!(
+        output_of_cmd(
+            "rsv transpose --no-header -m 0 --out-sep ';' data.csv",
+            "no-header-passes"
+        ),
+        expected.replace(',', ";").replace("\"x; y\"", "x, y")
+    );
+}
+
+#[test]
+fn test_transpose_excel() {
+    assert_eq!(
+        output_of_cmd(
+            "rsv transpose --xlsx data.csv > /dev/null && rsv transpose data-transposed.xlsx",
+            "excel"
+        ),
+        "id,name,q1,\n1,ann,a,\n2,bob,\"x, y\",extra\n3,,,\n"
+    );
+}
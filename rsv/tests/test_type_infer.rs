@@ -0,0 +1,62 @@
+use std::process::Output;
+mod common;
+use crate::common::{cmd_output, temp_dir_with};
+
+// integers until a N/A beyond the first 5000 rows
+fn run_cmd(cmd: &str, dir: &str) -> Output {
+    let mut data = "id,v\n".to_owned();
+    (0..6000).for_each(|i| data.push_str(&format!("{i},{i}\n")));
+    data.push_str("6000,N/A\n");
+    let dir = temp_dir_with(&format!("type-infer-tests/{dir}"), &[("data.csv", &data)]);
+    cmd_output(cmd, "", Some(&dir))
+}
+
+fn column_type(out: &Output, col: &str) -> String {
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout
+        .lines()
+        .find(|l| l.contains(&format!(" {col} ")))
+        .unwrap();
+    line.split('│').nth(2).unwrap().trim().to_owned()
+}
+
+#[test]
+fn test_stats_full_scan() {
+    let out = run_cmd("rsv stats --full-scan data.csv", "stats");
+    assert_eq!(column_type(&out, "id"), "int");
+    assert_eq!(column_type(&out, "v"), "string");
+
+    let out = run_cmd(
+        "rsv stats --full-scan --confidence 0.99 data.csv",
+        "confidence",
+    );
+    assert_eq!(column_type(&out, "v"), "int");
+    assert!(String::from_utf8_lossy(&out.stderr).contains(
+        "[info] column <v> is inferred as int, with 1 of 6001 values violating the type."
+    ));
+}
+
+#[test]
+fn test_stats_full_scan_stdin() {
+    let out = run_cmd(
+        "cat data.csv | rsv stats --full-scan --confidence 0.99",
+        "stdin",
+    );
+    assert_eq!(column_type(&out, "v"), "int");
+}
+
+#[test]
+fn test_to_full_scan() {
+    let out = run_cmd(
+        "rsv to out.parquet --full-scan data.csv && rsv to csv out.parquet && rsv tail -n 1 export.csv",
+        "to",
+    );
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("6000,N/A"));
+}
+
+#[test]
+fn test_invalid_confidence() {
+    let out = run_cmd("rsv stats --full-scan --confidence 1.5 data.csv", "invalid");
+    assert!(!out.status.success());
+}
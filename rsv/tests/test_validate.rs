@@ -0,0 +1,77 @@
+use std::process::Output;
+mod common;
+use crate::common::{cmd_output, temp_dir_with};
+
+// run the command in a temporary directory, where test files are created
+fn output_of_cmd_in_temp_dir(cmd: &str, dir: &str) -> Output {
+    let dir = temp_dir_with(&format!("validate-tests/{dir}"), &[]);
+    cmd_output(cmd, "sales.csv", Some(&dir))
+}
+
+#[test]
+fn test_clean_file() {
+    let out = output_of_cmd_in_temp_dir("rsv validate FILE", "clean");
+    assert_eq!(out.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "line,issue,detail\n"
+    );
+    assert_eq!(
+        String::from_utf8(out.stderr).unwrap(),
+        "6 rows, no problem found.\n"
+    );
+}
+
+#[test]
+fn test_problems() {
+    let cmd = r#"printf 'a,,a\n1,2,3\n4,5\n6,7,"8\n' > bad.csv && rsv validate bad.csv"#;
+    let out = output_of_cmd_in_temp_dir(cmd, "problems");
+    assert_eq!(out.status.code(), Some(9));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "line,issue,detail\n\
+        1,empty-header,column 1 has an empty name\n\
+        1,duplicate-header,column 2 has a duplicate name <a>\n\
+        3,field-count,\"2 fields, while the first row has 3\"\n\
+        4,unbalanced-quote,quoted field is not closed until the end of file\n"
+    );
+    assert_eq!(
+        String::from_utf8(out.stderr).unwrap(),
+        "Error: 3 rows, 4 problems found: 1 empty-header, 1 duplicate-header, 1 field-count, 1 unbalanced-quote.\n"
+    );
+}
+
+#[test]
+fn test_encoding_and_line_endings() {
+    let cmd = r#"printf '\357\273\277a,b\r\n1,2 \n\377,3\n' | rsv validate"#;
+    let out = output_of_cmd_in_temp_dir(cmd, "encoding");
+    assert_eq!(out.status.code(), Some(9));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "line,issue,detail\n\
+        1,bom,file starts with a UTF-8 BOM\n\
+        2,mixed-line-endings,\"line ends with \\n, while line 1 ends with \\r\\n\"\n\
+        2,trailing-whitespace,field 1 ends with whitespace\n\
+        3,mixed-line-endings,\"line ends with \\n, while line 1 ends with \\r\\n\"\n\
+        3,invalid-utf8,invalid byte at position 0\n"
+    );
+}
+
+#[test]
+fn test_stdin_encoding() {
+    let cmd = r#"printf 'name,city\nJos\351 ,Paris\n' | rsv validate --encoding latin1"#;
+    let out = output_of_cmd_in_temp_dir(cmd, "stdin-encoding");
+    assert_eq!(out.status.code(), Some(9));
+    assert_eq!(
+        String::from_utf8(out.stdout).unwrap(),
+        "line,issue,detail\n2,trailing-whitespace,field 0 ends with whitespace\n"
+    );
+}
+
+#[test]
+fn test_reject_file() {
+    let cmd = r#"printf 'a,b\n1,2\n3\n4,5,6\n7,8\n' > bad.csv && rsv validate -r rejected.csv bad.csv; cat rejected.csv"#;
+    let out = output_of_cmd_in_temp_dir(cmd, "reject");
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.ends_with("a,b\n3\n4,5,6\n"));
+}